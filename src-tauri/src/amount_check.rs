@@ -0,0 +1,282 @@
+use crate::byte_to_char_index;
+use crate::TextIssue;
+use crate::MAX_ISSUES;
+use regex::Regex;
+
+const UPPER_DIGITS: [&str; 10] = ["零", "壹", "贰", "叁", "肆", "伍", "陆", "柒", "捌", "玖"];
+const SMALL_UNITS: [&str; 4] = ["", "拾", "佰", "仟"];
+const BIG_UNITS: [&str; 4] = ["", "万", "亿", "万亿"];
+
+// 支持的最大金额（元），超出万亿级别不再转换
+const MAX_YUAN: u64 = 9_999_999_999_999_999;
+
+// 把 0..=9999 的四位数转换为中文大写
+fn group_to_upper(group: u64) -> String {
+    let mut result = String::new();
+    let mut pending_zero = false;
+
+    for pos in (0..4).rev() {
+        let digit = (group / 10u64.pow(pos as u32) % 10) as usize;
+        if digit == 0 {
+            pending_zero = true;
+            continue;
+        }
+        if pending_zero && !result.is_empty() {
+            result.push_str(UPPER_DIGITS[0]);
+        }
+        pending_zero = false;
+        result.push_str(UPPER_DIGITS[digit]);
+        result.push_str(SMALL_UNITS[pos]);
+    }
+
+    result
+}
+
+// 把整数部分转换为中文大写
+fn integer_to_upper(mut value: u64) -> String {
+    if value == 0 {
+        return UPPER_DIGITS[0].to_string();
+    }
+
+    let mut groups = Vec::new();
+    while value > 0 {
+        groups.push(value % 10_000);
+        value /= 10_000;
+    }
+
+    let mut result = String::new();
+    let mut pending_zero = false;
+    for (idx, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            pending_zero = !result.is_empty();
+            continue;
+        }
+        // 高位已有内容且本组不足千位时需要补"零"
+        if !result.is_empty() && (pending_zero || group < 1000) {
+            result.push_str(UPPER_DIGITS[0]);
+        }
+        pending_zero = false;
+        result.push_str(&group_to_upper(group));
+        result.push_str(BIG_UNITS[idx]);
+    }
+
+    result
+}
+
+/// 把以分为单位的金额转换为中文大写金额，例如 100000 -> "壹仟元整"
+pub fn amount_to_chinese_upper(cents: u64) -> Option<String> {
+    let yuan = cents / 100;
+    let jiao = (cents / 10 % 10) as usize;
+    let fen = (cents % 10) as usize;
+
+    if yuan > MAX_YUAN {
+        return None;
+    }
+
+    let mut result = String::new();
+    if yuan > 0 || (jiao == 0 && fen == 0) {
+        result.push_str(&integer_to_upper(yuan));
+        result.push('元');
+    }
+
+    if jiao == 0 && fen == 0 {
+        result.push('整');
+        return Some(result);
+    }
+
+    if jiao > 0 {
+        result.push_str(UPPER_DIGITS[jiao]);
+        result.push('角');
+    } else if yuan > 0 {
+        result.push_str(UPPER_DIGITS[0]);
+    }
+
+    if fen > 0 {
+        result.push_str(UPPER_DIGITS[fen]);
+        result.push('分');
+    }
+
+    Some(result)
+}
+
+// 解析中文大写金额，返回以分为单位的数值
+fn parse_chinese_upper(text: &str) -> Option<u64> {
+    // 已确定的亿以上部分
+    let mut total: u64 = 0;
+    // 亿以下、万以上的部分，遇到"亿"时与 section 一起乘以一亿
+    let mut below_yi: u64 = 0;
+    let mut section: u64 = 0;
+    let mut number: u64 = 0;
+    let mut yuan: Option<u64> = None;
+    let mut jiao: u64 = 0;
+    let mut fen: u64 = 0;
+
+    for c in text.chars() {
+        if let Some(digit) = UPPER_DIGITS.iter().position(|d| d.starts_with(c)) {
+            number = digit as u64;
+            continue;
+        }
+
+        match c {
+            '拾' | '佰' | '仟' => {
+                let unit = match c {
+                    '拾' => 10,
+                    '佰' => 100,
+                    _ => 1000,
+                };
+                // "拾元" 这类省略了"壹"的写法
+                let base = if number == 0 { 1 } else { number };
+                section = section.checked_add(base.checked_mul(unit)?)?;
+                number = 0;
+            }
+            '万' => {
+                below_yi = below_yi.checked_add((section + number).checked_mul(10_000)?)?;
+                section = 0;
+                number = 0;
+            }
+            '亿' => {
+                // "壹万贰仟亿" 中万和仟都属于亿以下的部分；"壹万亿贰仟亿" 中前后两段分别累加
+                let part = (below_yi + section + number).checked_mul(100_000_000)?;
+                total = total.checked_add(part)?;
+                below_yi = 0;
+                section = 0;
+                number = 0;
+            }
+            '元' | '圆' => {
+                yuan = Some(total.checked_add(below_yi + section + number)?);
+                total = 0;
+                below_yi = 0;
+                section = 0;
+                number = 0;
+            }
+            '角' => {
+                jiao = number;
+                number = 0;
+            }
+            '分' => {
+                fen = number;
+                number = 0;
+            }
+            '整' | '正' => {}
+            _ => return None,
+        }
+    }
+
+    let yuan = yuan.unwrap_or(0);
+    yuan.checked_mul(100)?.checked_add(jiao * 10 + fen)
+}
+
+// 解析小写金额（如 1,000.50），返回以分为单位的数值
+fn parse_arabic_amount(integer: &str, decimal: Option<&str>) -> Option<u64> {
+    let yuan: u64 = integer.replace(',', "").parse().ok()?;
+    let cents = match decimal {
+        Some(d) if d.len() == 1 => d.parse::<u64>().ok()? * 10,
+        Some(d) => d.parse::<u64>().ok()?,
+        None => 0,
+    };
+    yuan.checked_mul(100)?.checked_add(cents)
+}
+
+// Check that lowercase amounts come with a matching Chinese uppercase amount
+pub fn check_amount_capitalization(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let lower_re = match Regex::new(r"[¥￥]\s*(\d{1,3}(?:,\d{3})+|\d+)(?:\.(\d{1,2}))?") {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+    let upper_re = match Regex::new(
+        r"[零壹贰叁肆伍陆柒捌玖拾佰仟万亿]+[元圆](?:[零壹贰叁肆伍陆柒捌玖]角)?(?:零?[壹贰叁肆伍陆柒捌玖]分)?[整正]?|[壹贰叁肆伍陆柒捌玖]角(?:零?[壹贰叁肆伍陆柒捌玖]分)?[整正]?|[壹贰叁肆伍陆柒捌玖]分",
+    ) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    let lower_amounts: Vec<_> = lower_re.captures_iter(line).collect();
+    if lower_amounts.is_empty() {
+        return;
+    }
+    let upper_amounts: Vec<_> = upper_re.find_iter(line).collect();
+
+    // 按出现顺序把小写金额与大写金额一一对应
+    for (idx, caps) in lower_amounts.iter().enumerate() {
+        let whole = match caps.get(0) {
+            Some(m) => m,
+            None => continue,
+        };
+        let cents = match parse_arabic_amount(&caps[1], caps.get(2).map(|m| m.as_str())) {
+            Some(cents) => cents,
+            None => continue,
+        };
+        let expected = match amount_to_chinese_upper(cents) {
+            Some(upper) => upper,
+            None => continue,
+        };
+
+        match upper_amounts.get(idx) {
+            None => {
+                issues.push(TextIssue {
+                    line_number: line_idx + 1,
+                    start: byte_to_char_index(line, whole.start()),
+                    end: byte_to_char_index(line, whole.end()),
+                    issue_type: "金额大小写".to_string(),
+                    message: format!("金额 '{}' 缺少对应的中文大写", whole.as_str()),
+                    suggestion: format!("建议补充大写金额: '{}'", expected),
+                    rule_id: "amount_capitalization".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "warning".to_string(),
+                    language: String::new(),
+                });
+            }
+            Some(upper) => {
+                if parse_chinese_upper(upper.as_str()) != Some(cents) {
+                    issues.push(TextIssue {
+                        line_number: line_idx + 1,
+                        start: byte_to_char_index(line, upper.start()),
+                        end: byte_to_char_index(line, upper.end()),
+                        issue_type: "金额大小写".to_string(),
+                        message: format!(
+                            "大写金额 '{}' 与小写金额 '{}' 不一致",
+                            upper.as_str(),
+                            whole.as_str()
+                        ),
+                        suggestion: format!("应为: '{}'", expected),
+                        rule_id: "amount_capitalization".to_string(),
+                        replacement: Some(expected.clone()),
+                        confidence: 1.0,
+                        severity: "warning".to_string(),
+                        language: String::new(),
+                    });
+                }
+            }
+        }
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compound_amounts_above_yi_are_parsed() {
+        let expected = 1_200_000_000_000 * 100;
+        assert_eq!(parse_chinese_upper("壹万贰仟亿元整"), Some(expected));
+        assert_eq!(parse_chinese_upper("壹万亿贰仟亿元整"), Some(expected));
+        assert_eq!(
+            parse_chinese_upper("壹亿贰仟万零伍元"),
+            Some(120_000_005 * 100)
+        );
+
+        let upper = amount_to_chinese_upper(expected).unwrap();
+        assert_eq!(parse_chinese_upper(&upper), Some(expected));
+    }
+}
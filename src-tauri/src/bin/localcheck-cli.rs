@@ -0,0 +1,82 @@
+// 命令行批处理：检查一个文件，把结果输出到 stdout，便于接入 CI
+//
+// 用法：localcheck-cli [--format json|md|csv] <文件路径>
+// 退出码：0 没有 error 级问题，1 存在 error 级问题，2 参数错误或文件无法读取
+use std::process::ExitCode;
+
+const USAGE: &str = "用法: localcheck-cli [--format json|md|csv] <文件路径>";
+
+enum Format {
+    Json,
+    Markdown,
+    Csv,
+}
+
+// 解析命令行参数，返回输出格式和文件路径
+fn parse_args(args: &[String]) -> Result<(Format, String), String> {
+    let mut format = Format::Json;
+    let mut path = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" | "-f" => {
+                format = match iter.next().map(String::as_str) {
+                    Some("json") => Format::Json,
+                    Some("md") => Format::Markdown,
+                    Some("csv") => Format::Csv,
+                    Some(other) => return Err(format!("不支持的输出格式: {}", other)),
+                    None => return Err("--format 缺少参数".to_string()),
+                };
+            }
+            "--help" | "-h" => return Err(USAGE.to_string()),
+            _ if arg.starts_with('-') => return Err(format!("未知参数: {}", arg)),
+            _ if path.is_some() => return Err("只能指定一个文件".to_string()),
+            _ => path = Some(arg.clone()),
+        }
+    }
+
+    match path {
+        Some(path) => Ok((format, path)),
+        None => Err(USAGE.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (format, path) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let text = match localcheck_lib::parse_document(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+    let result = localcheck_lib::analyze(&text);
+
+    let output = match format {
+        Format::Json => match serde_json::to_string_pretty(&result) {
+            Ok(json) => json,
+            Err(e) => {
+                eprintln!("无法序列化分析结果: {}", e);
+                return ExitCode::from(2);
+            }
+        },
+        Format::Markdown => localcheck_lib::report_markdown(&result),
+        Format::Csv => localcheck_lib::report_csv(&result),
+    };
+    println!("{}", output);
+
+    if result.issues.iter().any(|issue| issue.severity == "error") {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,353 @@
+// 逐行检查的管线：每个检查器实现统一的 LineChecker，顺序和短路由配置决定
+use crate::config::{self, AnalysisConfig};
+use crate::TextIssue;
+use crate::{
+    amount_check, fix_functions, grammar_check, idiom_check, improved_checker, unit_check,
+};
+use std::collections::HashSet;
+
+/// 单行检查时共享的上下文
+pub struct LineContext<'a> {
+    /// 行号（从 0 开始）
+    pub line_idx: usize,
+    /// 原文中的上一行，文档第一行为 None
+    pub prev_line: Option<&'a str>,
+    /// 该行的主要语言（"zh" / "en"）
+    pub language: &'a str,
+    /// 该行中英混排，另一种语言的占比达到配置的阈值
+    pub mixed: bool,
+    /// 该行是未经转换的繁体中文
+    pub traditional: bool,
+    pub config: &'a AnalysisConfig,
+    /// 本行已报告过的拼写错误词根，避免重复提示；跨行的去重在合并结果时进行
+    pub detected_words: &'a mut HashSet<String>,
+}
+
+/// 逐行检查器
+pub trait LineChecker: Send + Sync {
+    /// 规则 ID，与配置中的规则开关一致
+    fn id(&self) -> &str;
+
+    fn check(&self, line: &str, ctx: &mut LineContext) -> Vec<TextIssue>;
+}
+
+type CheckFn = fn(&str, &mut LineContext, &mut Vec<TextIssue>);
+
+/// 用普通函数实现的检查器，现有的检查函数都通过它接入管线
+pub struct FnChecker {
+    id: &'static str,
+    /// 只对指定语言的行运行，None 表示不限
+    language: Option<&'static str>,
+    /// 限定语言时，中英混排的行也运行
+    include_mixed: bool,
+    func: CheckFn,
+}
+
+impl FnChecker {
+    pub fn new(id: &'static str, func: CheckFn) -> Self {
+        FnChecker {
+            id,
+            language: None,
+            include_mixed: false,
+            func,
+        }
+    }
+
+    /// 限定只检查某种语言的行
+    pub fn only_for(mut self, language: &'static str) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// 限定语言时，主要语言不同但中英混排的行也检查
+    pub fn include_mixed(mut self) -> Self {
+        self.include_mixed = true;
+        self
+    }
+}
+
+impl LineChecker for FnChecker {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn check(&self, line: &str, ctx: &mut LineContext) -> Vec<TextIssue> {
+        let mut issues = Vec::new();
+        let applies = match self.language {
+            Some(language) => language == ctx.language || (self.include_mixed && ctx.mixed),
+            None => true,
+        };
+        if applies {
+            (self.func)(line, ctx, &mut issues);
+        }
+        issues
+    }
+}
+
+/// 所有内置检查器，按默认顺序排列
+pub fn default_checkers() -> Vec<Box<dyn LineChecker>> {
+    vec![
+        Box::new(FnChecker::new("repeated_words", |line, ctx, issues| {
+            crate::check_repeated_words(line, ctx.line_idx, issues)
+        })),
+        // 相邻重复的短语
+        Box::new(FnChecker::new("repeated_phrases", |line, ctx, issues| {
+            crate::check_repeated_phrases(line, ctx.line_idx, issues)
+        })),
+        Box::new(FnChecker::new("punctuation", |line, ctx, issues| {
+            crate::check_punctuation(line, ctx.line_idx, issues)
+        })),
+        Box::new(FnChecker::new(
+            "redundant_expressions",
+            |line, ctx, issues| {
+                crate::check_redundant_expressions(line, ctx.line_idx, issues, ctx.language)
+            },
+        )),
+        // 统一的拼写检查，improved_checker 已包含所有拼写检查逻辑
+        Box::new(FnChecker::new("spelling", |line, ctx, issues| {
+            improved_checker::check_spelling(
+                line,
+                ctx.line_idx,
+                issues,
+                ctx.detected_words,
+                ctx.mixed,
+            )
+        })),
+        Box::new(FnChecker::new("grammar", |line, ctx, issues| {
+            // 的得地等中文语法规则针对简体，繁体行跳过
+            if !ctx.traditional {
+                crate::check_grammar_issues(line, ctx.line_idx, issues, ctx.language)
+            }
+        })),
+        // 英文冠词 a / an
+        Box::new(
+            FnChecker::new("article_usage", |line, ctx, issues| {
+                crate::check_article_usage(line, ctx.line_idx, issues)
+            })
+            .only_for("en")
+            .include_mixed(),
+        ),
+        Box::new(FnChecker::new("word_order", |line, ctx, issues| {
+            grammar_check::check_word_order(line, ctx.line_idx, issues)
+        })),
+        Box::new(FnChecker::new(
+            "chinese_punctuation",
+            |line, ctx, issues| {
+                grammar_check::check_chinese_punctuation(line, ctx.line_idx, issues)
+            },
+        )),
+        Box::new(FnChecker::new("tense_consistency", |line, ctx, issues| {
+            grammar_check::check_tense_consistency(line, ctx.line_idx, issues)
+        })),
+        Box::new(FnChecker::new("preposition_usage", |line, ctx, issues| {
+            grammar_check::check_preposition_usage(line, ctx.line_idx, issues)
+        })),
+        Box::new(FnChecker::new("idiom_usage", |line, ctx, issues| {
+            fix_functions::check_idiom_usage(line, ctx.line_idx, issues)
+        })),
+        Box::new(FnChecker::new("academic_style", |line, ctx, issues| {
+            fix_functions::check_academic_style(line, ctx.line_idx, issues, ctx.language)
+        })),
+        Box::new(FnChecker::new("sentence_length", |line, ctx, issues| {
+            let max_length = if ctx.language == "zh" {
+                ctx.config
+                    .max_sentence_chars
+                    .unwrap_or(fix_functions::DEFAULT_MAX_SENTENCE_CHARS)
+            } else {
+                ctx.config
+                    .max_sentence_words
+                    .unwrap_or(fix_functions::DEFAULT_MAX_SENTENCE_WORDS)
+            };
+            fix_functions::check_sentence_length(
+                line,
+                ctx.line_idx,
+                issues,
+                ctx.language,
+                max_length,
+            )
+        })),
+        Box::new(FnChecker::new("citation_format", |line, ctx, issues| {
+            fix_functions::check_citation_format(line, ctx.line_idx, issues)
+        })),
+        // 财务场景：金额大小写一致性（默认关闭）
+        Box::new(FnChecker::new(
+            "amount_capitalization",
+            |line, ctx, issues| {
+                amount_check::check_amount_capitalization(line, ctx.line_idx, issues)
+            },
+        )),
+        // 英文列举中的牛津逗号（风格偏好，默认关闭）
+        Box::new(FnChecker::new("oxford_comma", |line, ctx, issues| {
+            grammar_check::check_oxford_comma(line, ctx.line_idx, issues)
+        })),
+        // between / among 的区分（默认关闭）
+        Box::new(FnChecker::new("between_among", |line, ctx, issues| {
+            grammar_check::check_between_among(line, ctx.line_idx, issues)
+        })),
+        // affect / effect 混用（低置信度）
+        Box::new(FnChecker::new("affect_effect", |line, ctx, issues| {
+            grammar_check::check_affect_effect(line, ctx.line_idx, issues)
+        })),
+        // 中文引号与冒号、句末标点的搭配
+        Box::new(FnChecker::new("quote_punctuation", |line, ctx, issues| {
+            grammar_check::check_quote_colon_punct(line, ctx.line_idx, issues)
+        })),
+        // i.e. / e.g. 的格式与混用
+        Box::new(FnChecker::new("ie_eg", |line, ctx, issues| {
+            grammar_check::check_ie_eg(line, ctx.line_idx, issues)
+        })),
+        // 英文名词堆叠
+        Box::new(FnChecker::new("noun_stacking", |line, ctx, issues| {
+            grammar_check::check_noun_stacking(line, ctx.line_idx, issues)
+        })),
+        // 英文逗号连接独立句
+        Box::new(FnChecker::new("comma_splice", |line, ctx, issues| {
+            grammar_check::check_comma_splice(line, ctx.line_idx, issues)
+        })),
+        // 英文 could of / should of 误用
+        Box::new(FnChecker::new("modal_of", |line, ctx, issues| {
+            grammar_check::check_modal_of(line, ctx.line_idx, issues)
+        })),
+        // 英文动词与名词的常见错误搭配
+        Box::new(
+            FnChecker::new("collocations", |line, ctx, issues| {
+                grammar_check::check_collocations(line, ctx.line_idx, issues)
+            })
+            .only_for("en")
+            .include_mixed(),
+        ),
+        // 英文易混词 its/it's、their/there/they're 等
+        Box::new(
+            FnChecker::new("confusables", |line, ctx, issues| {
+                grammar_check::check_confusables(line, ctx.line_idx, issues)
+            })
+            .only_for("en")
+            .include_mixed(),
+        ),
+        // 常被误用语义的成语
+        Box::new(FnChecker::new("idiom_misuse", |line, ctx, issues| {
+            idiom_check::check_idiom_misuse(line, ctx.line_idx, issues)
+        })),
+        // 中文作品名缺少书名号（实验性）
+        Box::new(FnChecker::new("title_marks", |line, ctx, issues| {
+            fix_functions::check_missing_title_marks(line, ctx.line_idx, issues)
+        })),
+        // 英文反身代词误用
+        Box::new(FnChecker::new("reflexive_pronoun", |line, ctx, issues| {
+            grammar_check::check_reflexive_misuse(line, ctx.line_idx, issues)
+        })),
+        // 英文句子中的全角标点
+        Box::new(
+            FnChecker::new("fullwidth_punct", |line, ctx, issues| {
+                grammar_check::check_fullwidth_punct_in_english(line, ctx.line_idx, issues)
+            })
+            .only_for("en"),
+        ),
+        // 全角字母和数字
+        Box::new(FnChecker::new("fullwidth_alnum", |line, ctx, issues| {
+            grammar_check::check_fullwidth_alnum(line, ctx.line_idx, issues)
+        })),
+        // 英文从句嵌套过深
+        Box::new(
+            FnChecker::new("clause_nesting", |line, ctx, issues| {
+                let max_markers = ctx
+                    .config
+                    .max_clause_markers
+                    .unwrap_or(grammar_check::DEFAULT_MAX_CLAUSE_MARKERS);
+                grammar_check::check_clause_nesting(line, ctx.line_idx, issues, max_markers)
+            })
+            .only_for("en"),
+        ),
+        // 中文“的”字堆叠
+        Box::new(
+            FnChecker::new("excessive_de", |line, ctx, issues| {
+                let max_per_clause = ctx
+                    .config
+                    .max_de_per_clause
+                    .unwrap_or(grammar_check::DEFAULT_MAX_DE_PER_CLAUSE);
+                grammar_check::check_excessive_de(line, ctx.line_idx, issues, max_per_clause)
+            })
+            .only_for("zh"),
+        ),
+        // 完整句子末尾缺少句号等结束标点
+        Box::new(FnChecker::new(
+            "missing_end_punctuation",
+            |line, ctx, issues| {
+                grammar_check::check_missing_end_punctuation(line, ctx.line_idx, issues)
+            },
+        )),
+        // 中文同音、形近错别字
+        Box::new(FnChecker::new("chinese_typos", |line, ctx, issues| {
+            grammar_check::check_chinese_typos(line, ctx.line_idx, issues)
+        })),
+        // 中文量词与名词的搭配
+        Box::new(FnChecker::new("measure_words", |line, ctx, issues| {
+            grammar_check::check_measure_words(line, ctx.line_idx, issues)
+        })),
+        // 数字与单位之间的空格
+        Box::new(FnChecker::new(
+            "number_unit_spacing",
+            |line, ctx, issues| {
+                let space = ctx.config.number_unit_space.unwrap_or(true);
+                unit_check::check_number_unit_spacing(line, ctx.line_idx, issues, space)
+            },
+        )),
+        // 多余空格、标点前的空格和中英文间距
+        Box::new(FnChecker::new("extra_whitespace", |line, ctx, issues| {
+            grammar_check::check_extra_whitespace(
+                line,
+                ctx.line_idx,
+                issues,
+                ctx.config.cjk_latin_space,
+            )
+        })),
+        // 英文单词错误拆分或连写
+        Box::new(
+            FnChecker::new("word_boundaries", |line, ctx, issues| {
+                grammar_check::check_word_boundaries(line, ctx.line_idx, issues)
+            })
+            .only_for("en")
+            .include_mixed(),
+        ),
+        // 英文句首字母未大写
+        Box::new(
+            FnChecker::new("sentence_capitalization", |line, ctx, issues| {
+                grammar_check::check_sentence_capitalization(
+                    line,
+                    ctx.line_idx,
+                    ctx.prev_line,
+                    issues,
+                )
+            })
+            .only_for("en"),
+        ),
+        // 被动语态提示，可在配置中关闭
+        Box::new(FnChecker::new("passive_voice", |line, ctx, issues| {
+            crate::check_passive_voice(line, ctx.line_idx, issues, ctx.language)
+        })),
+        // 用户自定义的正则规则，各条规则的开关在内部判断
+        Box::new(FnChecker::new("custom_rules", |line, ctx, issues| {
+            config::check_custom_rules(line, ctx.line_idx, issues, ctx.config)
+        })),
+    ]
+}
+
+/// 按配置构建检查管线：去掉禁用的检查器，并按 checker_order 调整顺序
+///
+/// checker_order 中未列出的检查器保持默认顺序，排在列出的检查器之后。
+pub fn build_pipeline(config: &AnalysisConfig) -> Vec<Box<dyn LineChecker>> {
+    let mut checkers: Vec<Box<dyn LineChecker>> = default_checkers()
+        .into_iter()
+        .filter(|checker| config.is_rule_enabled(checker.id()))
+        .collect();
+
+    checkers.sort_by_key(|checker| {
+        config
+            .checker_order
+            .iter()
+            .position(|id| id == checker.id())
+            .unwrap_or(usize::MAX)
+    });
+
+    checkers
+}
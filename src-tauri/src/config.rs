@@ -0,0 +1,816 @@
+use crate::byte_to_char_index;
+use crate::dictionary;
+use crate::feedback;
+use crate::spelling_dict;
+use crate::TextIssue;
+use crate::{MAX_FILE_SIZE, MAX_ISSUES, MAX_LINE_LENGTH, MAX_TEXT_LENGTH};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+
+/// 当前配置文件格式版本
+pub const CONFIG_VERSION: u32 = 1;
+
+// 默认关闭的规则，需要在配置中显式启用
+const DEFAULT_DISABLED_RULES: &[&str] = &[
+    "amount_capitalization",
+    "oxford_comma",
+    "between_among",
+    "comma_splice",
+    "date_format",
+    "title_marks",
+    "reflexive_pronoun",
+];
+
+/// 分析配置：控制各条规则的启用状态
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AnalysisConfig {
+    /// 按规则 ID 覆盖默认的启用状态
+    pub rules: HashMap<String, bool>,
+    /// 按规则 ID 覆盖问题的严重程度：error / warning / info
+    pub severity_overrides: HashMap<String, String>,
+    /// 每行最多报告的问题数，None 表示不限制
+    pub max_issues_per_line: Option<usize>,
+    /// 问题优先级排序时各因子的权重
+    pub ranking_weights: RankingWeights,
+    /// 逐行检查器的执行顺序（规则 ID），未列出的按默认顺序排在后面
+    pub checker_order: Vec<String>,
+    /// 这些检查器在某行报告问题后，跳过该行后续的检查
+    pub short_circuit_rules: Vec<String>,
+    /// 英文一句中允许的从句标志词数量，None 时使用默认值
+    pub max_clause_markers: Option<usize>,
+    /// 中文一个短句中允许的“的”字数量，None 时使用默认值
+    pub max_de_per_clause: Option<usize>,
+    /// 中文句子允许的最大字符数，None 时使用默认值
+    pub max_sentence_chars: Option<usize>,
+    /// 英文句子允许的最大单词数，None 时使用默认值
+    pub max_sentence_words: Option<usize>,
+    /// 数字与英文单位之间是否加空格（50 kg / 50kg），None 时默认加空格
+    pub number_unit_space: Option<bool>,
+    /// 中文与英文字母之间是否加空格，None 时不检查
+    pub cjk_latin_space: Option<bool>,
+    /// 英文引号风格："straight" 直引号，"curly" 弯引号；None 时以段落中较多的一种为准
+    pub quote_style: Option<String>,
+    /// 另一种语言的字符占比达到该值时视为中英混排，两种语言的规则都运行；None 时使用默认值
+    pub mixed_language_threshold: Option<f32>,
+    /// 文本长度、问题数等处理上限
+    pub limits: AnalysisLimits,
+    /// 按检查类别的开关
+    pub check_options: CheckOptions,
+    /// 检查前把繁体中文转换为简体
+    pub convert_traditional_to_simplified: bool,
+    /// 解析 .docx 时同时提取页眉和页脚，追加在正文之后
+    pub docx_headers_footers: bool,
+}
+
+/// 按检查类别的开关，默认全部开启
+///
+/// 关闭某个类别会跳过其下所有规则；类别开启时仍以 rules 中的单条规则开关为准。
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CheckOptions {
+    pub spelling: bool,
+    pub repeated_words: bool,
+    /// 标点混用、中文标点、引号搭配、英文中的全角标点
+    pub punctuation: bool,
+    pub redundant_expressions: bool,
+    /// 的得地、主谓一致、语序、时态、介词等语法检查
+    pub grammar: bool,
+    /// 英文冠词 a / an
+    pub english_articles: bool,
+    /// 中文成语用法和常被误用的成语
+    pub chinese_idioms: bool,
+    pub academic_style: bool,
+    pub sentence_length: bool,
+    pub citation_format: bool,
+    pub passive_voice: bool,
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self {
+        CheckOptions {
+            spelling: true,
+            repeated_words: true,
+            punctuation: true,
+            redundant_expressions: true,
+            grammar: true,
+            english_articles: true,
+            chinese_idioms: true,
+            academic_style: true,
+            sentence_length: true,
+            citation_format: true,
+            passive_voice: true,
+        }
+    }
+}
+
+impl CheckOptions {
+    /// 按类别名开关检查，类别名与字段名相同
+    pub fn set(&mut self, category: &str, enabled: bool) -> Result<(), String> {
+        let field = match category {
+            "spelling" => &mut self.spelling,
+            "repeated_words" => &mut self.repeated_words,
+            "punctuation" => &mut self.punctuation,
+            "redundant_expressions" => &mut self.redundant_expressions,
+            "grammar" => &mut self.grammar,
+            "english_articles" => &mut self.english_articles,
+            "chinese_idioms" => &mut self.chinese_idioms,
+            "academic_style" => &mut self.academic_style,
+            "sentence_length" => &mut self.sentence_length,
+            "citation_format" => &mut self.citation_format,
+            "passive_voice" => &mut self.passive_voice,
+            _ => return Err(format!("未知的检查类别: '{}'", category)),
+        };
+        *field = enabled;
+        Ok(())
+    }
+
+    /// 规则所属的类别是否开启，不属于任何类别的规则不受影响
+    pub fn allows(&self, rule_id: &str) -> bool {
+        match rule_id {
+            "spelling" | "chinese_typos" => self.spelling,
+            "repeated_words" | "repeated_phrases" | "paragraph_repeated_words" => {
+                self.repeated_words
+            }
+            "punctuation"
+            | "chinese_punctuation"
+            | "quote_punctuation"
+            | "fullwidth_punct"
+            | "fullwidth_alnum"
+            | "quote_style"
+            | "extra_whitespace"
+            | "missing_end_punctuation" => self.punctuation,
+            "redundant_expressions" => self.redundant_expressions,
+            "grammar"
+            | "word_order"
+            | "tense_consistency"
+            | "preposition_usage"
+            | "sentence_capitalization"
+            | "measure_words"
+            | "word_boundaries"
+            | "excessive_de"
+            | "collocations"
+            | "confusables" => self.grammar,
+            "article_usage" => self.english_articles,
+            "idiom_usage" | "idiom_misuse" => self.chinese_idioms,
+            "academic_style" => self.academic_style,
+            "sentence_length" => self.sentence_length,
+            "citation_format" => self.citation_format,
+            "passive_voice" => self.passive_voice,
+            _ => true,
+        }
+    }
+}
+
+// 各项上限允许设置的最大值，避免一次处理过多内容导致卡死
+const MAX_ALLOWED_TEXT_LENGTH: usize = 5_000_000;
+const MAX_ALLOWED_LINE_LENGTH: usize = 100_000;
+const MAX_ALLOWED_ISSUES: usize = 100_000;
+const MAX_ALLOWED_FILE_SIZE: u64 = 100_000_000;
+
+/// 分析时的处理上限，默认值与内置常量一致
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AnalysisLimits {
+    /// 一次处理的最大字符数，超出部分截断
+    pub max_text_length: usize,
+    /// 单行检查的最大字符数
+    pub max_line_length: usize,
+    /// 返回的最大问题数
+    pub max_issues: usize,
+    /// 可打开的最大文件字节数
+    pub max_file_size: u64,
+}
+
+impl Default for AnalysisLimits {
+    fn default() -> Self {
+        AnalysisLimits {
+            max_text_length: MAX_TEXT_LENGTH,
+            max_line_length: MAX_LINE_LENGTH,
+            max_issues: MAX_ISSUES,
+            max_file_size: MAX_FILE_SIZE,
+        }
+    }
+}
+
+impl AnalysisLimits {
+    /// 检查各项上限是否在合理范围内
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_text_length == 0 || self.max_text_length > MAX_ALLOWED_TEXT_LENGTH {
+            return Err(format!(
+                "文本长度上限必须在 1 到 {} 个字符之间",
+                MAX_ALLOWED_TEXT_LENGTH
+            ));
+        }
+        if self.max_line_length == 0 || self.max_line_length > MAX_ALLOWED_LINE_LENGTH {
+            return Err(format!(
+                "单行长度上限必须在 1 到 {} 个字符之间",
+                MAX_ALLOWED_LINE_LENGTH
+            ));
+        }
+        if self.max_issues == 0 || self.max_issues > MAX_ALLOWED_ISSUES {
+            return Err(format!("问题数上限必须在 1 到 {} 之间", MAX_ALLOWED_ISSUES));
+        }
+        if self.max_file_size == 0 || self.max_file_size > MAX_ALLOWED_FILE_SIZE {
+            return Err(format!(
+                "文件大小上限必须在 1 到 {}MB 之间",
+                MAX_ALLOWED_FILE_SIZE / 1_000_000
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 问题优先级排序的因子权重
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct RankingWeights {
+    pub severity: f32,
+    pub confidence: f32,
+    /// 有明确替换文本、可一键修复的问题额外加分
+    pub auto_fixable: f32,
+}
+
+impl Default for RankingWeights {
+    fn default() -> Self {
+        RankingWeights {
+            severity: 1.0,
+            confidence: 1.0,
+            auto_fixable: 0.5,
+        }
+    }
+}
+
+impl RankingWeights {
+    /// 计算问题的综合优先级分数，分数越高越值得优先修改
+    pub fn score(&self, issue: &TextIssue) -> f32 {
+        let severity = match issue.severity.as_str() {
+            "error" => 1.0,
+            "info" => 0.2,
+            _ => 0.6,
+        };
+        let auto_fixable = if issue.replacement.is_some() {
+            1.0
+        } else {
+            0.0
+        };
+
+        self.severity * severity
+            + self.confidence * issue.confidence
+            + self.auto_fixable * auto_fixable
+    }
+}
+
+// 问题严重程度的可选值
+const SEVERITIES: &[&str] = &["error", "warning", "info"];
+
+impl AnalysisConfig {
+    /// 检查处理上限和严重程度覆盖是否有效
+    pub fn validate(&self) -> Result<(), String> {
+        self.limits.validate()?;
+        for (rule_id, severity) in &self.severity_overrides {
+            if !SEVERITIES.contains(&severity.as_str()) {
+                return Err(format!(
+                    "规则 '{}' 的严重程度无效: '{}'，应为 error、warning 或 info",
+                    rule_id, severity
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 按 severity_overrides 调整问题的严重程度
+    pub fn apply_severity_override(&self, issue: &mut TextIssue) {
+        if let Some(severity) = self.severity_overrides.get(&issue.rule_id) {
+            issue.severity = severity.clone();
+        }
+    }
+
+    /// 判断某条规则是否启用（未配置时使用默认值）
+    pub fn is_rule_enabled(&self, rule_id: &str) -> bool {
+        if !self.check_options.allows(rule_id) {
+            return false;
+        }
+        match self.rules.get(rule_id) {
+            Some(enabled) => *enabled,
+            None => !DEFAULT_DISABLED_RULES.contains(&rule_id),
+        }
+    }
+}
+
+/// 用户自定义的正则检查规则
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CustomRule {
+    pub id: String,
+    pub pattern: String,
+    #[serde(default = "default_custom_issue_type")]
+    pub issue_type: String,
+    pub message: String,
+    #[serde(default)]
+    pub suggestion: String,
+}
+
+fn default_custom_issue_type() -> String {
+    "自定义规则".to_string()
+}
+
+/// 可导入导出的配置文件内容
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConfigFile {
+    pub version: u32,
+    #[serde(default)]
+    pub config: AnalysisConfig,
+    #[serde(default)]
+    pub user_words: Vec<String>,
+    #[serde(default)]
+    pub custom_corrections: BTreeMap<String, String>,
+    #[serde(default)]
+    pub custom_rules: Vec<CustomRule>,
+}
+
+/// 团队共享的忽略配置，TOML 或 JSON 格式
+///
+/// 除 ignore_words 外，其余键与 AnalysisConfig 的序列化格式相同（即导出文件中的 config 和配置档案），
+/// 只需写出要修改的部分。TOML 示例：顶层 `ignore_words = ["Localcheck"]`，
+/// `[check_options]` 下写 `passive_voice = false`，`[rules]` 下按规则 ID 开关单条规则。
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct IgnoreConfig {
+    /// 不再提示的词，大小写和单复数视为同一个词
+    pub ignore_words: Vec<String>,
+    /// 要覆盖的分析配置项，未写出的保持当前设置
+    #[serde(flatten)]
+    pub config: serde_json::Map<String, serde_json::Value>,
+}
+
+// 当前生效的分析配置
+static CURRENT_CONFIG: OnceLock<Mutex<AnalysisConfig>> = OnceLock::new();
+
+// 已编译的自定义规则，避免每行重复编译正则
+static CUSTOM_RULES: OnceLock<Mutex<Vec<(CustomRule, Regex)>>> = OnceLock::new();
+
+fn config_store() -> &'static Mutex<AnalysisConfig> {
+    CURRENT_CONFIG.get_or_init(|| Mutex::new(AnalysisConfig::default()))
+}
+
+fn custom_rule_store() -> &'static Mutex<Vec<(CustomRule, Regex)>> {
+    CUSTOM_RULES.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// 获取当前生效配置的副本
+pub fn current_config() -> AnalysisConfig {
+    match config_store().lock() {
+        Ok(config) => config.clone(),
+        Err(_) => AnalysisConfig::default(),
+    }
+}
+
+/// 替换当前生效的配置
+pub fn set_current_config(config: AnalysisConfig) {
+    if let Ok(mut current) = config_store().lock() {
+        *current = config;
+    }
+}
+
+/// 单独开关某条规则
+pub fn set_rule_enabled(rule_id: &str, enabled: bool) {
+    if let Ok(mut current) = config_store().lock() {
+        current.rules.insert(rule_id.to_string(), enabled);
+    }
+}
+
+/// 获取当前的自定义规则列表
+pub fn custom_rules() -> Vec<CustomRule> {
+    match custom_rule_store().lock() {
+        Ok(rules) => rules.iter().map(|(rule, _)| rule.clone()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// 校验并编译自定义规则，任何一条无效都会返回错误
+fn compile_custom_rules(rules: Vec<CustomRule>) -> Result<Vec<(CustomRule, Regex)>, String> {
+    let mut compiled = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for rule in rules {
+        if rule.id.trim().is_empty() {
+            return Err("自定义规则缺少 id".to_string());
+        }
+        if !seen_ids.insert(rule.id.clone()) {
+            return Err(format!("自定义规则 id 重复: {}", rule.id));
+        }
+        if rule.pattern.is_empty() {
+            return Err(format!("自定义规则 '{}' 的正则为空", rule.id));
+        }
+
+        let regex = Regex::new(&rule.pattern)
+            .map_err(|e| format!("自定义规则 '{}' 的正则无效: {}", rule.id, e))?;
+        compiled.push((rule, regex));
+    }
+
+    Ok(compiled)
+}
+
+// 运行用户自定义的正则规则
+pub fn check_custom_rules(
+    line: &str,
+    line_idx: usize,
+    issues: &mut Vec<TextIssue>,
+    config: &AnalysisConfig,
+) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let rules = match custom_rule_store().lock() {
+        Ok(rules) => rules,
+        Err(_) => return,
+    };
+
+    for (rule, regex) in rules.iter() {
+        if !config.is_rule_enabled(&rule.id) {
+            continue;
+        }
+
+        for mat in regex.find_iter(line) {
+            if mat.start() == mat.end() {
+                continue;
+            }
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, mat.start()),
+                end: byte_to_char_index(line, mat.end()),
+                issue_type: rule.issue_type.clone(),
+                message: rule.message.clone(),
+                suggestion: rule.suggestion.clone(),
+                rule_id: rule.id.clone(),
+                replacement: None,
+                confidence: 1.0,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+/// 把当前配置、用户词、自定义纠正对和自定义规则导出为 JSON 文件
+pub fn export_to_file(path: &str) -> Result<(), String> {
+    let file = ConfigFile {
+        version: CONFIG_VERSION,
+        config: current_config(),
+        user_words: dictionary::get_user_words(),
+        custom_corrections: spelling_dict::get_custom_typos(),
+        custom_rules: custom_rules(),
+    };
+
+    let json =
+        serde_json::to_string_pretty(&file).map_err(|e| format!("无法序列化配置: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("无法写入配置文件: {}", e))
+}
+
+/// 从 JSON 文件导入配置，校验通过后才会生效
+///
+/// 规则开关和自定义规则会整体替换，用户词和自定义纠正对与现有内容合并。
+pub fn import_from_file(path: &str) -> Result<(), String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("无法读取配置文件: {}", e))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("配置文件格式错误: {}", e))?;
+
+    let value = migrate_config(value)?;
+    let file: ConfigFile =
+        serde_json::from_value(value).map_err(|e| format!("配置文件内容无效: {}", e))?;
+
+    // 先完成全部校验，避免导入一半后失败
+    file.config.validate()?;
+    let compiled_rules = compile_custom_rules(file.custom_rules)?;
+
+    let user_words: Vec<String> = file
+        .user_words
+        .iter()
+        .map(|w| w.trim().to_string())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if let Some(word) = user_words.iter().find(|w| w.contains(char::is_whitespace)) {
+        return Err(format!("用户词不能包含空白字符: '{}'", word));
+    }
+
+    for (wrong, correct) in &file.custom_corrections {
+        if wrong.trim().is_empty() || correct.trim().is_empty() {
+            return Err(format!("自定义纠正对不能为空: '{}' -> '{}'", wrong, correct));
+        }
+    }
+
+    // 先写入可能失败的纠正对和用户词，用户词保存失败时恢复原来的纠正对，
+    // 全部成功后再替换配置和自定义规则
+    let previous_typos = spelling_dict::get_custom_typos();
+    spelling_dict::add_custom_typos(&file.custom_corrections)?;
+    if let Err(e) = dictionary::add_user_words(&user_words) {
+        let _ = spelling_dict::restore_custom_typos(previous_typos);
+        return Err(e);
+    }
+
+    set_current_config(file.config);
+    if let Ok(mut rules) = custom_rule_store().lock() {
+        *rules = compiled_rules;
+    }
+    Ok(())
+}
+
+// 字节位置所在的行号（从 1 开始）
+fn line_at(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+// 查找键名或取值首次出现的行号，用于给内容错误标注位置
+fn line_of(content: &str, key: &str) -> Option<usize> {
+    content
+        .lines()
+        .position(|line| line.contains(key))
+        .map(|idx| idx + 1)
+}
+
+// 给内容错误加上所在行号，找不到对应的行时原样返回
+fn with_line(content: &str, key: &str, message: String) -> String {
+    match line_of(content, key) {
+        Some(line) => format!("第{}行: {}", line, message),
+        None => message,
+    }
+}
+
+// 解析忽略配置：.json 按 JSON 解析，其他扩展名按 TOML 解析，格式错误时带上行号
+fn parse_ignore_config(path: &str, content: &str) -> Result<IgnoreConfig, String> {
+    if path.to_lowercase().ends_with(".json") {
+        serde_json::from_str(content)
+            .map_err(|e| format!("配置文件第{}行第{}列格式错误: {}", e.line(), e.column(), e))
+    } else {
+        toml::from_str(content).map_err(|e| match e.span() {
+            Some(span) => format!(
+                "配置文件第{}行格式错误: {}",
+                line_at(content, span.start),
+                e.message()
+            ),
+            None => format!("配置文件格式错误: {}", e.message()),
+        })
+    }
+}
+
+// 内置规则和当前自定义规则的 ID
+fn is_known_rule(rule_id: &str) -> bool {
+    crate::checker::default_checkers()
+        .iter()
+        .any(|checker| checker.id() == rule_id)
+        || crate::document_check::DOCUMENT_RULES.contains(&rule_id)
+        || custom_rules().iter().any(|rule| rule.id == rule_id)
+}
+
+// AnalysisConfig 中以规则 ID 为键的 HashMap 字段，加载时逐条合并而不是整体替换
+const MAP_FIELDS: &[&str] = &["rules", "severity_overrides"];
+
+// 把 overrides 中写出的配置项合并到序列化后的配置上，出错时返回出错的键名和原因
+//
+// 结构体中没有的字段是未知配置项；MAP_FIELDS 中的表可以新增条目。
+fn merge_config_value(
+    base: &mut serde_json::Value,
+    overrides: serde_json::Map<String, serde_json::Value>,
+) -> Result<(), (String, String)> {
+    let object = match base.as_object_mut() {
+        Some(object) => object,
+        None => return Ok(()),
+    };
+    for (key, value) in overrides {
+        let slot = match object.get_mut(&key) {
+            Some(slot) => slot,
+            None => return Err((key.clone(), format!("未知的配置项: '{}'", key))),
+        };
+        match value {
+            serde_json::Value::Object(entries) if MAP_FIELDS.contains(&key.as_str()) => {
+                match slot.as_object_mut() {
+                    Some(map) => map.extend(entries),
+                    None => *slot = serde_json::Value::Object(entries),
+                }
+            }
+            serde_json::Value::Object(entries) if slot.is_object() => {
+                merge_config_value(slot, entries)?;
+            }
+            value => *slot = value,
+        }
+    }
+    Ok(())
+}
+
+/// 从 TOML 或 JSON 文件加载忽略词和配置项，应用到之后的分析
+///
+/// 全部校验通过后才会生效；忽略词只在本次运行中有效，不写入用户词典。
+pub fn load_config(path: &str) -> Result<(), String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("无法读取配置文件: {}", e))?;
+    let file = parse_ignore_config(path, &content)?;
+
+    let rule_ids = MAP_FIELDS
+        .iter()
+        .filter_map(|field| file.config.get(*field))
+        .filter_map(|entries| entries.as_object())
+        .flat_map(|entries| entries.keys());
+    for rule_id in rule_ids {
+        if !is_known_rule(rule_id) {
+            return Err(with_line(
+                &content,
+                rule_id,
+                format!("未知的规则 ID: '{}'", rule_id),
+            ));
+        }
+    }
+
+    let words: Vec<&str> = file
+        .ignore_words
+        .iter()
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty())
+        .collect();
+    if let Some(word) = words.iter().find(|word| word.contains(char::is_whitespace)) {
+        return Err(with_line(
+            &content,
+            word,
+            format!("忽略词不能包含空白字符: '{}'", word),
+        ));
+    }
+
+    // 合并与替换在同一次加锁中完成，避免覆盖其他地方同时做的修改
+    let mut current = config_store()
+        .lock()
+        .map_err(|_| "无法读取当前配置".to_string())?;
+    let mut merged =
+        serde_json::to_value(&*current).map_err(|e| format!("无法序列化配置: {}", e))?;
+    merge_config_value(&mut merged, file.config)
+        .map_err(|(key, message)| with_line(&content, &key, message))?;
+    let config: AnalysisConfig =
+        serde_json::from_value(merged).map_err(|e| format!("配置文件内容无效: {}", e))?;
+    config.validate()?;
+    *current = config;
+    drop(current);
+
+    for word in words {
+        feedback::ignore_word_for_session(word);
+    }
+    Ok(())
+}
+
+// 用户保存的配置档案：档案名 -> 分析配置
+const PROFILES_FILE: &str = "profiles.json";
+
+/// 内置的配置档案名
+pub const BUILTIN_PROFILES: &[&str] = &["通用", "学术"];
+
+// 内置配置档案，用户保存的同名档案优先
+fn builtin_profile(name: &str) -> Option<AnalysisConfig> {
+    let mut config = AnalysisConfig::default();
+    match name {
+        "通用" => {
+            config.check_options.academic_style = false;
+            config.check_options.citation_format = false;
+        }
+        "学术" => {
+            for rule_id in [
+                "comma_splice",
+                "between_among",
+                "reflexive_pronoun",
+                "date_format",
+            ] {
+                config.rules.insert(rule_id.to_string(), true);
+            }
+        }
+        _ => return None,
+    }
+    Some(config)
+}
+
+// 读取用户保存的配置档案，文件不存在时为空
+fn saved_profiles() -> Result<BTreeMap<String, AnalysisConfig>, String> {
+    let path = crate::storage::data_file(PROFILES_FILE)?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = std::fs::read_to_string(path).map_err(|e| format!("无法读取配置档案: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("配置档案文件格式错误: {}", e))
+}
+
+/// 把配置保存为命名档案，写入应用数据目录；同名档案会被覆盖
+pub fn save_profile(name: &str, config: AnalysisConfig) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("配置档案名不能为空".to_string());
+    }
+    config.validate()?;
+
+    let mut profiles = saved_profiles()?;
+    profiles.insert(name.to_string(), config);
+
+    let json = serde_json::to_string_pretty(&profiles)
+        .map_err(|e| format!("无法序列化配置档案: {}", e))?;
+    let path = crate::storage::data_file(PROFILES_FILE)?;
+    std::fs::write(path, json).map_err(|e| format!("无法写入配置档案: {}", e))
+}
+
+/// 应用命名档案作为当前配置，先查找用户保存的档案，再查找内置档案
+pub fn apply_profile(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    let config = match saved_profiles()?.remove(name) {
+        Some(config) => config,
+        None => builtin_profile(name).ok_or_else(|| format!("未找到配置档案: '{}'", name))?,
+    };
+
+    config.validate()?;
+    set_current_config(config);
+    Ok(())
+}
+
+/// 列出全部可用的档案名：内置档案在前，其后是用户保存的档案
+pub fn list_profiles() -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = BUILTIN_PROFILES
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    for name in saved_profiles()?.into_keys() {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+// 把旧版本的配置文件迁移到当前版本的结构
+fn migrate_config(mut value: serde_json::Value) -> Result<serde_json::Value, String> {
+    let object = match value.as_object_mut() {
+        Some(object) => object,
+        None => return Err("配置文件顶层必须是对象".to_string()),
+    };
+
+    // 没有版本号的配置视为手写的早期格式（版本 0）
+    let version = match object.get("version") {
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| "配置文件的 version 字段必须是整数".to_string())?,
+        None => 0,
+    };
+
+    if version > CONFIG_VERSION {
+        return Err(format!(
+            "配置文件版本 {} 高于当前支持的版本 {}，请升级应用",
+            version, CONFIG_VERSION
+        ));
+    }
+
+    if version == 0 {
+        // 版本 0 把规则开关直接写在顶层，迁移到 config.rules 下
+        if let Some(rules) = object.remove("rules") {
+            let mut config = serde_json::Map::new();
+            config.insert("rules".to_string(), rules);
+            object
+                .entry("config".to_string())
+                .or_insert(serde_json::Value::Object(config));
+        }
+    }
+
+    object.insert("version".to_string(), serde_json::Value::from(CONFIG_VERSION));
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loaded_config_adds_map_entries_and_rejects_unknown_fields() {
+        let content =
+            "[severity_overrides]\nspelling = \"error\"\n\n[rules]\npassive_voice = false\n";
+        let file = parse_ignore_config("shared.toml", content).unwrap();
+        let mut merged = serde_json::to_value(AnalysisConfig::default()).unwrap();
+        merge_config_value(&mut merged, file.config).unwrap();
+
+        let config: AnalysisConfig = serde_json::from_value(merged).unwrap();
+        assert_eq!(
+            config
+                .severity_overrides
+                .get("spelling")
+                .map(String::as_str),
+            Some("error")
+        );
+        assert_eq!(config.rules.get("passive_voice"), Some(&false));
+
+        // 结构体字段中没有的键仍是未知配置项
+        let file = parse_ignore_config("shared.toml", "[limits]\nunknown = 1\n").unwrap();
+        let mut merged = serde_json::to_value(AnalysisConfig::default()).unwrap();
+        assert!(merge_config_value(&mut merged, file.config).is_err());
+    }
+}
@@ -1,48 +1,156 @@
 use std::collections::HashSet;
 use std::fs::File;
 use std::io::{self, BufRead, BufReader};
-use std::sync::OnceLock;
-
-// 使用 OnceLock 来实现单例模式，确保词典只被加载一次
-static DICTIONARY: OnceLock<HashSet<String>> = OnceLock::new();
-
-// 加载词典文件
-pub fn load_dictionary() -> &'static HashSet<String> {
-    DICTIONARY.get_or_init(|| {
-        let mut words = HashSet::new();
-
-        // 尝试从不同位置加载词典文件
-        let paths = [
-            "English.dic",             // 当前目录
-            "./English.dic",           // 当前目录（显式）
-            "../English.dic",          // 上级目录
-            "../../English.dic",       // 上上级目录
-            "./src-tauri/English.dic", // src-tauri 目录
-            "./resources/English.dic", // resources 目录
-            "./_up_/English.dic", // _up_目录
-            "_up_/English.dic", // _up_目录
-        ];
-
-        for path in paths {
-            if let Ok(dict) = read_dictionary_file(path) {
-                words = dict;
-                println!("成功加载词典文件: {}", path);
-                break;
-            }
-        }
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 
-        // 如果没有找到词典文件，使用内置的常见单词列表
-        if words.is_empty() {
-            println!("未找到词典文件，使用内置的常见单词列表");
-            for word in COMMON_WORDS {
-                words.insert(word.to_lowercase());
-            }
+// 首次使用时加载的词典，reload_dictionary 会整体替换；检查过程中持有 Arc，替换不影响正在进行的检查
+static DICTIONARY: RwLock<Option<Arc<HashSet<String>>>> = RwLock::new(None);
+
+// 用户自定义单词（统一以小写存储），与内置词典合并使用
+static USER_WORDS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+// 持久化用户词典的文件名，每行一个单词
+const USER_DICT_FILE: &str = "user_dict.txt";
+
+// 从数据目录读取已保存的用户词，文件不存在时为空
+fn read_user_words() -> HashSet<String> {
+    crate::storage::data_file(USER_DICT_FILE)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|content| {
+            content
+                .lines()
+                .map(|line| line.trim().to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 首次使用时从数据目录加载已保存的用户词
+fn user_word_store() -> &'static Mutex<HashSet<String>> {
+    USER_WORDS.get_or_init(|| Mutex::new(read_user_words()))
+}
+
+// 把用户词写入用户词典文件
+fn save_user_words(words: &HashSet<String>) -> Result<(), String> {
+    let mut sorted: Vec<&String> = words.iter().collect();
+    sorted.sort();
+    let content: String = sorted.iter().map(|word| format!("{}\n", word)).collect();
+
+    let path = crate::storage::data_file(USER_DICT_FILE)?;
+    std::fs::write(path, content).map_err(|e| format!("无法保存用户词典: {}", e))
+}
+
+// 添加用户自定义单词，并持久化到用户词典文件
+pub fn add_user_words(words: &[String]) -> Result<(), String> {
+    let mut user_words = user_word_store()
+        .lock()
+        .map_err(|_| "用户词典已损坏".to_string())?;
+
+    let previous = user_words.clone();
+    for word in words {
+        let word = word.trim();
+        if !word.is_empty() {
+            user_words.insert(word.to_lowercase());
         }
+    }
+
+    if user_words.len() == previous.len() {
+        return Ok(());
+    }
+    // 保存失败时撤销内存中的修改，保持与文件一致
+    let result = save_user_words(&user_words);
+    if result.is_err() {
+        *user_words = previous;
+    }
+    result
+}
+
+// 获取所有用户自定义单词（按字母排序）
+pub fn get_user_words() -> Vec<String> {
+    let mut words: Vec<String> = match user_word_store().lock() {
+        Ok(user_words) => user_words.iter().cloned().collect(),
+        Err(_) => Vec::new(),
+    };
+    words.sort();
+    words
+}
+
+// 从各个可能的位置查找并读取词典文件，都找不到时返回 None
+// 资源文件的查找目录，依次为当前目录、上级目录、src-tauri、resources 和打包后的 _up_ 目录
+const RESOURCE_DIRS: [&str; 8] = [
+    "",
+    "./",
+    "../",
+    "../../",
+    "./src-tauri/",
+    "./resources/",
+    "./_up_/",
+    "_up_/",
+];
+
+/// 在各资源目录中查找文件，返回第一个能被 `load` 成功读取的结果
+pub(crate) fn find_resource_file<T>(
+    file_name: &str,
+    label: &str,
+    load: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    RESOURCE_DIRS.iter().find_map(|dir| {
+        let path = format!("{}{}", dir, file_name);
+        let loaded = load(&path)?;
+        eprintln!("成功加载{}: {}", label, path);
+        Some(loaded)
+    })
+}
 
-        words
+fn find_dictionary_file() -> Option<HashSet<String>> {
+    find_resource_file("English.dic", "词典文件", |path| {
+        read_dictionary_file(path).ok()
     })
 }
 
+// 加载词典文件，首次调用时读取，之后返回已加载的词典
+pub fn load_dictionary() -> Arc<HashSet<String>> {
+    if let Some(dict) = DICTIONARY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        return Arc::clone(dict);
+    }
+
+    let mut dictionary = DICTIONARY.write().unwrap_or_else(|e| e.into_inner());
+    // 等待写锁期间可能已被其他线程加载
+    let dict = dictionary.get_or_insert_with(|| {
+        let words = find_dictionary_file().unwrap_or_else(|| {
+            // 如果没有找到词典文件，使用内置的常见单词列表
+            eprintln!("未找到词典文件，使用内置的常见单词列表");
+            COMMON_WORDS
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect()
+        });
+        Arc::new(words)
+    });
+    Arc::clone(dict)
+}
+
+/// 重新读取词典文件和用户词典，之后的检查立即使用新词典
+///
+/// 新词典在锁外读取完毕后整体替换；找不到词典文件时保留当前词典并返回错误。
+pub fn reload_dictionary() -> Result<(), String> {
+    let words =
+        find_dictionary_file().ok_or_else(|| "未找到词典文件，继续使用当前词典".to_string())?;
+    let user_words = read_user_words();
+
+    *DICTIONARY.write().map_err(|_| "词典已损坏".to_string())? = Some(Arc::new(words));
+    *user_word_store()
+        .lock()
+        .map_err(|_| "用户词典已损坏".to_string())? = user_words;
+    Ok(())
+}
+
 // 从文件中读取词典
 fn read_dictionary_file(path: &str) -> io::Result<HashSet<String>> {
     let file = File::open(path)?;
@@ -60,45 +168,8 @@ fn read_dictionary_file(path: &str) -> io::Result<HashSet<String>> {
             if let Some(idx) = line.find('/') {
                 let word = line[..idx].to_string();
                 if !word.is_empty() {
-                    // 添加原始单词
+                    // 只保存基形，复数、时态等变形交给 is_word_in_dictionary 判断
                     words.insert(word.to_lowercase());
-
-                    // 如果单词是数字，跳过
-                    if word.chars().all(|c| c.is_numeric()) {
-                        continue;
-                    }
-
-                    // 添加常见的词形变化
-                    let word_lower = word.to_lowercase();
-
-                    // 添加复数形式
-                    if !word_lower.ends_with('s') {
-                        words.insert(format!("{}s", word_lower));
-                    }
-
-                    // 添加过去式和过去分词
-                    if word_lower.ends_with('e') {
-                        words.insert(format!("{}d", word_lower));
-                    } else {
-                        words.insert(format!("{}ed", word_lower));
-                    }
-
-                    // 添加现在分词
-                    if word_lower.ends_with('e') {
-                        words.insert(format!("{}ing", &word_lower[..word_lower.len() - 1]));
-                    } else {
-                        words.insert(format!("{}ing", word_lower));
-                    }
-
-                    // 添加形容词形式
-                    if !word_lower.ends_with("al") {
-                        words.insert(format!("{}al", word_lower));
-                    }
-
-                    // 添加副词形式
-                    if !word_lower.ends_with("ly") {
-                        words.insert(format!("{}ly", word_lower));
-                    }
                 }
             } else {
                 // 如果没有词性标记，直接添加整行
@@ -644,9 +715,13 @@ fn read_dictionary_file(path: &str) -> io::Result<HashSet<String>> {
 // 检查单词是否在词典中，考虑常见的单词变形
 pub fn is_word_in_dictionary(word: &str) -> bool {
     let dict = load_dictionary();
+    // 用户词与内置词典使用相同的变形规则
+    let user_words = user_word_store().lock().ok();
+    let contains =
+        |w: &str| dict.contains(w) || user_words.as_ref().is_some_and(|words| words.contains(w));
 
     // 保留原始大小写检查
-    if dict.contains(word) {
+    if contains(word) {
         return true;
     }
 
@@ -658,15 +733,21 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     let word_lower = word.to_lowercase();
 
     // 直接检查单词是否在词典中
-    if dict.contains(&word_lower) {
+    if contains(&word_lower) {
         return true;
     }
 
+    is_inflected_form(&word_lower, contains)
+}
+
+// 判断小写单词是否是词典中某个基形的变形（复数、时态、派生词和常见不规则变化）
+// 词典只保存基形，变形都在这里按规则还原后查找
+fn is_inflected_form(word_lower: &str, contains: impl Fn(&str) -> bool) -> bool {
     // 检查单词的基本形式
     // 1. 去掉结尾的 's'（复数形式）
     if word_lower.ends_with('s') && word_lower.len() > 2 {
         let singular = &word_lower[..word_lower.len() - 1];
-        if dict.contains(singular) {
+        if contains(singular) {
             return true;
         }
     }
@@ -674,29 +755,39 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 2. 去掉结尾的 'es'（复数形式）
     if word_lower.ends_with("es") && word_lower.len() > 3 {
         let singular = &word_lower[..word_lower.len() - 2];
-        if dict.contains(singular) {
+        if contains(singular) {
             return true;
         }
     }
 
+    // 去掉结尾的 'ies'/'ied'，还原为 'y'，如 'studies'/'studied' -> 'study'
+    for suffix in ["ies", "ied"] {
+        if word_lower.ends_with(suffix) && word_lower.len() > 4 {
+            let base_y = format!("{}y", &word_lower[..word_lower.len() - 3]);
+            if contains(&base_y) {
+                return true;
+            }
+        }
+    }
+
     // 3. 去掉结尾的 'ed'（过去式）
     if word_lower.ends_with("ed") && word_lower.len() > 3 {
         let base = &word_lower[..word_lower.len() - 2];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
 
         // 处理双写辅音字母的情况，如 'stopped' -> 'stop'
         if word_lower.len() > 4 && base.ends_with(base.chars().last().unwrap()) {
             let base_single = &base[..base.len() - 1];
-            if dict.contains(base_single) {
+            if contains(base_single) {
                 return true;
             }
         }
 
         // 处理以 'e' 结尾的动词，如 'related' -> 'relate'
         let base_e = format!("{}e", base);
-        if dict.contains(&base_e) {
+        if contains(&base_e) {
             return true;
         }
     }
@@ -704,20 +795,20 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 4. 去掉结尾的 'ing'（现在分词）
     if word_lower.ends_with("ing") && word_lower.len() > 4 {
         let base = &word_lower[..word_lower.len() - 3];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
 
         // 处理去掉 'e' 的情况，如 'making' -> 'make'
         let base_e = format!("{}e", base);
-        if dict.contains(&base_e) {
+        if contains(&base_e) {
             return true;
         }
 
         // 处理双写辅音字母的情况，如 'running' -> 'run'
         if word_lower.len() > 5 && base.ends_with(base.chars().last().unwrap()) {
             let base_single = &base[..base.len() - 1];
-            if dict.contains(base_single) {
+            if contains(base_single) {
                 return true;
             }
         }
@@ -726,7 +817,7 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 5. 去掉结尾的 'ly'（副词）
     if word_lower.ends_with("ly") && word_lower.len() > 3 {
         let base = &word_lower[..word_lower.len() - 2];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
     }
@@ -734,7 +825,7 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 6. 去掉结尾的 'er'（比较级）
     if word_lower.ends_with("er") && word_lower.len() > 3 {
         let base = &word_lower[..word_lower.len() - 2];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
     }
@@ -742,7 +833,7 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 7. 去掉结尾的 'est'（最高级）
     if word_lower.ends_with("est") && word_lower.len() > 4 {
         let base = &word_lower[..word_lower.len() - 3];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
     }
@@ -751,7 +842,7 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     if word_lower.ends_with("tion") && word_lower.len() > 5 {
         let base = &word_lower[..word_lower.len() - 4];
         let base_e = format!("{}e", base);
-        if dict.contains(&base_e) {
+        if contains(&base_e) {
             return true;
         }
     }
@@ -759,7 +850,7 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 9. 处理 'ment' 结尾的名词，如 'development' -> 'develop'
     if word_lower.ends_with("ment") && word_lower.len() > 6 {
         let base = &word_lower[..word_lower.len() - 4];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
     }
@@ -767,21 +858,21 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 10. 处理 'able'/'ible' 结尾的形容词，如 'readable' -> 'read'
     if (word_lower.ends_with("able") || word_lower.ends_with("ible")) && word_lower.len() > 5 {
         let base = &word_lower[..word_lower.len() - 4];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
 
         // 处理去掉 'e' 的情况，如 'reliable' -> 'rely'
         if base.len() > 1 && base.ends_with('i') {
             let base_y = format!("{}y", &base[..base.len() - 1]);
-            if dict.contains(&base_y) {
+            if contains(&base_y) {
                 return true;
             }
         }
 
         // 处理以 'e' 结尾的动词，如 'usable' -> 'use'
         let base_e = format!("{}e", base);
-        if dict.contains(&base_e) {
+        if contains(&base_e) {
             return true;
         }
     }
@@ -790,21 +881,21 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     if word_lower.ends_with("al") && word_lower.len() > 4 {
         // 检查基本形式
         let base = &word_lower[..word_lower.len() - 2];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
 
         // 处理 'tion' -> 'tional'，如 'functional' -> 'function'
         if base.ends_with("tion") {
             let function_base = &base[..base.len() - 3]; // 去掉 'ion'
-            if dict.contains(function_base) {
+            if contains(function_base) {
                 return true;
             }
         }
 
         // 处理以 'e' 结尾的词，如 'cultural' -> 'culture'
         let base_e = format!("{}e", base);
-        if dict.contains(&base_e) {
+        if contains(&base_e) {
             return true;
         }
     }
@@ -812,13 +903,13 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 12. 处理 'ive' 结尾的形容词，如 'productive' -> 'product'
     if word_lower.ends_with("ive") && word_lower.len() > 4 {
         let base = &word_lower[..word_lower.len() - 3];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
 
         // 处理 'duct' -> 'ductive'，如 'productive' -> 'product'
         let base_e = format!("{}e", base);
-        if dict.contains(&base_e) {
+        if contains(&base_e) {
             return true;
         }
 
@@ -826,7 +917,7 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
         if base.ends_with('t') {
             let alt_base = &base[..base.len() - 1];
             let alt_base_e = format!("{}e", alt_base);
-            if dict.contains(&alt_base_e) {
+            if contains(&alt_base_e) {
                 return true;
             }
         }
@@ -835,13 +926,13 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 13. 处理 'ize'/'ise' 结尾的动词，如 'optimize' -> 'optimal'
     if (word_lower.ends_with("ize") || word_lower.ends_with("ise")) && word_lower.len() > 4 {
         let base = &word_lower[..word_lower.len() - 3];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
 
         // 处理 'al' -> 'alize'，如 'formalize' -> 'formal'
         let base_al = format!("{}al", base);
-        if dict.contains(&base_al) {
+        if contains(&base_al) {
             return true;
         }
     }
@@ -849,7 +940,7 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     // 14. 处理 'ful' 结尾的形容词，如 'helpful' -> 'help'
     if word_lower.ends_with("ful") && word_lower.len() > 4 {
         let base = &word_lower[..word_lower.len() - 3];
-        if dict.contains(base) {
+        if contains(base) {
             return true;
         }
     }
@@ -860,13 +951,13 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
 
         // 处理 'ive' -> 'ivity'，如 'activity' -> 'active'
         let base_ive = format!("{}ive", base);
-        if dict.contains(&base_ive) {
+        if contains(&base_ive) {
             return true;
         }
 
         // 处理 'al' -> 'ality'，如 'reality' -> 'real'
         let base_al = format!("{}al", base);
-        if dict.contains(&base_al) {
+        if contains(&base_al) {
             return true;
         }
     }
@@ -912,87 +1003,87 @@ pub fn is_word_in_dictionary(word: &str) -> bool {
     ];
 
     for &(adj, base) in &common_adjectives {
-        if word_lower == adj && dict.contains(base) {
+        if word_lower == adj && contains(base) {
             return true;
         }
     }
 
     // 17. 检查常见的不规则变化
-    match word_lower.as_str() {
-        "am" | "are" | "is" | "was" | "were" => return dict.contains("be"),
-        "has" | "have" | "had" | "having" => return dict.contains("have"),
-        "does" | "did" | "done" | "doing" => return dict.contains("do"),
-        "goes" | "went" | "gone" | "going" => return dict.contains("go"),
-        "makes" | "made" | "making" => return dict.contains("make"),
-        "takes" | "took" | "taken" | "taking" => return dict.contains("take"),
-        "comes" | "came" | "coming" => return dict.contains("come"),
-        "sees" | "saw" | "seen" | "seeing" => return dict.contains("see"),
-        "knows" | "knew" | "known" | "knowing" => return dict.contains("know"),
-        "gets" | "got" | "gotten" | "getting" => return dict.contains("get"),
-        "gives" | "gave" | "given" | "giving" => return dict.contains("give"),
-        "finds" | "found" | "finding" => return dict.contains("find"),
-        "thinks" | "thought" | "thinking" => return dict.contains("think"),
-        "tells" | "told" | "telling" => return dict.contains("tell"),
-        "becomes" | "became" | "becoming" => return dict.contains("become"),
-        "shows" | "showed" | "shown" | "showing" => return dict.contains("show"),
-        "leaves" | "left" | "leaving" => return dict.contains("leave"),
-        "feels" | "felt" | "feeling" => return dict.contains("feel"),
-        "puts" | "putting" => return dict.contains("put"),
-        "means" | "meant" | "meaning" => return dict.contains("mean"),
-        "keeps" | "kept" | "keeping" => return dict.contains("keep"),
-        "lets" | "letting" => return dict.contains("let"),
-        "begins" | "began" | "begun" | "beginning" => return dict.contains("begin"),
-        "seems" | "seemed" | "seeming" => return dict.contains("seem"),
-        "helps" | "helped" | "helping" => return dict.contains("help"),
-        "talks" | "talked" | "talking" => return dict.contains("talk"),
-        "turns" | "turned" | "turning" => return dict.contains("turn"),
-        "starts" | "started" | "starting" => return dict.contains("start"),
-        "hears" | "heard" | "hearing" => return dict.contains("hear"),
-        "plays" | "played" | "playing" => return dict.contains("play"),
-        "runs" | "ran" | "running" => return dict.contains("run"),
-        "moves" | "moved" | "moving" => return dict.contains("move"),
-        "lives" | "lived" | "living" => return dict.contains("live"),
-        "believes" | "believed" | "believing" => return dict.contains("believe"),
-        "says" | "said" | "saying" => return dict.contains("say"),
-        "sits" | "sat" | "sitting" => return dict.contains("sit"),
-        "stands" | "stood" | "standing" => return dict.contains("stand"),
-        "loses" | "lost" | "losing" => return dict.contains("lose"),
-        "pays" | "paid" | "paying" => return dict.contains("pay"),
-        "meets" | "met" | "meeting" => return dict.contains("meet"),
-        "includes" | "included" | "including" => return dict.contains("include"),
-        "continues" | "continued" | "continuing" => return dict.contains("continue"),
-        "sets" | "setting" => return dict.contains("set"),
-        "learns" | "learned" | "learnt" | "learning" => return dict.contains("learn"),
-        "changes" | "changed" | "changing" => return dict.contains("change"),
-        "leads" | "led" | "leading" => return dict.contains("lead"),
-        "understands" | "understood" | "understanding" => return dict.contains("understand"),
-        "watches" | "watched" | "watching" => return dict.contains("watch"),
-        "follows" | "followed" | "following" => return dict.contains("follow"),
-        "stops" | "stopped" | "stopping" => return dict.contains("stop"),
-        "creates" | "created" | "creating" => return dict.contains("create"),
-        "speaks" | "spoke" | "spoken" | "speaking" => return dict.contains("speak"),
-        "reads" | "read" | "reading" => return dict.contains("read"),
-        "spends" | "spent" | "spending" => return dict.contains("spend"),
-        "grows" | "grew" | "grown" | "growing" => return dict.contains("grow"),
-        "opens" | "opened" | "opening" => return dict.contains("open"),
-        "walks" | "walked" | "walking" => return dict.contains("walk"),
-        "wins" | "won" | "winning" => return dict.contains("win"),
-        "teaches" | "taught" | "teaching" => return dict.contains("teach"),
-        "offers" | "offered" | "offering" => return dict.contains("offer"),
-        "remembers" | "remembered" | "remembering" => return dict.contains("remember"),
-        "considers" | "considered" | "considering" => return dict.contains("consider"),
-        "appears" | "appeared" | "appearing" => return dict.contains("appear"),
-        "buys" | "bought" | "buying" => return dict.contains("buy"),
-        "serves" | "served" | "serving" => return dict.contains("serve"),
-        "dies" | "died" | "dying" => return dict.contains("die"),
-        "sends" | "sent" | "sending" => return dict.contains("send"),
-        "builds" | "built" | "building" => return dict.contains("build"),
-        "stays" | "stayed" | "staying" => return dict.contains("stay"),
-        "falls" | "fell" | "fallen" | "falling" => return dict.contains("fall"),
-        "cuts" | "cutting" => return dict.contains("cut"),
-        "reaches" | "reached" | "reaching" => return dict.contains("reach"),
-        "kills" | "killed" | "killing" => return dict.contains("kill"),
-        "raises" | "raised" | "raising" => return dict.contains("raise"),
+    match word_lower {
+        "am" | "are" | "is" | "was" | "were" => return contains("be"),
+        "has" | "have" | "had" | "having" => return contains("have"),
+        "does" | "did" | "done" | "doing" => return contains("do"),
+        "goes" | "went" | "gone" | "going" => return contains("go"),
+        "makes" | "made" | "making" => return contains("make"),
+        "takes" | "took" | "taken" | "taking" => return contains("take"),
+        "comes" | "came" | "coming" => return contains("come"),
+        "sees" | "saw" | "seen" | "seeing" => return contains("see"),
+        "knows" | "knew" | "known" | "knowing" => return contains("know"),
+        "gets" | "got" | "gotten" | "getting" => return contains("get"),
+        "gives" | "gave" | "given" | "giving" => return contains("give"),
+        "finds" | "found" | "finding" => return contains("find"),
+        "thinks" | "thought" | "thinking" => return contains("think"),
+        "tells" | "told" | "telling" => return contains("tell"),
+        "becomes" | "became" | "becoming" => return contains("become"),
+        "shows" | "showed" | "shown" | "showing" => return contains("show"),
+        "leaves" | "left" | "leaving" => return contains("leave"),
+        "feels" | "felt" | "feeling" => return contains("feel"),
+        "puts" | "putting" => return contains("put"),
+        "means" | "meant" | "meaning" => return contains("mean"),
+        "keeps" | "kept" | "keeping" => return contains("keep"),
+        "lets" | "letting" => return contains("let"),
+        "begins" | "began" | "begun" | "beginning" => return contains("begin"),
+        "seems" | "seemed" | "seeming" => return contains("seem"),
+        "helps" | "helped" | "helping" => return contains("help"),
+        "talks" | "talked" | "talking" => return contains("talk"),
+        "turns" | "turned" | "turning" => return contains("turn"),
+        "starts" | "started" | "starting" => return contains("start"),
+        "hears" | "heard" | "hearing" => return contains("hear"),
+        "plays" | "played" | "playing" => return contains("play"),
+        "runs" | "ran" | "running" => return contains("run"),
+        "moves" | "moved" | "moving" => return contains("move"),
+        "lives" | "lived" | "living" => return contains("live"),
+        "believes" | "believed" | "believing" => return contains("believe"),
+        "says" | "said" | "saying" => return contains("say"),
+        "sits" | "sat" | "sitting" => return contains("sit"),
+        "stands" | "stood" | "standing" => return contains("stand"),
+        "loses" | "lost" | "losing" => return contains("lose"),
+        "pays" | "paid" | "paying" => return contains("pay"),
+        "meets" | "met" | "meeting" => return contains("meet"),
+        "includes" | "included" | "including" => return contains("include"),
+        "continues" | "continued" | "continuing" => return contains("continue"),
+        "sets" | "setting" => return contains("set"),
+        "learns" | "learned" | "learnt" | "learning" => return contains("learn"),
+        "changes" | "changed" | "changing" => return contains("change"),
+        "leads" | "led" | "leading" => return contains("lead"),
+        "understands" | "understood" | "understanding" => return contains("understand"),
+        "watches" | "watched" | "watching" => return contains("watch"),
+        "follows" | "followed" | "following" => return contains("follow"),
+        "stops" | "stopped" | "stopping" => return contains("stop"),
+        "creates" | "created" | "creating" => return contains("create"),
+        "speaks" | "spoke" | "spoken" | "speaking" => return contains("speak"),
+        "reads" | "read" | "reading" => return contains("read"),
+        "spends" | "spent" | "spending" => return contains("spend"),
+        "grows" | "grew" | "grown" | "growing" => return contains("grow"),
+        "opens" | "opened" | "opening" => return contains("open"),
+        "walks" | "walked" | "walking" => return contains("walk"),
+        "wins" | "won" | "winning" => return contains("win"),
+        "teaches" | "taught" | "teaching" => return contains("teach"),
+        "offers" | "offered" | "offering" => return contains("offer"),
+        "remembers" | "remembered" | "remembering" => return contains("remember"),
+        "considers" | "considered" | "considering" => return contains("consider"),
+        "appears" | "appeared" | "appearing" => return contains("appear"),
+        "buys" | "bought" | "buying" => return contains("buy"),
+        "serves" | "served" | "serving" => return contains("serve"),
+        "dies" | "died" | "dying" => return contains("die"),
+        "sends" | "sent" | "sending" => return contains("send"),
+        "builds" | "built" | "building" => return contains("build"),
+        "stays" | "stayed" | "staying" => return contains("stay"),
+        "falls" | "fell" | "fallen" | "falling" => return contains("fall"),
+        "cuts" | "cutting" => return contains("cut"),
+        "reaches" | "reached" | "reaching" => return contains("reach"),
+        "kills" | "killed" | "killing" => return contains("kill"),
+        "raises" | "raised" | "raising" => return contains("raise"),
         _ => false,
     }
 }
@@ -1224,3 +1315,51 @@ const COMMON_WORDS: &[&str] = &[
     "meant",
     "meaning",
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 只含基形的小词典，验证变形不需要预先生成也能识别
+    fn base_words() -> HashSet<String> {
+        [
+            "run", "study", "relate", "stop", "make", "box", "develop", "help",
+        ]
+        .iter()
+        .map(|word| word.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn inflected_forms_of_base_words_are_accepted() {
+        let words = base_words();
+        let contains = |word: &str| words.contains(word);
+        for word in [
+            "running",
+            "runs",
+            "studies",
+            "studied",
+            "related",
+            "stopped",
+            "making",
+            "boxes",
+            "development",
+            "helpful",
+        ] {
+            assert!(is_inflected_form(word, contains), "{} 应被识别为变形", word);
+        }
+    }
+
+    #[test]
+    fn unrelated_words_are_rejected() {
+        let words = base_words();
+        let contains = |word: &str| words.contains(word);
+        for word in ["xyzzy", "studio", "runway"] {
+            assert!(
+                !is_inflected_form(word, contains),
+                "{} 不应被识别为变形",
+                word
+            );
+        }
+    }
+}
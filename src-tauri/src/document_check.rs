@@ -0,0 +1,826 @@
+// 文档级检查：需要统计全文信息后才能判断的问题
+use crate::byte_to_char_index;
+use crate::config::AnalysisConfig;
+use crate::detect_language;
+use crate::traditional::is_chinese_char;
+use crate::TextIssue;
+use crate::MAX_ISSUES;
+use regex::Regex;
+
+// 判定段落语言所需的最小文字量
+const MIN_PARAGRAPH_UNITS: usize = 20;
+// 主语言占比达到该值时才视为单语文档
+const MAIN_LANGUAGE_RATIO: f64 = 0.7;
+// 段落中主语言占比低于该值时视为语言混杂
+const PARAGRAPH_MAIN_RATIO: f64 = 0.3;
+
+/// 文本中的一个段落（以空行分隔）
+struct Paragraph<'a> {
+    /// 段落首行的行号（从 0 开始）
+    start_line: usize,
+    lines: Vec<&'a str>,
+}
+
+// 按空行切分段落，跳过 ``` 代码块
+fn split_paragraphs(text: &str) -> Vec<Paragraph<'_>> {
+    let mut paragraphs = Vec::new();
+    let mut current: Option<Paragraph> = None;
+    let mut in_code_block = false;
+
+    for (idx, line) in text.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            paragraphs.extend(current.take());
+            continue;
+        }
+        if in_code_block || line.trim().is_empty() {
+            paragraphs.extend(current.take());
+            continue;
+        }
+
+        current
+            .get_or_insert_with(|| Paragraph {
+                start_line: idx,
+                lines: Vec::new(),
+            })
+            .lines
+            .push(line);
+    }
+    paragraphs.extend(current);
+
+    paragraphs
+}
+
+// 统计中文字符数和英文单词数，英文单词按两个字计，使两种语言的文字量大致可比
+fn language_units(text: &str) -> (usize, usize) {
+    let chinese = text.chars().filter(|c| is_chinese_char(*c)).count();
+    let english_words = text
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| !word.is_empty())
+        .count();
+
+    (chinese, english_words * 2)
+}
+
+// 引用、摘要、关键词这类段落允许使用另一种语言
+fn is_expected_foreign(paragraph: &Paragraph) -> bool {
+    let first = paragraph.lines[0].trim_start();
+    if first.starts_with('>') || first.starts_with('“') || first.starts_with('"') {
+        return true;
+    }
+
+    let lower = first.to_lowercase();
+    ["abstract", "keywords", "key words", "摘要", "关键词"]
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+}
+
+// 参考文献部分通常中英混排，之后的段落不再检查
+fn is_references_heading(line: &str) -> bool {
+    let heading = line.trim().trim_start_matches('#').trim().to_lowercase();
+    ["参考文献", "references", "bibliography"].contains(&heading.as_str())
+}
+
+// Check for paragraphs whose language differs from the rest of the document
+pub fn check_language_consistency(text: &str, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // 参考文献之后以及引用、摘要等段落不参与统计和检查
+    let paragraphs: Vec<Paragraph> = split_paragraphs(text)
+        .into_iter()
+        .take_while(|paragraph| !is_references_heading(paragraph.lines[0]))
+        .filter(|paragraph| !is_expected_foreign(paragraph))
+        .collect();
+    let (total_zh, total_en) = paragraphs.iter().fold((0, 0), |(zh, en), paragraph| {
+        let (p_zh, p_en) = language_units(&paragraph.lines.join("\n"));
+        (zh + p_zh, en + p_en)
+    });
+    let total = total_zh + total_en;
+    if total == 0 {
+        return;
+    }
+
+    // 全文需有明显的主语言，中英文均衡的文档不检查
+    let main_is_chinese = total_zh >= total_en;
+    let main_ratio = total_zh.max(total_en) as f64 / total as f64;
+    if main_ratio < MAIN_LANGUAGE_RATIO {
+        return;
+    }
+
+    for paragraph in &paragraphs {
+        let (zh, en) = language_units(&paragraph.lines.join("\n"));
+        if zh + en < MIN_PARAGRAPH_UNITS {
+            continue;
+        }
+        let main_units = if main_is_chinese { zh } else { en };
+        if (main_units as f64 / (zh + en) as f64) >= PARAGRAPH_MAIN_RATIO {
+            continue;
+        }
+
+        let (main_name, paragraph_name, paragraph_language) = if main_is_chinese {
+            ("中文", "英文", "en")
+        } else {
+            ("英文", "中文", "zh")
+        };
+        let first_line = paragraph.lines[0];
+
+        issues.push(TextIssue {
+            line_number: paragraph.start_line + 1,
+            start: 0,
+            end: first_line.chars().count(),
+            issue_type: "语言混杂".to_string(),
+            message: format!(
+                "语言混杂：该段落（共 {} 行）主要为{}，而全文以{}为主",
+                paragraph.lines.len(),
+                paragraph_name,
+                main_name
+            ),
+            suggestion: "请确认是否为误粘贴的内容，或将其标记为引用".to_string(),
+            rule_id: "language_consistency".to_string(),
+            replacement: None,
+            confidence: 0.6,
+            severity: "info".to_string(),
+            language: paragraph_language.to_string(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+const MONTHS: &str = "January|February|March|April|May|June|July|August|September|October|\
+                      November|December|Jan|Feb|Mar|Apr|Jun|Jul|Aug|Sept|Sep|Oct|Nov|Dec";
+
+// 常见的英文日期格式：(名称, 正则, 示例)
+fn date_formats() -> Vec<(&'static str, String, &'static str)> {
+    vec![
+        (
+            "month_day_year",
+            format!(r"\b(?:{})\.? \d{{1,2}}, \d{{4}}\b", MONTHS),
+            "Jan 5, 2020",
+        ),
+        (
+            "day_month_year",
+            format!(r"\b\d{{1,2}} (?:{})\.? \d{{4}}\b", MONTHS),
+            "5 January 2020",
+        ),
+        ("iso", r"\b\d{4}-\d{2}-\d{2}\b".to_string(), "2020-01-05"),
+        (
+            "slash",
+            r"\b\d{1,2}/\d{1,2}/\d{4}\b".to_string(),
+            "01/05/2020",
+        ),
+    ]
+}
+
+// Check for dates written in formats other than the document's dominant one
+pub fn check_date_format_consistency(text: &str, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // (格式下标, 行号, 起始字节, 结束字节)
+    let mut occurrences: Vec<(usize, usize, usize, usize)> = Vec::new();
+    let formats = date_formats();
+    let regexes: Vec<Regex> = formats
+        .iter()
+        .filter_map(|(_, pattern, _)| Regex::new(pattern).ok())
+        .collect();
+    if regexes.len() != formats.len() {
+        return;
+    }
+
+    for (line_idx, line) in text.lines().enumerate() {
+        // 参考文献中的日期格式由引用规范决定，不参与统计
+        if is_references_heading(line) {
+            break;
+        }
+        for (format_idx, re) in regexes.iter().enumerate() {
+            for mat in re.find_iter(line) {
+                occurrences.push((format_idx, line_idx, mat.start(), mat.end()));
+            }
+        }
+    }
+
+    // 主流格式：出现次数最多，次数相同时取最先出现的
+    let mut counts = vec![0usize; formats.len()];
+    for (format_idx, _, _, _) in &occurrences {
+        counts[*format_idx] += 1;
+    }
+    if counts.iter().filter(|count| **count > 0).count() < 2 {
+        return;
+    }
+    occurrences.sort_by_key(|(_, line_idx, start, _)| (*line_idx, *start));
+    let main_format = occurrences
+        .iter()
+        .map(|(format_idx, _, _, _)| *format_idx)
+        .fold(None, |best: Option<usize>, idx| match best {
+            Some(best) if counts[best] >= counts[idx] => Some(best),
+            _ => Some(idx),
+        });
+    let main_format = match main_format {
+        Some(idx) => idx,
+        None => return,
+    };
+
+    let lines: Vec<&str> = text.lines().collect();
+    for (format_idx, line_idx, start, end) in occurrences {
+        if format_idx == main_format {
+            continue;
+        }
+        let line = lines[line_idx];
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, start),
+            end: byte_to_char_index(line, end),
+            issue_type: "日期格式".to_string(),
+            message: format!("日期格式不统一: '{}'", &line[start..end]),
+            suggestion: format!(
+                "全文主要使用 '{}' 这样的格式，建议统一",
+                formats[main_format].2
+            ),
+            rule_id: "date_format".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "info".to_string(),
+            language: detect_language(line),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 章节、条款等序号："第3章""第三节"；年份、"第一时间"等其他用法不在统计范围内
+const ORDINAL_PATTERN: &str =
+    r"第([0-9]+|[一二两三四五六七八九十百零〇]+)(章|节|部分|篇|条|款|编|卷)";
+// 中文数字转换只支持到这个值
+const MAX_CHINESE_NUMBER: usize = 999;
+
+// 解析不超过三位的中文数字，如 "十二" "一百零五"
+fn parse_chinese_number(text: &str) -> Option<usize> {
+    let mut total = 0;
+    let mut digit: Option<usize> = None;
+    for c in text.chars() {
+        match c {
+            '零' | '〇' => digit = Some(0),
+            '一' => digit = Some(1),
+            '二' | '两' => digit = Some(2),
+            '三' => digit = Some(3),
+            '四' => digit = Some(4),
+            '五' => digit = Some(5),
+            '六' => digit = Some(6),
+            '七' => digit = Some(7),
+            '八' => digit = Some(8),
+            '九' => digit = Some(9),
+            // "十二" 省略了前面的 "一"
+            '十' => total += digit.take().unwrap_or(1) * 10,
+            '百' => total += digit.take()? * 100,
+            _ => return None,
+        }
+    }
+    Some(total + digit.unwrap_or(0))
+}
+
+// 把不超过三位的数字写成中文，如 12 -> "十二"，105 -> "一百零五"
+fn to_chinese_number(n: usize) -> Option<String> {
+    const DIGITS: [&str; 10] = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+    if n == 0 || n > MAX_CHINESE_NUMBER {
+        return None;
+    }
+
+    let (hundreds, tens, units) = (n / 100, n / 10 % 10, n % 10);
+    let mut result = String::new();
+    if hundreds > 0 {
+        result.push_str(DIGITS[hundreds]);
+        result.push('百');
+        if tens == 0 && units > 0 {
+            result.push('零');
+        }
+    }
+    if tens > 0 {
+        // 十几不写 "一十"，一百一十则要写
+        if tens > 1 || hundreds > 0 {
+            result.push_str(DIGITS[tens]);
+        }
+        result.push('十');
+    }
+    if units > 0 {
+        result.push_str(DIGITS[units]);
+    }
+    Some(result)
+}
+
+// Check that chapter / section numbers use either Chinese or Arabic numerals consistently
+pub fn check_number_style_consistency(text: &str, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let re = match Regex::new(ORDINAL_PATTERN) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    // (行号, 起始字节, 结束字节, 数字部分, 是否为阿拉伯数字)
+    let mut occurrences: Vec<(usize, usize, usize, String, bool)> = Vec::new();
+    for (line_idx, line) in text.lines().enumerate() {
+        // 参考文献中的 "第3卷" 等由引用规范决定，不参与统计
+        if is_references_heading(line) {
+            break;
+        }
+        for caps in re.captures_iter(line) {
+            let (whole, number) = match (caps.get(0), caps.get(1)) {
+                (Some(whole), Some(number)) => (whole, number),
+                _ => continue,
+            };
+            let arabic = number.as_str().starts_with(|c: char| c.is_ascii_digit());
+            occurrences.push((
+                line_idx,
+                whole.start(),
+                whole.end(),
+                number.as_str().to_string(),
+                arabic,
+            ));
+        }
+    }
+
+    let arabic_count = occurrences.iter().filter(|o| o.4).count();
+    let chinese_count = occurrences.len() - arabic_count;
+    if arabic_count == 0 || chinese_count == 0 {
+        return;
+    }
+    // 次数相同时以最先出现的写法为准
+    let main_arabic = match arabic_count.cmp(&chinese_count) {
+        std::cmp::Ordering::Greater => true,
+        std::cmp::Ordering::Less => false,
+        std::cmp::Ordering::Equal => occurrences[0].4,
+    };
+    let main_name = if main_arabic {
+        "阿拉伯数字"
+    } else {
+        "中文数字"
+    };
+
+    let lines: Vec<&str> = text.lines().collect();
+    for (line_idx, start, end, number, arabic) in occurrences {
+        if arabic == main_arabic {
+            continue;
+        }
+        let line = lines[line_idx];
+        let original = &line[start..end];
+        let converted = if arabic {
+            number.parse().ok().and_then(to_chinese_number)
+        } else {
+            parse_chinese_number(&number)
+                .filter(|n| *n > 0)
+                .map(|n| n.to_string())
+        };
+        let replacement = converted.map(|converted| original.replacen(&number, &converted, 1));
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, start),
+            end: byte_to_char_index(line, end),
+            issue_type: "数字格式".to_string(),
+            message: format!("序号中的数字写法不统一: '{}'", original),
+            suggestion: match &replacement {
+                Some(replacement) => format!(
+                    "建议修改为: '{}'，全文序号主要使用{}",
+                    replacement, main_name
+                ),
+                None => format!("全文序号主要使用{}，建议统一", main_name),
+            },
+            rule_id: "number_style".to_string(),
+            replacement,
+            confidence: 0.8,
+            severity: "info".to_string(),
+            language: detect_language(line),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// GB/T 7714 的文献类型标识，如 [J] 期刊、[M] 专著、[D] 学位论文、[EB/OL] 电子资源
+pub const GBT_TYPE_PATTERN: &str = r"\[(?:[MCNJDRSPGZA]|EB|DB|CP)(?:/OL)?\]";
+
+// 其它引用格式：(名称, 正则)，包括正文中的 APA 引用和 APA、MLA 风格的文献条目
+const OTHER_CITATION_STYLES: &[(&str, &str)] = &[
+    (
+        "APA",
+        r"\([A-Z][A-Za-z-]+(?: et al\.)?(?: (?:&|and) [A-Z][A-Za-z-]+)?,\s*\d{4}[a-z]?\)",
+    ),
+    (
+        "APA",
+        r"^\s*[A-Z][A-Za-z'-]+, (?:[A-Z]\.\s?)+.*\(\d{4}[a-z]?\)\.",
+    ),
+    (
+        "MLA",
+        r#"^\s*[A-Z][A-Za-z'-]+, [A-Z][a-z]+(?: [A-Z]\.)?\. [“"]"#,
+    ),
+];
+
+// Check for GB/T 7714 references mixed with other citation styles, and for
+// numbered reference entries missing the GB/T document type identifier
+pub fn check_reference_format(text: &str, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let gbt_entry = match Regex::new(&format!(r"^\s*\[\d+\]\s*\S.*{}", GBT_TYPE_PATTERN)) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+    let type_marker = match Regex::new(GBT_TYPE_PATTERN) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+    let numbered_entry = match Regex::new(r"^\s*\[\d+\]\s*\S") {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+    let other_styles: Vec<(&str, Regex)> = OTHER_CITATION_STYLES
+        .iter()
+        .filter_map(|(name, pattern)| Regex::new(pattern).ok().map(|re| (*name, re)))
+        .collect();
+
+    let lines: Vec<&str> = text.lines().collect();
+    // 国标条目所在的行，以及其它格式的 (行号, 起始字节, 结束字节, 格式名)
+    let mut gbt_lines: Vec<usize> = Vec::new();
+    let mut others: Vec<(usize, usize, usize, &str)> = Vec::new();
+    // 参考文献部分中带编号但没有文献类型标识的条目
+    let mut untyped: Vec<usize> = Vec::new();
+    let mut in_references = false;
+
+    for (line_idx, line) in lines.iter().enumerate() {
+        if is_references_heading(line) {
+            in_references = true;
+            continue;
+        }
+        if gbt_entry.is_match(line) {
+            gbt_lines.push(line_idx);
+            continue;
+        }
+        if in_references && numbered_entry.is_match(line) && !type_marker.is_match(line) {
+            untyped.push(line_idx);
+        }
+        for (name, re) in &other_styles {
+            if let Some(mat) = re.find(line) {
+                others.push((line_idx, mat.start(), mat.end(), name));
+                break;
+            }
+        }
+    }
+
+    // 混用时以数量较多的格式为准，数量相同时以国标为准
+    let gbt_is_main = gbt_lines.len() >= others.len();
+    if !gbt_lines.is_empty() && !others.is_empty() {
+        let mixed: Vec<(usize, usize, usize, &str)> = if gbt_is_main {
+            others.clone()
+        } else {
+            gbt_lines
+                .iter()
+                .map(|&line_idx| (line_idx, 0, lines[line_idx].len(), "GB/T 7714"))
+                .collect()
+        };
+        let main_name = if gbt_is_main {
+            "GB/T 7714"
+        } else {
+            others[0].3
+        };
+
+        for (line_idx, start, end, name) in mixed {
+            let line = lines[line_idx];
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, start),
+                end: byte_to_char_index(line, end),
+                issue_type: "引用格式".to_string(),
+                message: format!("引用格式不统一：此处为 {} 格式", name),
+                suggestion: format!("全文主要使用 {} 格式，建议统一", main_name),
+                rule_id: "citation_format".to_string(),
+                replacement: None,
+                confidence: 0.8,
+                severity: "warning".to_string(),
+                language: detect_language(line),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+
+    // 只在参考文献以国标为主或条目含中文时提示，IEEE 等编号格式本就没有类型标识
+    let gbt_references = gbt_is_main && !gbt_lines.is_empty();
+    for line_idx in untyped {
+        let line = lines[line_idx];
+        if !gbt_references && !line.chars().any(is_chinese_char) {
+            continue;
+        }
+        let content = line.trim();
+        let start = line.len() - line.trim_start().len();
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, start),
+            end: byte_to_char_index(line, start + content.len()),
+            issue_type: "引用格式".to_string(),
+            message: "参考文献条目缺少文献类型标识".to_string(),
+            suggestion: "按 GB/T 7714 在题名后添加类型标识，如期刊 [J]、专著 [M]、学位论文 [D]"
+                .to_string(),
+            rule_id: "citation_format".to_string(),
+            replacement: None,
+            confidence: 0.7,
+            severity: "warning".to_string(),
+            language: detect_language(line),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 行首是列表、标题、引用等标记时，上一行已结束，不与上一行连读
+fn starts_new_block(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with(['-', '*', '+', '#', '>', '|'])
+        || trimmed
+            .split_once(['.', '、', ')', '）'])
+            .is_some_and(|(marker, _)| {
+                !marker.is_empty() && marker.chars().all(|c| c.is_ascii_digit())
+            })
+}
+
+// 行末的词：中文取最后一个字，英文取最后一个单词；行末是标点时返回 None
+fn last_word(line: &str) -> Option<&str> {
+    let trimmed = line.trim_end();
+    let last = trimmed.chars().next_back()?;
+    if is_chinese_char(last) {
+        return Some(&trimmed[trimmed.len() - last.len_utf8()..]);
+    }
+    if !last.is_alphanumeric() {
+        return None;
+    }
+    let start = trimmed
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphanumeric() && !is_chinese_char(*c))
+        .last()
+        .map_or(trimmed.len(), |(idx, _)| idx);
+    Some(&trimmed[start..])
+}
+
+// 行首的词及其字节起始位置：中文取第一个字，英文取第一个单词；行首是标点时返回 None
+fn first_word(line: &str) -> Option<(usize, &str)> {
+    let start = line.len() - line.trim_start().len();
+    let rest = &line[start..];
+    let first = rest.chars().next()?;
+    if is_chinese_char(first) {
+        return Some((start, &rest[..first.len_utf8()]));
+    }
+    if !first.is_alphanumeric() {
+        return None;
+    }
+    let end = rest
+        .find(|c: char| !c.is_alphanumeric() || is_chinese_char(c))
+        .unwrap_or(rest.len());
+    Some((start, &rest[..end]))
+}
+
+// Check for words repeated across a line break inside a paragraph, e.g. "这是\n是错误"
+//
+// 只比较上一行末尾与下一行开头的词，换行处有标点（跨句、跨分句）时不算重复。
+pub fn check_cross_line_repeats(text: &str, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    for paragraph in split_paragraphs(text) {
+        for (idx, pair) in paragraph.lines.windows(2).enumerate() {
+            if starts_new_block(pair[1]) {
+                continue;
+            }
+            let (previous, (start, next)) = match (last_word(pair[0]), first_word(pair[1])) {
+                (Some(previous), Some(next)) => (previous, next),
+                _ => continue,
+            };
+            if previous.to_lowercase() != next.to_lowercase() {
+                continue;
+            }
+
+            // 与逐行检查的阈值一致：英文词至少4个字母，中文排除“天天”这类正常叠字
+            if is_chinese_char(previous.chars().next().unwrap_or(' ')) {
+                if crate::NORMAL_CHINESE_REPEATS.contains(&format!("{}{}", previous, next).as_str())
+                {
+                    continue;
+                }
+            } else if previous.chars().count() < 4 {
+                continue;
+            }
+
+            let line = pair[1];
+            let line_number = paragraph.start_line + idx + 2;
+            issues.push(TextIssue {
+                line_number,
+                start: byte_to_char_index(line, start),
+                end: byte_to_char_index(line, start + next.len()),
+                issue_type: "重复词".to_string(),
+                message: format!("跨行重复使用词语 '{}'（与上一行末尾重复）", next),
+                suggestion: format!("删除第 {} 行开头多余的 '{}'", line_number, next),
+                rule_id: "paragraph_repeated_words".to_string(),
+                replacement: None,
+                confidence: 0.9,
+                severity: "warning".to_string(),
+                language: detect_language(line),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+// 英文段落中的一个引号：(行号, 字符位置, 引号, 是否为弯引号, 是否为左引号)
+type QuoteMark = (usize, usize, char, bool, bool);
+
+// 引号前是行首、空白或左括号等时视为左引号
+fn is_opening_position(prev: Option<char>) -> bool {
+    match prev {
+        None => true,
+        Some(c) => c.is_whitespace() || "([{<—-“‘\"'".contains(c),
+    }
+}
+
+// 收集一个段落中英文行里的引号，撇号（don't、'90s、students'）不算
+fn collect_quote_marks(paragraph: &Paragraph) -> Vec<QuoteMark> {
+    let mut marks = Vec::new();
+    // 尚未闭合的单引号数量，用于区分 students' 这类撇号与右单引号
+    let mut open_singles = 0usize;
+
+    for (offset, line) in paragraph.lines.iter().enumerate() {
+        if detect_language(line) != "en" {
+            continue;
+        }
+        // 行内代码、网址等中的引号不检查
+        let chars: Vec<char> = crate::strip_technical_tokens(line).chars().collect();
+        for (idx, &c) in chars.iter().enumerate() {
+            let curly = match c {
+                '"' | '\'' => false,
+                '“' | '”' | '‘' | '’' => true,
+                _ => continue,
+            };
+            let prev = idx.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(idx + 1).copied();
+            let after_word = prev.is_some_and(|p| p.is_alphanumeric());
+
+            let opening = match c {
+                '“' | '‘' => true,
+                '”' => false,
+                '’' | '\'' if after_word && next.is_some_and(|n| n.is_alphabetic()) => continue,
+                '’' | '\'' if !after_word && next.is_some_and(|n| n.is_ascii_digit()) => continue,
+                '’' | '\'' if after_word && open_singles == 0 => continue,
+                _ => is_opening_position(prev),
+            };
+
+            if matches!(c, '\'' | '‘' | '’') {
+                if opening {
+                    open_singles += 1;
+                } else {
+                    open_singles = open_singles.saturating_sub(1);
+                }
+            }
+            marks.push((paragraph.start_line + offset, idx, c, curly, opening));
+        }
+    }
+
+    marks
+}
+
+// 把引号转换为目标风格
+fn convert_quote(c: char, to_curly: bool, opening: bool) -> char {
+    match (c, to_curly, opening) {
+        ('"', true, true) => '“',
+        ('"', true, false) => '”',
+        ('\'', true, true) => '‘',
+        ('\'', true, false) => '’',
+        ('“' | '”', false, _) => '"',
+        ('‘' | '’', false, _) => '\'',
+        _ => c,
+    }
+}
+
+// Check for straight and curly quotes mixed in one English paragraph
+//
+// quote_style 为 "straight" 或 "curly" 时按该风格给出替换建议，
+// 未设置时以段落中数量较多的风格为准，数量相同时以先出现的为准。
+pub fn check_quote_style_consistency(
+    text: &str,
+    quote_style: Option<&str>,
+    issues: &mut Vec<TextIssue>,
+) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let lines: Vec<&str> = text.lines().collect();
+    for paragraph in split_paragraphs(text) {
+        let marks = collect_quote_marks(&paragraph);
+        let curly_count = marks.iter().filter(|mark| mark.3).count();
+        let straight_count = marks.len() - curly_count;
+        if curly_count == 0 || straight_count == 0 {
+            continue;
+        }
+
+        let to_curly = match quote_style {
+            Some("curly") => true,
+            Some("straight") => false,
+            _ if curly_count != straight_count => curly_count > straight_count,
+            _ => marks[0].3,
+        };
+        let style_name = if to_curly { "弯引号" } else { "直引号" };
+
+        for (line_idx, idx, c, curly, opening) in marks {
+            if curly == to_curly {
+                continue;
+            }
+            let replacement = convert_quote(c, to_curly, opening).to_string();
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: idx,
+                end: idx + 1,
+                issue_type: "引号风格".to_string(),
+                message: format!("同一段落中直引号和弯引号混用: '{}'", c),
+                suggestion: format!("统一使用{}，建议修改为: '{}'", style_name, replacement),
+                rule_id: "quote_style".to_string(),
+                replacement: Some(replacement),
+                confidence: 0.9,
+                severity: "info".to_string(),
+                language: detect_language(lines[line_idx]),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+/// 文档级检查的规则 ID
+pub const DOCUMENT_RULES: &[&str] = &[
+    "language_consistency",
+    "date_format",
+    "paragraph_repeated_words",
+    "citation_format",
+    "quote_style",
+    "number_style",
+];
+
+/// 运行所有启用的文档级检查
+pub fn check_document(text: &str, config: &AnalysisConfig, issues: &mut Vec<TextIssue>) {
+    if config.is_rule_enabled("language_consistency") {
+        check_language_consistency(text, issues);
+    }
+    if config.is_rule_enabled("date_format") {
+        check_date_format_consistency(text, issues);
+    }
+    if config.is_rule_enabled("paragraph_repeated_words") {
+        check_cross_line_repeats(text, issues);
+    }
+    if config.is_rule_enabled("citation_format") {
+        check_reference_format(text, issues);
+    }
+    if config.is_rule_enabled("quote_style") {
+        check_quote_style_consistency(text, config.quote_style.as_deref(), issues);
+    }
+    if config.is_rule_enabled("number_style") {
+        check_number_style_consistency(text, issues);
+    }
+    for issue in issues.iter_mut() {
+        config.apply_severity_override(issue);
+    }
+}
@@ -20,7 +20,11 @@ pub fn parse_document(file_path: &str) -> Result<String, String> {
     match extension.as_str() {
         "docx" => parse_docx(file_path),
         "doc" => parse_doc(file_path),
+        "pdf" => parse_pdf(file_path),
+        "odt" => parse_odt(file_path),
+        "rtf" => parse_rtf(file_path),
         "txt" | "md" => parse_text_file(file_path),
+        "json" | "yaml" | "yml" => parse_config_file(file_path, &extension),
         _ => parse_text_file(file_path), // 默认尝试作为文本文件解析
     }
 }
@@ -33,20 +37,52 @@ fn parse_docx(file_path: &str) -> Result<String, String> {
     let mut archive = ZipArchive::new(reader).map_err(|e| format!("无法解析DOCX文件: {}", e))?;
 
     // 查找document.xml文件
-    let mut document_xml = archive
-        .by_name("word/document.xml")
+    let xml_content = read_zip_entry(&mut archive, "word/document.xml")?;
+
+    // 解析XML并提取文本
+    let mut text_content = extract_text_from_docx_xml(&xml_content)?;
+
+    // 页眉页脚为可选内容，按文件名顺序追加在正文之后
+    if crate::config::current_config().docx_headers_footers {
+        let mut names: Vec<String> = archive
+            .file_names()
+            .filter(|name| {
+                (name.starts_with("word/header") || name.starts_with("word/footer"))
+                    && name.ends_with(".xml")
+            })
+            .map(|name| name.to_string())
+            .collect();
+        names.sort();
+
+        for name in names {
+            let xml_content = read_zip_entry(&mut archive, &name)?;
+            let text = extract_text_from_docx_xml(&xml_content)?;
+            if !text.trim().is_empty() {
+                text_content.push('\n');
+                text_content.push_str(&text);
+            }
+        }
+    }
+
+    Ok(text_content)
+}
+
+// 读取压缩包中指定文件的全部内容
+fn read_zip_entry(archive: &mut ZipArchive<BufReader<File>>, name: &str) -> Result<String, String> {
+    let mut entry = archive
+        .by_name(name)
         .map_err(|e| format!("无法找到文档内容: {}", e))?;
 
-    let mut xml_content = String::new();
-    document_xml
-        .read_to_string(&mut xml_content)
+    let mut content = String::new();
+    entry
+        .read_to_string(&mut content)
         .map_err(|e| format!("无法读取文档内容: {}", e))?;
-
-    // 解析XML并提取文本
-    extract_text_from_docx_xml(&xml_content)
+    Ok(content)
 }
 
 /// 从DOCX的XML内容中提取纯文本
+///
+/// 表格中每个单元格单独占一行，避免相邻单元格的文字粘连成一个词。
 fn extract_text_from_docx_xml(xml_content: &str) -> Result<String, String> {
     let mut reader = Reader::from_str(xml_content);
     reader.trim_text(true);
@@ -54,6 +90,8 @@ fn extract_text_from_docx_xml(xml_content: &str) -> Result<String, String> {
     let mut text_content = String::new();
     let mut buf = Vec::new();
     let mut in_text_element = false;
+    // 刚结束一个单元格或表格行，已换行，下一段落不再重复换行
+    let mut at_cell_boundary = false;
 
     loop {
         match reader.read_event_into(&mut buf) {
@@ -62,9 +100,10 @@ fn extract_text_from_docx_xml(xml_content: &str) -> Result<String, String> {
                     b"w:t" => in_text_element = true,
                     b"w:p" => {
                         // 段落开始，添加换行（如果不是第一段）
-                        if !text_content.is_empty() {
+                        if !text_content.is_empty() && !at_cell_boundary {
                             text_content.push('\n');
                         }
+                        at_cell_boundary = false;
                     }
                     b"w:br" => {
                         // 换行符
@@ -73,11 +112,17 @@ fn extract_text_from_docx_xml(xml_content: &str) -> Result<String, String> {
                     _ => {}
                 }
             }
-            Ok(Event::End(ref e)) => {
-                if e.name().as_ref() == b"w:t" {
-                    in_text_element = false;
+            Ok(Event::End(ref e)) => match e.name().as_ref() {
+                b"w:t" => in_text_element = false,
+                b"w:tc" | b"w:tr" => {
+                    // 单元格、表格行结束时换行分隔
+                    if !text_content.is_empty() && !text_content.ends_with('\n') {
+                        text_content.push('\n');
+                    }
+                    at_cell_boundary = true;
                 }
-            }
+                _ => {}
+            },
             Ok(Event::Text(e)) => {
                 if in_text_element {
                     let text = e.unescape().map_err(|e| format!("XML解析错误: {}", e))?;
@@ -94,89 +139,866 @@ fn extract_text_from_docx_xml(xml_content: &str) -> Result<String, String> {
     Ok(text_content)
 }
 
-/// 解析DOC文件（旧版Word格式）
-fn parse_doc(file_path: &str) -> Result<String, String> {
-    // DOC文件是复杂的二进制格式，这里提供一个简单的实现
-    // 实际应用中可能需要更专业的库如python-docx的Rust等价物
+/// 解析ODT文件（OpenDocument文本，LibreOffice 默认格式）
+fn parse_odt(file_path: &str) -> Result<String, String> {
+    let file = File::open(file_path).map_err(|e| format!("无法打开文件: {}", e))?;
+
+    let reader = BufReader::new(file);
+    let mut archive = ZipArchive::new(reader).map_err(|e| format!("无法解析ODT文件: {}", e))?;
+
+    // 正文在content.xml中
+    let mut content_xml = archive
+        .by_name("content.xml")
+        .map_err(|e| format!("无法找到文档内容: {}", e))?;
+
+    let mut xml_content = String::new();
+    content_xml
+        .read_to_string(&mut xml_content)
+        .map_err(|e| format!("无法读取文档内容: {}", e))?;
+
+    extract_text_from_odt_xml(&xml_content)
+}
+
+// ODT 中 <text:s text:c="N"/> 表示 N 个连续空格
+fn odt_space_count(e: &quick_xml::events::BytesStart) -> usize {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == b"text:c")
+        .and_then(|attr| std::str::from_utf8(&attr.value).ok()?.parse().ok())
+        .unwrap_or(1)
+}
+
+/// 从ODT的content.xml中提取纯文本，每个段落或标题一行
+fn extract_text_from_odt_xml(xml_content: &str) -> Result<String, String> {
+    let mut reader = Reader::from_str(xml_content);
+    // 不去除空白，否则相邻 <text:span> 之间的空格会丢失
+    reader.trim_text(false);
+
+    let mut text_content = String::new();
+    let mut buf = Vec::new();
+    // 段落可以嵌套（如脚注中的段落），只在最外层段落内收集文本
+    let mut paragraph_depth = 0usize;
+    let mut paragraph_count = 0usize;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                b"text:p" | b"text:h" => {
+                    if paragraph_depth == 0 {
+                        if paragraph_count > 0 {
+                            text_content.push('\n');
+                        }
+                        paragraph_count += 1;
+                    }
+                    paragraph_depth += 1;
+                }
+                b"text:s" if paragraph_depth > 0 => {
+                    text_content.push_str(&" ".repeat(odt_space_count(e)));
+                }
+                _ => {}
+            },
+            Ok(Event::Empty(ref e)) => match e.name().as_ref() {
+                b"text:p" | b"text:h" if paragraph_depth == 0 => {
+                    // 空段落也占一行
+                    if paragraph_count > 0 {
+                        text_content.push('\n');
+                    }
+                    paragraph_count += 1;
+                }
+                b"text:s" if paragraph_depth > 0 => {
+                    text_content.push_str(&" ".repeat(odt_space_count(e)));
+                }
+                b"text:tab" if paragraph_depth > 0 => text_content.push('\t'),
+                b"text:line-break" if paragraph_depth > 0 => text_content.push('\n'),
+                _ => {}
+            },
+            Ok(Event::End(ref e)) if matches!(e.name().as_ref(), b"text:p" | b"text:h") => {
+                paragraph_depth = paragraph_depth.saturating_sub(1);
+            }
+            Ok(Event::Text(e)) if paragraph_depth > 0 => {
+                let text = e.unescape().map_err(|e| format!("XML解析错误: {}", e))?;
+                text_content.push_str(&text);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("XML解析错误: {}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(text_content)
+}
 
-    let mut file = File::open(file_path).map_err(|e| format!("无法打开DOC文件: {}", e))?;
+/// 解析RTF文件，去掉控制字只保留正文
+fn parse_rtf(file_path: &str) -> Result<String, String> {
+    let mut file = File::open(file_path).map_err(|e| format!("无法打开RTF文件: {}", e))?;
 
     let mut buffer = Vec::new();
     file.read_to_end(&mut buffer)
-        .map_err(|e| format!("无法读取DOC文件: {}", e))?;
+        .map_err(|e| format!("无法读取RTF文件: {}", e))?;
 
-    // 尝试检测编码并提取可读文本
-    // 这是一个简化的实现，可能不能处理所有DOC文件
-    let text = extract_text_from_binary(&buffer);
+    if !buffer.starts_with(b"{\\rtf") {
+        return Err("无法解析RTF文件：文件头不是 {\\rtf".to_string());
+    }
 
+    let text = extract_text_from_rtf(&buffer);
     if text.trim().is_empty() {
-        return Err("无法从DOC文件中提取文本内容。建议将文件另存为DOCX格式或TXT格式。".to_string());
+        return Err("无法从RTF文件中提取文本内容".to_string());
     }
 
     Ok(text)
 }
 
-/// 从二进制数据中提取可能的文本内容
-fn extract_text_from_binary(data: &[u8]) -> String {
+// 这些组只包含格式信息或元数据，整组跳过
+const RTF_SKIP_DESTINATIONS: &[&str] = &[
+    "fonttbl",
+    "colortbl",
+    "stylesheet",
+    "info",
+    "pict",
+    "object",
+    "header",
+    "footer",
+    "listtable",
+    "listoverridetable",
+    "rsidtbl",
+    "themedata",
+    "datastore",
+    "latentstyles",
+    "generator",
+];
+
+// RTF 解析时每一层组的状态
+#[derive(Clone, Copy)]
+struct RtfGroup {
+    // 本组内容是否跳过
+    skip: bool,
+    // \uN 之后需要跳过的替代字符数（\ucN）
+    unicode_skip: usize,
+}
+
+/// 从RTF数据中提取纯文本
+///
+/// \par、\line 转为换行，\'hh 按 \ansicpg 指定的代码页解码（936 即 GBK），\uN 按 Unicode 解码。
+fn extract_text_from_rtf(data: &[u8]) -> String {
     let mut text = String::new();
-    let mut current_word = Vec::new();
-
-    for &byte in data {
-        if byte.is_ascii_graphic()
-            || byte == b' '
-            || byte == b'\n'
-            || byte == b'\r'
-            || byte == b'\t'
-        {
-            current_word.push(byte);
-        } else {
-            if !current_word.is_empty() {
-                if let Ok(word) = String::from_utf8(current_word.clone()) {
-                    // 只保留看起来像有意义的文本片段
-                    if word.len() > 2 && word.chars().any(|c| c.is_alphabetic()) {
-                        text.push_str(&word);
-                        text.push(' ');
+    // \'hh 转义得到的字节先攒起来，遇到其他内容时再按代码页解码
+    let mut pending: Vec<u8> = Vec::new();
+    let mut encoding: &'static Encoding = WINDOWS_1252;
+    let mut stack: Vec<RtfGroup> = Vec::new();
+    let mut group = RtfGroup {
+        skip: false,
+        unicode_skip: 1,
+    };
+    // \uN 后还需跳过的替代字符数
+    let mut skip_chars = 0usize;
+    let mut i = 0;
+
+    let flush = |pending: &mut Vec<u8>, text: &mut String, encoding: &'static Encoding| {
+        if !pending.is_empty() {
+            let (decoded, _, _) = encoding.decode(pending);
+            text.push_str(&decoded);
+            pending.clear();
+        }
+    };
+
+    while i < data.len() {
+        let byte = data[i];
+        match byte {
+            b'{' => {
+                flush(&mut pending, &mut text, encoding);
+                stack.push(group);
+                skip_chars = 0;
+                // {\* ...} 是可忽略的扩展目标
+                if data[i + 1..].starts_with(b"\\*") {
+                    group.skip = true;
+                }
+                i += 1;
+            }
+            b'}' => {
+                flush(&mut pending, &mut text, encoding);
+                group = stack.pop().unwrap_or(group);
+                skip_chars = 0;
+                i += 1;
+            }
+            b'\\' => {
+                let next = data.get(i + 1).copied().unwrap_or(b' ');
+                if next.is_ascii_alphabetic() {
+                    // 控制字：字母序列加可选的数字参数，后面的一个空格属于控制字
+                    let word_start = i + 1;
+                    let mut j = word_start;
+                    while j < data.len() && data[j].is_ascii_alphabetic() {
+                        j += 1;
+                    }
+                    let word = String::from_utf8_lossy(&data[word_start..j]).into_owned();
+                    let param_start = j;
+                    if j < data.len() && data[j] == b'-' {
+                        j += 1;
+                    }
+                    while j < data.len() && data[j].is_ascii_digit() {
+                        j += 1;
+                    }
+                    let param: Option<i64> = std::str::from_utf8(&data[param_start..j])
+                        .ok()
+                        .and_then(|p| p.parse().ok());
+                    if j < data.len() && data[j] == b' ' {
+                        j += 1;
+                    }
+                    i = j;
+
+                    flush(&mut pending, &mut text, encoding);
+                    if RTF_SKIP_DESTINATIONS.contains(&word.as_str()) {
+                        group.skip = true;
+                        continue;
+                    }
+                    match word.as_str() {
+                        "ansicpg" => {
+                            if let Some(code_page) = param {
+                                encoding = match code_page {
+                                    936 => GBK,
+                                    950 => BIG5,
+                                    932 => SHIFT_JIS,
+                                    65001 => UTF_8,
+                                    _ => WINDOWS_1252,
+                                };
+                            }
+                        }
+                        "uc" => group.unicode_skip = param.unwrap_or(1).max(0) as usize,
+                        _ if group.skip => {}
+                        "par" | "line" | "sect" | "page" => text.push('\n'),
+                        "tab" => text.push('\t'),
+                        "emdash" => text.push('—'),
+                        "endash" => text.push('–'),
+                        "lquote" => text.push('‘'),
+                        "rquote" => text.push('’'),
+                        "ldblquote" => text.push('“'),
+                        "rdblquote" => text.push('”'),
+                        "u" => {
+                            // 参数是有符号16位整数，负数需加 65536
+                            let code = param.unwrap_or(0);
+                            let code = if code < 0 { code + 65536 } else { code };
+                            text.push(char::from_u32(code as u32).unwrap_or('\u{FFFD}'));
+                            skip_chars = group.unicode_skip;
+                        }
+                        _ => {}
                     }
+                } else if next == b'\'' {
+                    // \'hh：代码页中的一个字节
+                    let hex = data.get(i + 2..i + 4).unwrap_or(b"");
+                    let value = std::str::from_utf8(hex)
+                        .ok()
+                        .and_then(|h| u8::from_str_radix(h, 16).ok());
+                    i += 2 + hex.len();
+                    if skip_chars > 0 {
+                        skip_chars -= 1;
+                    } else if let (false, Some(value)) = (group.skip, value) {
+                        pending.push(value);
+                    }
+                } else {
+                    // 控制符号：\\ \{ \} 为字面字符，\~ 为不换行空格，其余忽略
+                    flush(&mut pending, &mut text, encoding);
+                    if !group.skip {
+                        match next {
+                            b'\\' | b'{' | b'}' => text.push(next as char),
+                            b'~' => text.push(' '),
+                            b'\n' | b'\r' => text.push('\n'),
+                            _ => {}
+                        }
+                    }
+                    i += 2;
+                }
+            }
+            // 源文件中的换行不是正文
+            b'\r' | b'\n' => i += 1,
+            _ => {
+                i += 1;
+                if skip_chars > 0 {
+                    skip_chars -= 1;
+                    continue;
+                }
+                if !group.skip {
+                    // 控制字之外的字节原样按代码页解码
+                    pending.push(byte);
                 }
-                current_word.clear();
             }
         }
     }
+    flush(&mut pending, &mut text, encoding);
+
+    text.trim_end().to_string()
+}
+
+/// 解析DOC文件（Word 97-2003 二进制格式）
+///
+/// DOC 是 OLE2/CFB 复合文档：正文存放在 WordDocument 流中，文本块的位置表（piece table）
+/// 存放在 0Table 或 1Table 流中。这里按位置表拼出正文，并去掉字段代码和特殊标记字符。
+fn parse_doc(file_path: &str) -> Result<String, String> {
+    const SAVE_AS_DOCX: &str = "建议用 Word 或 LibreOffice 将文件另存为DOCX格式后再导入。";
+
+    let file = File::open(file_path).map_err(|e| format!("无法打开DOC文件: {}", e))?;
+    let mut compound = cfb::CompoundFile::open(file).map_err(|_| {
+        format!(
+            "无法解析DOC文件：不是有效的Word 97-2003文档。{}",
+            SAVE_AS_DOCX
+        )
+    })?;
+
+    let word_document = read_cfb_stream(&mut compound, "/WordDocument")
+        .map_err(|_| format!("无法解析DOC文件：找不到正文数据。{}", SAVE_AS_DOCX))?;
+    let fib = DocFib::parse(&word_document)
+        .map_err(|e| format!("无法解析DOC文件：{}。{}", e, SAVE_AS_DOCX))?;
+
+    let table_stream = read_cfb_stream(&mut compound, fib.table_stream_name())
+        .map_err(|_| format!("无法解析DOC文件：找不到文本位置表。{}", SAVE_AS_DOCX))?;
+
+    let raw = extract_doc_pieces(&word_document, &table_stream, &fib)
+        .map_err(|e| format!("无法解析DOC文件：{}。{}", e, SAVE_AS_DOCX))?;
+    let text = clean_doc_text(&raw);
+
+    if text.trim().is_empty() {
+        return Err(format!("无法从DOC文件中提取文本内容。{}", SAVE_AS_DOCX));
+    }
+
+    Ok(text)
+}
+
+// 读取复合文档中一个流的全部内容
+fn read_cfb_stream(compound: &mut cfb::CompoundFile<File>, name: &str) -> std::io::Result<Vec<u8>> {
+    let mut stream = compound.open_stream(name)?;
+    let mut buffer = Vec::new();
+    stream.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+// WordDocument 流开头的文件信息块（FIB）中需要用到的字段
+struct DocFib {
+    // 位置表是否在 1Table 流中（否则在 0Table 中）
+    use_1table: bool,
+    // 正文（不含脚注、页眉等）的字符数
+    main_text_chars: usize,
+    // 位置表（Clx）在表流中的偏移和长度
+    clx_offset: usize,
+    clx_len: usize,
+}
+
+impl DocFib {
+    fn parse(data: &[u8]) -> Result<DocFib, String> {
+        if read_u16(data, 0x00) != Some(0xA5EC) {
+            return Err("文件头标识不正确，可能是 Word 6.0/95 等更早的格式".to_string());
+        }
+        let flags = read_u16(data, 0x0A).ok_or("文件信息不完整")?;
+        if flags & 0x0100 != 0 {
+            return Err("文档已加密".to_string());
+        }
+
+        let field = |offset: usize| {
+            read_u32(data, offset)
+                .map(|value| value as usize)
+                .ok_or("文件信息不完整")
+        };
+
+        Ok(DocFib {
+            use_1table: flags & 0x0200 != 0,
+            main_text_chars: field(0x4C)?,
+            clx_offset: field(0x01A2)?,
+            clx_len: field(0x01A6)?,
+        })
+    }
+
+    fn table_stream_name(&self) -> &'static str {
+        if self.use_1table {
+            "/1Table"
+        } else {
+            "/0Table"
+        }
+    }
+}
 
-    // 处理最后的单词
-    if !current_word.is_empty() {
-        if let Ok(word) = String::from_utf8(current_word) {
-            if word.len() > 2 && word.chars().any(|c| c.is_alphabetic()) {
-                text.push_str(&word);
+/// 按位置表依次读取各文本块，只保留正文部分
+fn extract_doc_pieces(word_document: &[u8], table: &[u8], fib: &DocFib) -> Result<String, String> {
+    let clx = table
+        .get(fib.clx_offset..fib.clx_offset + fib.clx_len)
+        .ok_or("文本位置表超出范围")?;
+
+    // 跳过位置表前面的格式修改记录（Prc），找到文本块表（Pcdt）
+    let mut pos = 0;
+    while clx.get(pos) == Some(&0x01) {
+        let size = read_u16(clx, pos + 1).ok_or("文本位置表损坏")? as usize;
+        pos += 3 + size;
+    }
+    if clx.get(pos) != Some(&0x02) {
+        return Err("找不到文本块表".to_string());
+    }
+    let plc_len = read_u32(clx, pos + 1).ok_or("文本位置表损坏")? as usize;
+    let plc = clx
+        .get(pos + 5..pos + 5 + plc_len)
+        .ok_or("文本位置表损坏")?;
+
+    // PlcPcd：n+1 个字符位置，后面跟 n 个 8 字节的文本块描述
+    if plc_len < 4 || !(plc_len - 4).is_multiple_of(12) {
+        return Err("文本块表长度不正确".to_string());
+    }
+    let count = (plc_len - 4) / 12;
+    let mut text = String::new();
+
+    for idx in 0..count {
+        let cp_start = read_u32(plc, idx * 4).ok_or("文本块表损坏")? as usize;
+        if cp_start >= fib.main_text_chars {
+            break;
+        }
+        let cp_end =
+            (read_u32(plc, idx * 4 + 4).ok_or("文本块表损坏")? as usize).min(fib.main_text_chars);
+        let chars = cp_end.saturating_sub(cp_start);
+
+        let fc_raw = read_u32(plc, (count + 1) * 4 + idx * 8 + 2).ok_or("文本块表损坏")?;
+        // 第 30 位为 1 表示 8 位压缩文本（CP1252），否则为 UTF-16LE
+        let compressed = fc_raw & 0x4000_0000 != 0;
+        let fc = (fc_raw & 0x3FFF_FFFF) as usize;
+
+        if compressed {
+            let start = fc / 2;
+            let bytes = word_document
+                .get(start..start + chars)
+                .ok_or("文本块超出范围")?;
+            let (decoded, _, _) = WINDOWS_1252.decode(bytes);
+            text.push_str(&decoded);
+        } else {
+            let bytes = word_document
+                .get(fc..fc + chars * 2)
+                .ok_or("文本块超出范围")?;
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]))
+                .collect();
+            text.push_str(&String::from_utf16_lossy(&units));
+        }
+    }
+
+    Ok(text)
+}
+
+/// 把 Word 正文中的特殊字符转为普通文本
+///
+/// 字段（0x13 开始、0x14 分隔、0x15 结束）只保留显示结果，去掉字段代码；
+/// 段落标记、换行符、表格单元格标记转为换行或制表符，图片锚点等标记直接删除。
+fn clean_doc_text(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    // 每层字段是否已进入结果部分；嵌套字段时用栈记录
+    let mut fields: Vec<bool> = Vec::new();
+
+    for c in raw.chars() {
+        match c {
+            '\u{13}' => {
+                fields.push(false);
+                continue;
+            }
+            '\u{14}' => {
+                if let Some(in_result) = fields.last_mut() {
+                    *in_result = true;
+                }
+                continue;
+            }
+            '\u{15}' => {
+                fields.pop();
+                continue;
             }
+            _ => {}
+        }
+
+        // 位于任一层字段代码中的字符都不输出
+        if fields.iter().any(|in_result| !in_result) {
+            continue;
+        }
+
+        match c {
+            '\r' | '\u{0B}' | '\u{0C}' => text.push('\n'),
+            // 表格中的单元格结束标记
+            '\u{07}' => text.push('\t'),
+            '\u{1E}' => text.push('-'),
+            '\u{A0}' => text.push(' '),
+            '\t' | '\n' => text.push(c),
+            // 可选连字符、图片锚点、脚注引用等
+            '\u{1F}' => {}
+            c if c.is_control() => {}
+            c => text.push(c),
         }
     }
 
-    // 清理文本：移除多余的空格和换行
-    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+    // 去掉行尾空白和表格行末多余的制表符
+    text.lines()
+        .map(|line| line.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// 解析PDF文件，只提取文本层
+fn parse_pdf(file_path: &str) -> Result<String, String> {
+    // pdf-extract 遇到不支持的字体或结构时可能 panic，这里转为错误返回
+    let path = file_path.to_string();
+    let text = std::panic::catch_unwind(|| pdf_extract::extract_text(&path))
+        .map_err(|_| "无法解析PDF文件：文件结构不受支持".to_string())?
+        .map_err(|e| format!("无法解析PDF文件: {}", e))?;
+
+    if text.trim().is_empty() {
+        return Err("该 PDF 无可提取文本，可能是扫描件".to_string());
+    }
+
+    Ok(normalize_pdf_text(&text))
+}
+
+// 整理PDF提取出的文本：去掉分页符和行尾空白，连续空行合并为一个，保留段落换行
+fn normalize_pdf_text(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut prev_blank = true;
+
+    for line in text.split('\n') {
+        let line = line.trim_end_matches(['\r', '\x0c', ' ', '\t']);
+        let line = line.trim_start_matches('\x0c');
+        let blank = line.trim().is_empty();
+        if blank && prev_blank {
+            continue;
+        }
+        lines.push(if blank { "" } else { line });
+        prev_blank = blank;
+    }
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+
+    lines.join("\n")
 }
 
 /// 解析纯文本文件，支持多种编码
 fn parse_text_file(file_path: &str) -> Result<String, String> {
+    read_text_file(file_path).map(|(content, _)| content)
+}
+
+/// 读取纯文本文件并探测编码，返回解码后的文本和编码名（如 "UTF-8"、"GBK"）
+pub fn read_text_file(file_path: &str) -> Result<(String, &'static str), String> {
     let mut file = File::open(file_path).map_err(|e| format!("无法打开文件: {}", e))?;
 
     let mut buffer = Vec::new();
     file.read_to_end(&mut buffer)
         .map_err(|e| format!("无法读取文件: {}", e))?;
 
-    // 尝试不同的编码
+    // 尝试不同的编码；带 BOM 的文件以 BOM 指示的编码为准
     let encodings = [UTF_8, GBK, GB18030, UTF_16LE, UTF_16BE];
 
     for encoding in &encodings {
-        let (decoded, _, had_errors) = encoding.decode(&buffer);
+        let (decoded, actual, had_errors) = encoding.decode(&buffer);
         if !had_errors {
-            return Ok(decoded.into_owned());
+            return Ok((decoded.into_owned(), actual.name()));
         }
     }
 
     // 如果所有编码都失败，尝试UTF-8并忽略错误
-    let (decoded, _, _) = UTF_8.decode(&buffer);
-    Ok(decoded.into_owned())
+    let (decoded, actual, _) = UTF_8.decode(&buffer);
+    Ok((decoded.into_owned(), actual.name()))
+}
+
+/// 解析JSON/YAML配置文件，只保留其中的字符串值
+fn parse_config_file(file_path: &str, extension: &str) -> Result<String, String> {
+    let content = parse_text_file(file_path)?;
+    if extension == "json" {
+        parse_json_strings(&content)
+    } else {
+        Ok(parse_yaml_strings(&content))
+    }
+}
+
+// 把字符串值放回它在原文件中的行和列，其余位置用空格填充
+fn place_string(lines: &mut [String], line: usize, column: usize, value: &str) {
+    if let Some(out) = lines.get_mut(line) {
+        let width = out.chars().count();
+        if width < column {
+            out.push_str(&" ".repeat(column - width));
+        } else if width > 0 {
+            out.push(' ');
+        }
+        out.push_str(value);
+    }
+}
+
+/// 提取JSON中的字符串值（跳过键名），输出的行号与原文件一致
+pub fn parse_json_strings(content: &str) -> Result<String, String> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut lines = vec![String::new(); content.lines().count().max(1)];
+    let mut line = 0;
+    let mut column = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            column = 0;
+            i += 1;
+            continue;
+        }
+        if c != '"' {
+            column += 1;
+            i += 1;
+            continue;
+        }
+
+        let (start_line, start_column) = (line, column + 1);
+        let mut value = String::new();
+        i += 1;
+        column += 1;
+
+        loop {
+            let c = match chars.get(i) {
+                Some(c) => *c,
+                None => return Err(format!("JSON字符串未闭合（第{}行）", start_line + 1)),
+            };
+            i += 1;
+            column += 1;
+
+            match c {
+                '"' => break,
+                '\n' => return Err(format!("JSON字符串未闭合（第{}行）", start_line + 1)),
+                '\\' => {
+                    let escaped = chars.get(i).copied().unwrap_or('\\');
+                    i += 1;
+                    column += 1;
+                    match escaped {
+                        // 换行等控制字符用空格代替，保证值仍在同一行
+                        'n' | 'r' | 't' | 'b' | 'f' => value.push(' '),
+                        'u' => {
+                            let hex: String = chars.iter().skip(i).take(4).collect();
+                            i += hex.chars().count();
+                            column += hex.chars().count();
+                            let code = u32::from_str_radix(&hex, 16).unwrap_or(0xFFFD);
+
+                            // 处理 UTF-16 代理对，如 😀
+                            let decoded = if (0xD800..0xDC00).contains(&code)
+                                && chars.get(i) == Some(&'\\')
+                                && chars.get(i + 1) == Some(&'u')
+                            {
+                                let low_hex: String = chars.iter().skip(i + 2).take(4).collect();
+                                match u32::from_str_radix(&low_hex, 16) {
+                                    Ok(low) if (0xDC00..0xE000).contains(&low) => {
+                                        i += 6;
+                                        column += 6;
+                                        char::from_u32(
+                                            0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00),
+                                        )
+                                    }
+                                    _ => None,
+                                }
+                            } else {
+                                char::from_u32(code)
+                            };
+                            value.push(decoded.unwrap_or('\u{FFFD}'));
+                        }
+                        other => value.push(other),
+                    }
+                }
+                other => value.push(other),
+            }
+        }
+
+        // 字符串后紧跟冒号的是键名，不参与检查
+        let is_key = chars[i..].iter().find(|c| !c.is_whitespace()) == Some(&':');
+        if !is_key {
+            place_string(&mut lines, start_line, start_column, &value);
+        }
+    }
+
+    Ok(lines.join("\n"))
+}
+
+// 在YAML行中查找第一个满足条件的引号外字符，条件函数的参数为 (位置, 字符, 前一个字符)。
+// 引号只在纯量开头（行首、空白或 [ { , 之后）才开始字符串，所以 it's 中的撇号不算引号；
+// 单引号字符串中 '' 表示一个单引号，双引号字符串中支持反斜杠转义
+fn find_yaml_unquoted(text: &str, matches: impl Fn(usize, char, char) -> bool) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        match quote {
+            Some('"') => match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => quote = None,
+                _ => {}
+            },
+            Some(_) => {
+                if c == '\'' {
+                    if chars.peek().map(|(_, next)| *next) == Some('\'') {
+                        chars.next();
+                    } else {
+                        quote = None;
+                    }
+                }
+            }
+            None => {
+                if (c == '"' || c == '\'')
+                    && (prev.is_whitespace() || matches!(prev, '[' | '{' | ','))
+                {
+                    quote = Some(c);
+                } else if matches(idx, c, prev) {
+                    return Some(idx);
+                }
+            }
+        }
+        prev = c;
+    }
+
+    None
+}
+
+// 去掉YAML行尾注释（引号内的 # 不算注释）
+fn strip_yaml_comment(line: &str) -> &str {
+    match find_yaml_unquoted(line, |_, c, prev| c == '#' && prev.is_whitespace()) {
+        Some(idx) => &line[..idx],
+        None => line,
+    }
+}
+
+// 判断YAML纯量是否为非文本值（数字、布尔、空值、锚点等）
+fn is_yaml_non_text(value: &str) -> bool {
+    let lower = value.to_lowercase();
+    matches!(
+        lower.as_str(),
+        "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+    ) || value.parse::<f64>().is_ok()
+        || value.starts_with(['&', '*', '!', '[', '{', '%', '@', '`'])
+}
+
+// 在YAML行中查找 "key: value" 的冒号位置（引号外，且冒号后为空白或行尾）
+fn find_yaml_key_colon(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    find_yaml_unquoted(text, |idx, c, _| {
+        c == ':' && matches!(bytes.get(idx + 1), None | Some(b' ') | Some(b'\t'))
+    })
+}
+
+/// 提取YAML中的字符串值（跳过键名），支持引号字符串和 | / > 多行块
+pub fn parse_yaml_strings(content: &str) -> String {
+    let source: Vec<&str> = content.lines().collect();
+    let mut lines = vec![String::new(); source.len().max(1)];
+    // 当前多行块所属键的缩进，块内容的缩进必须大于它
+    let mut block_indent: Option<usize> = None;
+    // 跨行的双引号字符串：(起始行, 起始列, 已读取的内容)
+    let mut open_quote: Option<(usize, usize, String)> = None;
+
+    for (line_idx, raw) in source.iter().enumerate() {
+        let indent = raw.chars().take_while(|c| *c == ' ').count();
+
+        // 多行双引号字符串的后续行
+        if let Some((start_line, start_column, mut value)) = open_quote.take() {
+            match raw.trim().find('"') {
+                Some(end) => {
+                    value.push(' ');
+                    value.push_str(&raw.trim()[..end]);
+                    place_string(&mut lines, start_line, start_column, &value);
+                }
+                None => {
+                    value.push(' ');
+                    value.push_str(raw.trim());
+                    open_quote = Some((start_line, start_column, value));
+                }
+            }
+            continue;
+        }
+
+        // | 或 > 块中的内容原样保留
+        if let Some(key_indent) = block_indent {
+            if raw.trim().is_empty() {
+                continue;
+            }
+            if indent > key_indent {
+                place_string(&mut lines, line_idx, indent, raw.trim());
+                continue;
+            }
+            block_indent = None;
+        }
+
+        let line = strip_yaml_comment(raw);
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed == "---" || trimmed == "..." {
+            continue;
+        }
+
+        // 去掉列表项前缀 "- "
+        let mut offset = indent;
+        let mut rest = &line[indent..];
+        while let Some(stripped) = rest.strip_prefix("- ") {
+            offset += 2;
+            rest = stripped;
+        }
+        if rest == "-" {
+            continue;
+        }
+
+        // 去掉键名，只保留冒号后的值
+        if let Some(colon) = find_yaml_key_colon(rest) {
+            offset += rest[..colon + 1].chars().count();
+            rest = &rest[colon + 1..];
+        }
+        let leading = rest.chars().take_while(|c| c.is_whitespace()).count();
+        offset += leading;
+        let value = rest.trim();
+        if value.is_empty() {
+            continue;
+        }
+
+        if value.starts_with('|') || value.starts_with('>') {
+            block_indent = Some(indent);
+        } else if let Some(inner) = value.strip_prefix('"') {
+            // 双引号字符串支持转义
+            let mut decoded = String::new();
+            let mut chars = inner.chars();
+            let mut closed = false;
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => {
+                        closed = true;
+                        break;
+                    }
+                    '\\' => match chars.next() {
+                        Some('n') | Some('t') | Some('r') => decoded.push(' '),
+                        Some(other) => decoded.push(other),
+                        None => {}
+                    },
+                    other => decoded.push(other),
+                }
+            }
+            if closed {
+                place_string(&mut lines, line_idx, offset + 1, &decoded);
+            } else {
+                open_quote = Some((line_idx, offset + 1, decoded));
+            }
+        } else if let Some(inner) = value.strip_prefix('\'') {
+            // 单引号字符串中 '' 表示一个单引号
+            let end = inner.rfind('\'').unwrap_or(inner.len());
+            place_string(
+                &mut lines,
+                line_idx,
+                offset + 1,
+                &inner[..end].replace("''", "'"),
+            );
+        } else if !is_yaml_non_text(value) {
+            place_string(&mut lines, line_idx, offset, value);
+        }
+    }
+
+    lines.join("\n")
 }
 
 /// 检测文件类型
@@ -187,3 +1009,32 @@ pub fn detect_file_type(file_path: &str) -> String {
         .unwrap_or("unknown")
         .to_lowercase()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yaml_apostrophes_do_not_start_quotes() {
+        assert_eq!(
+            strip_yaml_comment("note: it's done # todo"),
+            "note: it's done "
+        );
+        assert_eq!(
+            strip_yaml_comment("note: 'it''s # here' # x"),
+            "note: 'it''s # here' "
+        );
+        assert_eq!(
+            strip_yaml_comment(r#"note: "say \"hi\" # ok" # x"#),
+            r#"note: "say \"hi\" # ok" "#
+        );
+        assert_eq!(find_yaml_key_colon("it's key: value"), Some(8));
+
+        let text = parse_yaml_strings("a: it's fine # comment\nb: 'don''t stop'\nc: 3");
+        let lines: Vec<&str> = text.split('\n').collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].trim(), "it's fine");
+        assert_eq!(lines[1].trim(), "don't stop");
+        assert_eq!(lines[2].trim(), "");
+    }
+}
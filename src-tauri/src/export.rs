@@ -0,0 +1,185 @@
+// 把分析结果导出为便于分享的报告格式
+use crate::AnalysisResult;
+use crate::TextIssue;
+
+// 表格单元格中的 | 和换行会破坏 Markdown 表格结构
+fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+// 按问题类型分组，组内按行号和位置排序；问题多的类型排在前面
+fn group_by_type(issues: &[TextIssue]) -> Vec<(&str, Vec<&TextIssue>)> {
+    let mut groups: Vec<(&str, Vec<&TextIssue>)> = Vec::new();
+    for issue in issues {
+        match groups
+            .iter_mut()
+            .find(|(issue_type, _)| *issue_type == issue.issue_type)
+        {
+            Some((_, group)) => group.push(issue),
+            None => groups.push((&issue.issue_type, vec![issue])),
+        }
+    }
+
+    for (_, group) in &mut groups {
+        group.sort_by_key(|issue| (issue.line_number, issue.start));
+    }
+    groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(b.0)));
+
+    groups
+}
+
+/// 生成 Markdown 格式的检查报告：统计摘要加按类型分组的问题列表
+pub fn report_markdown(result: &AnalysisResult) -> String {
+    let mut report = String::from("# 检查报告\n\n");
+
+    if result.truncated {
+        report.push_str("> **注意**：文本过长或问题过多，本报告仅包含部分分析结果。\n\n");
+    }
+
+    let stat = |key: &str| result.stats.get(key).copied().unwrap_or(0);
+    let groups = group_by_type(&result.issues);
+
+    report.push_str("## 统计摘要\n\n");
+    report.push_str(&format!("- 总字符数：{}\n", stat("total_chars")));
+    report.push_str(&format!("- 总词数：{}\n", stat("total_words")));
+    report.push_str(&format!("- 总行数：{}\n", stat("total_lines")));
+    report.push_str(&format!("- 问题总数：{}\n", result.issues.len()));
+    for (issue_type, group) in &groups {
+        report.push_str(&format!("  - {}：{}\n", issue_type, group.len()));
+    }
+
+    for (issue_type, group) in &groups {
+        report.push_str(&format!("\n## {}（{}）\n\n", issue_type, group.len()));
+        report.push_str("| 行号 | 类型 | 问题 | 建议 |\n");
+        report.push_str("| ---: | --- | --- | --- |\n");
+        for issue in group {
+            report.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                issue.line_number,
+                escape_markdown_cell(&issue.issue_type),
+                escape_markdown_cell(&issue.message),
+                escape_markdown_cell(&issue.suggestion)
+            ));
+        }
+    }
+
+    report
+}
+
+// 含逗号、引号或换行的字段用双引号包裹，字段内的引号写成两个
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// 生成 CSV 格式的问题列表，一行一个问题，顺序与 issues 一致
+///
+/// 开头带 UTF-8 BOM，使 Excel 能正确识别中文。
+pub fn report_csv(result: &AnalysisResult) -> String {
+    let mut csv = String::from("\u{feff}line_number,start,end,issue_type,message,suggestion\r\n");
+
+    for issue in &result.issues {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\r\n",
+            issue.line_number,
+            issue.start,
+            issue.end,
+            escape_csv_field(&issue.issue_type),
+            escape_csv_field(&issue.message),
+            escape_csv_field(&issue.suggestion)
+        ));
+    }
+
+    csv
+}
+
+/// 把完整的分析结果（stats、issues、truncated 等）写成带缩进的 JSON 文件，供脚本批量统计
+pub fn save_json(result: &AnalysisResult, out_path: &str) -> Result<(), String> {
+    let path = std::path::Path::new(out_path);
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        if !dir.is_dir() {
+            return Err(format!("目录不存在: {}", dir.display()));
+        }
+    }
+
+    let json =
+        serde_json::to_string_pretty(result).map_err(|e| format!("无法序列化分析结果: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("无法写入文件 {}: {}", path.display(), e))
+}
+
+// 转义 HTML 特殊字符，属性值中同样适用
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 把原文渲染为 HTML，问题区间包裹为带 title 和 class 的 `<span>`
+///
+/// title 为问题说明和修改建议，class 为问题类型。区间重叠时只保留最外层（起点最早、范围最大）的问题。
+pub fn render_highlighted_html(text: &str, issues: &[TextIssue]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+
+    // 每行首字符在全文中的字符位置
+    let mut line_starts = vec![0];
+    for (idx, c) in chars.iter().enumerate() {
+        if *c == '\n' {
+            line_starts.push(idx + 1);
+        }
+    }
+
+    // 换算为全文字符区间，不跨行
+    let mut spans: Vec<(usize, usize, &TextIssue)> = issues
+        .iter()
+        .filter_map(|issue| {
+            let line_start = *line_starts.get(issue.line_number.checked_sub(1)?)?;
+            let line_end = line_starts
+                .get(issue.line_number)
+                .map_or(chars.len(), |next| next - 1);
+            let start = (line_start + issue.start).min(line_end);
+            let end = (line_start + issue.end).min(line_end);
+            (start < end).then_some((start, end, issue))
+        })
+        .collect();
+    spans.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+
+    let mut html = String::from("<div class=\"highlighted-text\" style=\"white-space: pre-wrap\">");
+    let mut pos = 0;
+    for (start, end, issue) in spans {
+        // 被外层区间覆盖或与之交叉的问题跳过
+        if start < pos {
+            continue;
+        }
+        html.push_str(&escape_html(&chars[pos..start].iter().collect::<String>()));
+        html.push_str(&format!(
+            "<span class=\"{}\" title=\"{}&#10;{}\">{}</span>",
+            escape_html(
+                &issue
+                    .issue_type
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join("-")
+            ),
+            escape_html(&issue.message),
+            escape_html(&issue.suggestion),
+            escape_html(&chars[start..end].iter().collect::<String>())
+        ));
+        pos = end;
+    }
+    html.push_str(&escape_html(&chars[pos..].iter().collect::<String>()));
+    html.push_str("</div>");
+
+    html
+}
@@ -0,0 +1,329 @@
+use crate::dictionary;
+use crate::storage;
+use crate::TextIssue;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 误报日志文件名（每行一条 JSON 记录）
+const FALSE_POSITIVE_LOG: &str = "false_positives.jsonl";
+
+// 同一个词在本次会话中被标记为误报达到该次数后自动忽略
+const AUTO_IGNORE_THRESHOLD: usize = 2;
+
+// 汇总中每条规则最多列出的高频词数量
+const MAX_TOP_WORDS: usize = 10;
+
+/// 一条误报记录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FalsePositiveRecord {
+    pub timestamp: u64,
+    pub rule_id: String,
+    pub issue_type: String,
+    pub word: String,
+    pub context: String,
+    pub line_number: usize,
+}
+
+/// 按规则汇总的误报统计
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FalsePositiveStat {
+    pub rule_id: String,
+    pub count: usize,
+    pub top_words: Vec<(String, usize)>,
+}
+
+// 本次会话中各个词被标记为误报的次数
+static SESSION_REPORT_COUNTS: OnceLock<Mutex<HashMap<String, usize>>> = OnceLock::new();
+
+// 本次会话中自动忽略的词（小写）
+static SESSION_IGNORED_WORDS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+fn report_count_store() -> &'static Mutex<HashMap<String, usize>> {
+    SESSION_REPORT_COUNTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn ignored_word_store() -> &'static Mutex<HashSet<String>> {
+    SESSION_IGNORED_WORDS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+// 以 s/x/z/ch/sh 结尾的词复数加 es
+fn takes_es(stem: &str) -> bool {
+    ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|ending| stem.ends_with(ending))
+}
+
+// 按规则变化判断 plural 是否为 singular 的复数；bus、process、analysis 这类词本身以 s 结尾，不是复数
+fn is_regular_plural(singular: &str, plural: &str) -> bool {
+    if let Some(stem) = plural.strip_suffix("ies") {
+        if singular.strip_suffix('y') == Some(stem)
+            && stem.ends_with(|c: char| c.is_ascii_alphabetic() && !"aeiou".contains(c))
+        {
+            return true;
+        }
+    }
+    if plural.strip_suffix("es") == Some(singular) && takes_es(singular) {
+        return true;
+    }
+    plural.strip_suffix('s') == Some(singular)
+        && !takes_es(singular)
+        && !singular.ends_with(['u', 'i'])
+}
+
+// 单复数形式互相匹配：返回一个词本身及其在词典中存在的单数或复数写法
+fn plural_variants(word: &str) -> Vec<String> {
+    let mut candidates = vec![format!("{}s", word), format!("{}es", word)];
+    if let Some(stem) = word.strip_suffix("ies") {
+        candidates.push(format!("{}y", stem));
+    }
+    if let Some(stem) = word.strip_suffix('y') {
+        candidates.push(format!("{}ies", stem));
+    }
+    if let Some(stem) = word.strip_suffix("es") {
+        candidates.push(stem.to_string());
+    }
+    if let Some(stem) = word.strip_suffix('s') {
+        candidates.push(stem.to_string());
+    }
+
+    let mut variants = vec![word.to_string()];
+    variants.extend(candidates.into_iter().filter(|candidate| {
+        (is_regular_plural(word, candidate) || is_regular_plural(candidate, word))
+            && dictionary::is_word_in_dictionary(candidate)
+    }));
+    variants
+}
+
+/// 判断一个词是否已在本次会话中被忽略（大小写无关，单复数视为同一个词）
+pub fn is_session_ignored(word: &str) -> bool {
+    let words = match ignored_word_store().lock() {
+        Ok(words) => words,
+        Err(_) => return false,
+    };
+    if words.is_empty() {
+        return false;
+    }
+
+    plural_variants(&word.trim().to_lowercase())
+        .iter()
+        .any(|variant| words.contains(variant))
+}
+
+/// 在本次会话中忽略某个词，应用重启后失效
+pub fn ignore_word_for_session(word: &str) {
+    let word = word.trim().to_lowercase();
+    if word.is_empty() {
+        return;
+    }
+    if let Ok(mut words) = ignored_word_store().lock() {
+        words.insert(word);
+    }
+}
+
+// 从问题描述中提取被标记的词，约定为第一个单引号内的内容
+fn extract_issue_word(issue: &TextIssue) -> String {
+    issue
+        .message
+        .split('\'')
+        .nth(1)
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+/// 记录一条用户标记的误报，同一个词重复误报时自动加入会话忽略集
+pub fn record_false_positive(issue: &TextIssue) -> Result<(), String> {
+    let word = extract_issue_word(issue);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let record = FalsePositiveRecord {
+        timestamp,
+        rule_id: issue.rule_id.clone(),
+        issue_type: issue.issue_type.clone(),
+        word: word.clone(),
+        context: format!("{} / {}", issue.message, issue.suggestion),
+        line_number: issue.line_number,
+    };
+
+    let line = serde_json::to_string(&record).map_err(|e| format!("无法序列化误报记录: {}", e))?;
+    let path = storage::data_file(FALSE_POSITIVE_LOG)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("无法打开误报日志: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("无法写入误报日志: {}", e))?;
+
+    if word.is_empty() {
+        return Ok(());
+    }
+
+    let word_lower = word.to_lowercase();
+    let count = match report_count_store().lock() {
+        Ok(mut counts) => {
+            let count = counts.entry(word_lower.clone()).or_insert(0);
+            *count += 1;
+            *count
+        }
+        Err(_) => return Ok(()),
+    };
+
+    if count >= AUTO_IGNORE_THRESHOLD {
+        if let Ok(mut ignored) = ignored_word_store().lock() {
+            ignored.insert(word_lower);
+        }
+    }
+
+    Ok(())
+}
+
+/// 汇总误报日志，按误报次数从多到少排列各条规则
+pub fn false_positive_stats() -> Result<Vec<FalsePositiveStat>, String> {
+    let path = storage::data_file(FALSE_POSITIVE_LOG)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = std::fs::File::open(&path).map_err(|e| format!("无法读取误报日志: {}", e))?;
+    let reader = BufReader::new(file);
+
+    let mut per_rule: HashMap<String, HashMap<String, usize>> = HashMap::new();
+    let mut rule_counts: HashMap<String, usize> = HashMap::new();
+
+    for line in reader.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => continue,
+        };
+        // 跳过损坏的记录，不影响整体统计
+        let record: FalsePositiveRecord = match serde_json::from_str(&line) {
+            Ok(record) => record,
+            Err(_) => continue,
+        };
+
+        let rule_id = if record.rule_id.is_empty() {
+            record.issue_type.clone()
+        } else {
+            record.rule_id.clone()
+        };
+
+        *rule_counts.entry(rule_id.clone()).or_insert(0) += 1;
+        if !record.word.is_empty() {
+            *per_rule
+                .entry(rule_id)
+                .or_default()
+                .entry(record.word.to_lowercase())
+                .or_insert(0) += 1;
+        }
+    }
+
+    let mut stats: Vec<FalsePositiveStat> = rule_counts
+        .into_iter()
+        .map(|(rule_id, count)| {
+            let mut top_words: Vec<(String, usize)> = per_rule
+                .remove(&rule_id)
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            top_words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            top_words.truncate(MAX_TOP_WORDS);
+
+            FalsePositiveStat {
+                rule_id,
+                count,
+                top_words,
+            }
+        })
+        .collect();
+
+    stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.rule_id.cmp(&b.rule_id)));
+
+    Ok(stats)
+}
+
+// 用户采纳纠正的记录文件：错词 -> (候选词 -> 采纳次数)
+const ACCEPTED_CORRECTIONS_FILE: &str = "accepted_corrections.json";
+
+type AcceptedCorrections = HashMap<String, HashMap<String, usize>>;
+
+// 已采纳的纠正计数，首次使用时从本地文件加载
+static ACCEPTED_CORRECTIONS: OnceLock<Mutex<AcceptedCorrections>> = OnceLock::new();
+
+fn accepted_store() -> &'static Mutex<AcceptedCorrections> {
+    ACCEPTED_CORRECTIONS.get_or_init(|| {
+        let loaded = storage::data_file(ACCEPTED_CORRECTIONS_FILE)
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Mutex::new(loaded)
+    })
+}
+
+/// 记录一次用户采纳的纠正，并写回本地文件
+pub fn record_accepted_correction(wrong: &str, chosen: &str) -> Result<(), String> {
+    let wrong = wrong.trim().to_lowercase();
+    let chosen = chosen.trim().to_string();
+    if wrong.is_empty() || chosen.is_empty() {
+        return Err("错词和采纳的纠正都不能为空".to_string());
+    }
+
+    let json = {
+        let mut accepted = accepted_store()
+            .lock()
+            .map_err(|_| "无法访问采纳记录".to_string())?;
+        *accepted
+            .entry(wrong)
+            .or_default()
+            .entry(chosen)
+            .or_insert(0) += 1;
+        serde_json::to_string_pretty(&*accepted)
+            .map_err(|e| format!("无法序列化采纳记录: {}", e))?
+    };
+
+    let path = storage::data_file(ACCEPTED_CORRECTIONS_FILE)?;
+    std::fs::write(path, json).map_err(|e| format!("无法写入采纳记录: {}", e))
+}
+
+/// 获取某个错词的各候选采纳次数
+pub fn accepted_corrections(wrong: &str) -> HashMap<String, usize> {
+    match accepted_store().lock() {
+        Ok(accepted) => accepted
+            .get(&wrong.trim().to_lowercase())
+            .cloned()
+            .unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regular_plurals_are_recognized() {
+        assert!(is_regular_plural("widget", "widgets"));
+        assert!(is_regular_plural("box", "boxes"));
+        assert!(is_regular_plural("process", "processes"));
+        assert!(is_regular_plural("study", "studies"));
+        assert!(!is_regular_plural("bu", "bus"));
+        assert!(!is_regular_plural("proces", "process"));
+        assert!(!is_regular_plural("analysi", "analysis"));
+        assert!(is_regular_plural("day", "days"));
+    }
+
+    #[test]
+    fn plural_variants_do_not_link_unrelated_words() {
+        assert!(!plural_variants("bus").contains(&"bu".to_string()));
+        assert!(!plural_variants("process").contains(&"proces".to_string()));
+        assert!(!plural_variants("bu").contains(&"bus".to_string()));
+        assert_eq!(plural_variants("bus")[0], "bus");
+    }
+}
@@ -2,6 +2,127 @@ use crate::byte_to_char_index;
 use crate::TextIssue;
 use crate::MAX_ISSUES;
 use regex::Regex;
+use std::sync::{Mutex, OnceLock};
+
+// 成语错别字规则：(错误写法, 正确写法, 说明)
+type IdiomRule = (String, String, String);
+
+// 成语错别字表，从 idioms.tsv 加载一次
+static IDIOM_RULES: OnceLock<Vec<IdiomRule>> = OnceLock::new();
+
+// 用户添加的成语规则，与内置表合并使用
+static CUSTOM_IDIOM_RULES: OnceLock<Mutex<Vec<IdiomRule>>> = OnceLock::new();
+
+// 持久化用户成语规则的文件名，格式与 idioms.tsv 相同
+const CUSTOM_IDIOMS_FILE: &str = "custom_idioms.tsv";
+
+// 未找到 idioms.tsv 时使用的内置条目
+const BUILTIN_IDIOM_RULES: &[(&str, &str)] = &[
+    ("一鸣惊动", "一鸣惊人"),
+    ("不可思异", "不可思议"),
+    ("入木三寸", "入木三分"),
+    ("文不加笔", "文不加点"),
+    ("契而不舍", "锲而不舍"),
+    ("首当其中", "首当其冲"),
+    ("无独有对", "无独有偶"),
+    ("鞭长莫逮", "鞭长莫及"),
+    ("本末颠倒", "本末倒置"),
+    ("刻船求剑", "刻舟求剑"),
+];
+
+// 没有给出说明时的默认说明
+fn default_idiom_explanation(correct: &str) -> String {
+    format!("错误用法，应为'{}'", correct)
+}
+
+// 解析成语规则：每行 "错误写法<Tab>正确写法[<Tab>说明]"，忽略空行和 # 注释
+fn parse_idiom_rules(content: &str) -> Vec<IdiomRule> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split('\t').map(str::trim);
+            let wrong = fields.next().filter(|field| !field.is_empty())?;
+            let correct = fields.next().filter(|field| !field.is_empty())?;
+            let explanation = fields
+                .next()
+                .filter(|field| !field.is_empty())
+                .map_or_else(|| default_idiom_explanation(correct), str::to_string);
+            Some((wrong.to_string(), correct.to_string(), explanation))
+        })
+        .collect()
+}
+
+// 加载成语错别字表
+fn load_idiom_rules() -> &'static Vec<IdiomRule> {
+    IDIOM_RULES.get_or_init(|| {
+        let rules =
+            crate::dictionary::find_resource_file("idioms.tsv", "成语错别字表", |path| {
+                let rules = parse_idiom_rules(&std::fs::read_to_string(path).ok()?);
+                (!rules.is_empty()).then_some(rules)
+            });
+        if let Some(rules) = rules {
+            return rules;
+        }
+
+        eprintln!("未找到成语错别字表文件，使用内置的成语列表");
+        BUILTIN_IDIOM_RULES
+            .iter()
+            .map(|(wrong, correct)| {
+                (
+                    wrong.to_string(),
+                    correct.to_string(),
+                    default_idiom_explanation(correct),
+                )
+            })
+            .collect()
+    })
+}
+
+// 首次使用时从数据目录加载用户添加的成语规则
+fn custom_idiom_store() -> &'static Mutex<Vec<IdiomRule>> {
+    CUSTOM_IDIOM_RULES.get_or_init(|| {
+        let rules = crate::storage::data_file(CUSTOM_IDIOMS_FILE)
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|content| parse_idiom_rules(&content))
+            .unwrap_or_default();
+        Mutex::new(rules)
+    })
+}
+
+/// 添加一条成语错别字规则并持久化；已有相同错误写法时覆盖
+pub fn add_idiom_rule(wrong: &str, correct: &str, explanation: &str) -> Result<(), String> {
+    let (wrong, correct, explanation) = (wrong.trim(), correct.trim(), explanation.trim());
+    if wrong.is_empty() || correct.is_empty() {
+        return Err("错误写法和正确写法都不能为空".to_string());
+    }
+    if [wrong, correct, explanation]
+        .iter()
+        .any(|field| field.contains(['\t', '\n', '\r']))
+    {
+        return Err("成语规则不能包含制表符或换行".to_string());
+    }
+    let explanation = if explanation.is_empty() {
+        default_idiom_explanation(correct)
+    } else {
+        explanation.to_string()
+    };
+
+    let mut rules = custom_idiom_store()
+        .lock()
+        .map_err(|_| "成语规则已损坏".to_string())?;
+    rules.retain(|(existing, _, _)| existing != wrong);
+    rules.push((wrong.to_string(), correct.to_string(), explanation));
+
+    let content: String = rules
+        .iter()
+        .map(|(wrong, correct, explanation)| format!("{}\t{}\t{}\n", wrong, correct, explanation))
+        .collect();
+    let path = crate::storage::data_file(CUSTOM_IDIOMS_FILE)?;
+    std::fs::write(path, content).map_err(|e| format!("无法保存成语规则: {}", e))
+}
 
 // Check for idiom usage - moved from lib.rs to avoid duplication
 pub fn check_idiom_usage(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
@@ -10,36 +131,37 @@ pub fn check_idiom_usage(line: &str, line_idx: usize, issues: &mut Vec<TextIssue
         return;
     }
 
-    // Common incorrect idiom usages - simplified list
-    let idiom_pairs = [
-        ("一鸣惊动", "一鸣惊人", "错误用法，应为'一鸣惊人'"),
-        ("不可思异", "不可思议", "错误用法，应为'不可思议'"),
-        ("入木三寸", "入木三分", "错误用法，应为'入木三分'"),
-        ("文不加笔", "文不加点", "错误用法，应为'文不加点'"),
-        ("契而不舍", "锲而不舍", "错误用法，应为'锲而不舍'"),
-        ("首当其中", "首当其冲", "错误用法，应为'首当其冲'"),
-        ("无独有对", "无独有偶", "错误用法，应为'无独有偶'"),
-        ("鞭长莫逮", "鞭长莫及", "错误用法，应为'鞭长莫及'"),
-        ("本末颠倒", "本末倒置", "错误用法，应为'本末倒置'"),
-        ("刻船求剑", "刻舟求剑", "错误用法，应为'刻舟求剑'"),
-    ];
+    let custom_rules = match custom_idiom_store().lock() {
+        Ok(rules) => rules.clone(),
+        Err(_) => Vec::new(),
+    };
+    // 用户规则优先，同一错误写法不重复报告
+    let mut rules: Vec<&IdiomRule> = custom_rules.iter().collect();
+    for rule in load_idiom_rules() {
+        if !custom_rules.iter().any(|(wrong, _, _)| *wrong == rule.0) {
+            rules.push(rule);
+        }
+    }
 
-    for (wrong_idiom, correct_idiom, explanation) in idiom_pairs {
-        if line.contains(wrong_idiom) {
-            if let Some(pos) = line.find(wrong_idiom) {
-                issues.push(TextIssue {
-                    line_number: line_idx + 1,
-                    start: byte_to_char_index(line, pos),
-                    end: byte_to_char_index(line, pos + wrong_idiom.len()),
-                    issue_type: "成语用法".to_string(),
-                    message: format!("成语使用错误: '{}'", wrong_idiom),
-                    suggestion: format!("应使用: '{}'，{}", correct_idiom, explanation),
-                });
+    for (wrong_idiom, correct_idiom, explanation) in rules {
+        for (pos, _) in line.match_indices(wrong_idiom.as_str()) {
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, pos),
+                end: byte_to_char_index(line, pos + wrong_idiom.len()),
+                issue_type: "成语用法".to_string(),
+                message: format!("成语使用错误: '{}'", wrong_idiom),
+                suggestion: format!("应使用: '{}'，{}", correct_idiom, explanation),
+                rule_id: "idiom_usage".to_string(),
+                replacement: Some(correct_idiom.clone()),
+                confidence: 1.0,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
 
-                // Stop if we've found too many issues
-                if issues.len() >= MAX_ISSUES {
-                    return;
-                }
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
             }
         }
     }
@@ -85,6 +207,11 @@ pub fn check_academic_style(
                     issue_type: "学术写作风格".to_string(),
                     message: "学术写作中应避免使用缩写形式".to_string(),
                     suggestion: format!("使用完整形式: '{}'", full_form),
+                    rule_id: "academic_style".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "info".to_string(),
+                    language: String::new(),
                 });
 
                 // Stop if we've found too many issues
@@ -110,6 +237,11 @@ pub fn check_academic_style(
                     issue_type: "学术写作风格".to_string(),
                     message: "正式学术写作中应避免使用第一人称代词".to_string(),
                     suggestion: "考虑使用被动语态或更客观的表达方式".to_string(),
+                    rule_id: "academic_style".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "info".to_string(),
+                    language: String::new(),
                 });
 
                 // Stop if we've found too many issues
@@ -142,6 +274,11 @@ pub fn check_academic_style(
                         issue_type: "学术写作风格".to_string(),
                         message: format!("非正式表达: '{}'", informal),
                         suggestion: format!("考虑使用更正式的表达: '{}'", formal),
+                        rule_id: "academic_style".to_string(),
+                        replacement: None,
+                        confidence: 1.0,
+                        severity: "info".to_string(),
+                        language: String::new(),
                     });
 
                     // Stop if we've found too many issues
@@ -166,6 +303,11 @@ pub fn check_academic_style(
                         issue_type: "学术写作风格".to_string(),
                         message: format!("正式学术写作中应避免使用非正式代词 '{}'", pronoun),
                         suggestion: "建议使用 '我们' 或更正式的表达方式".to_string(),
+                        rule_id: "academic_style".to_string(),
+                        replacement: None,
+                        confidence: 1.0,
+                        severity: "info".to_string(),
+                        language: String::new(),
                     });
 
                     // Stop if we've found too many issues
@@ -178,20 +320,41 @@ pub fn check_academic_style(
     }
 }
 
+// 中文句子默认允许的最大字符数
+pub const DEFAULT_MAX_SENTENCE_CHARS: usize = 100;
+
+// 英文句子默认允许的最大单词数
+pub const DEFAULT_MAX_SENTENCE_WORDS: usize = 40;
+
 // Check for sentence length issues
 pub fn check_sentence_length(
     line: &str,
     line_idx: usize,
     issues: &mut Vec<TextIssue>,
     language: &str,
+    max_length: usize,
 ) {
     // Skip if we've already found too many issues
     if issues.len() >= MAX_ISSUES {
         return;
     }
 
-    // Define maximum recommended sentence length (in characters)
-    let max_length = if language == "zh" { 100 } else { 200 };
+    // 中文按字符数、英文按单词数衡量句子长度
+    let unit = if language == "zh" {
+        "字符"
+    } else {
+        "个单词"
+    };
+    let measure = |sentence: &str| {
+        if language == "zh" {
+            sentence.chars().count()
+        } else {
+            sentence
+                .split_whitespace()
+                .filter(|word| word.chars().any(|c| c.is_alphanumeric()))
+                .count()
+        }
+    };
 
     // Split the line into sentences
     // Use Vec instead of fixed-size arrays to avoid type mismatch
@@ -203,14 +366,39 @@ pub fn check_sentence_length(
 
     let mut start_pos = 0;
     let mut in_sentence = true;
+    // 引号和括号的嵌套层数，层内的终止标点不断句
+    let mut depth = 0usize;
+    let mut in_straight_quote = false;
+    let mut prev_is_ending = false;
 
     for (i, c) in line.char_indices() {
-        if sentence_endings.contains(&c) {
+        let terminal = sentence_endings.contains(&c) && (c != '.' || is_sentence_period(line, i));
+        let quoted_before = depth > 0 || in_straight_quote;
+        match c {
+            '“' | '‘' | '（' | '(' | '「' | '『' | '【' | '[' => depth += 1,
+            '”' | '）' | ')' | '」' | '』' | '】' | ']' => depth = depth.saturating_sub(1),
+            // don’t 中的撇号不是右引号
+            '’' if !line[i + c.len_utf8()..]
+                .chars()
+                .next()
+                .is_some_and(|n| n.is_alphabetic()) =>
+            {
+                depth = depth.saturating_sub(1)
+            }
+            '"' => in_straight_quote = !in_straight_quote,
+            _ => {}
+        }
+        let quoted = depth > 0 || in_straight_quote;
+        // 引语以终止标点结尾时，如 “我来了。”，在右引号处断句
+        let is_ending =
+            (terminal && !quoted_before) || (quoted_before && !quoted && prev_is_ending);
+        prev_is_ending = terminal && quoted_before;
+
+        if is_ending {
             if in_sentence {
                 // 计算字符的结束位置（字符安全）
                 let char_end_pos = i + c.len_utf8();
-                let sentence = &line[start_pos..char_end_pos];
-                let sentence_length = sentence.chars().count();
+                let sentence_length = measure(&line[start_pos..char_end_pos]);
 
                 if sentence_length > max_length {
                     issues.push(TextIssue {
@@ -218,8 +406,13 @@ pub fn check_sentence_length(
                         start: byte_to_char_index(line, start_pos),
                         end: byte_to_char_index(line, char_end_pos),
                         issue_type: "句子长度".to_string(),
-                        message: format!("句子过长 ({} 字符)", sentence_length),
+                        message: format!("句子过长 ({} {})", sentence_length, unit),
                         suggestion: "考虑将长句拆分为多个短句，以提高可读性".to_string(),
+                        rule_id: "sentence_length".to_string(),
+                        replacement: None,
+                        confidence: 1.0,
+                        severity: "warning".to_string(),
+                        language: String::new(),
                     });
 
                     // Stop if we've found too many issues
@@ -237,18 +430,65 @@ pub fn check_sentence_length(
     }
 
     // Check if the last part of the line is a long sentence without ending punctuation
-    if in_sentence && line.len() - start_pos > max_length {
+    let tail_length = measure(&line[start_pos..]);
+    if in_sentence && tail_length > max_length {
         issues.push(TextIssue {
             line_number: line_idx + 1,
             start: byte_to_char_index(line, start_pos),
             end: byte_to_char_index(line, line.len()),
             issue_type: "句子长度".to_string(),
-            message: format!("可能的长句 ({} 字符)", line.len() - start_pos),
+            message: format!("可能的长句 ({} {})", tail_length, unit),
             suggestion: "考虑将长句拆分为多个短句，以提高可读性".to_string(),
+            rule_id: "sentence_length".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "warning".to_string(),
+            language: String::new(),
         });
     }
 }
 
+// 英文中带句点的常见缩写（不含末尾句点，小写）
+const ABBREVIATIONS: &[&str] = &[
+    "e.g", "i.e", "etc", "mr", "mrs", "ms", "dr", "prof", "st", "jr", "sr", "vs", "fig", "figs",
+    "no", "al", "cf", "approx", "eq", "vol", "pp", "ed", "eds", "dept", "inc", "ltd", "co", "corp",
+];
+
+/// 判断 pos 处的句点是否属于缩写、姓名首字母或 3.14 这类数字，而非句末句号
+pub fn is_abbreviation_period(line: &str, pos: usize) -> bool {
+    // 句点后紧跟字母数字，如 3.14、e.g 中的第一个点
+    if line[pos + 1..]
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric())
+    {
+        return true;
+    }
+
+    let word_start = line[..pos]
+        .rfind(|c: char| c.is_whitespace() || c == '(')
+        .map_or(0, |idx| idx + 1);
+    let word = line[word_start..pos].to_lowercase();
+    let is_initial = word.chars().count() == 1 && word.chars().all(|c| c.is_alphabetic());
+
+    is_initial || ABBREVIATIONS.contains(&word.as_str())
+}
+
+// 判断 pos 处的句点是否表示句子结束：排除缩写、姓名首字母和小数等
+fn is_sentence_period(line: &str, pos: usize) -> bool {
+    // 后面的词以小写字母开头，通常仍是同一句
+    if line[pos + 1..]
+        .trim_start()
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_lowercase())
+    {
+        return false;
+    }
+
+    !is_abbreviation_period(line, pos)
+}
+
 // Check for citation format consistency
 pub fn check_citation_format(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
     // Skip if we've already found too many issues
@@ -277,12 +517,19 @@ pub fn check_citation_format(line: &str, line_idx: usize, issues: &mut Vec<TextI
         Err(_) => return,
     };
 
+    // GB/T 7714 条目同样以 [1] 编号，按题名后的文献类型标识区分
+    let gbt_citation = match Regex::new(crate::document_check::GBT_TYPE_PATTERN) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
     let has_apa = apa_citation.is_match(line);
     let has_mla = mla_citation.is_match(line);
     let has_chicago = chicago_citation.is_match(line);
-    let has_ieee = ieee_citation.is_match(line);
+    let has_gbt = gbt_citation.is_match(line);
+    let has_ieee = ieee_citation.is_match(line) && !has_gbt;
 
-    let citation_count = [has_apa, has_mla, has_chicago, has_ieee]
+    let citation_count = [has_apa, has_mla, has_chicago, has_ieee, has_gbt]
         .iter()
         .filter(|&&x| x)
         .count();
@@ -294,7 +541,13 @@ pub fn check_citation_format(line: &str, line_idx: usize, issues: &mut Vec<TextI
             end: line.len(),
             issue_type: "引用格式".to_string(),
             message: "同一行中存在不同的引用格式".to_string(),
-            suggestion: "请统一使用一种引用格式（如APA、MLA、Chicago或IEEE）".to_string(),
+            suggestion: "请统一使用一种引用格式（如APA、MLA、Chicago、IEEE或GB/T 7714）"
+                .to_string(),
+            rule_id: "citation_format".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "warning".to_string(),
+            language: String::new(),
         });
 
         // Stop if we've found too many issues
@@ -331,6 +584,94 @@ pub fn check_citation_format(line: &str, line_idx: usize, issues: &mut Vec<TextI
                 issue_type: "引用格式".to_string(),
                 message: message.to_string(),
                 suggestion: suggestion.to_string(),
+                rule_id: "citation_format".to_string(),
+                replacement: None,
+                confidence: 1.0,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+// 常见的书名、作品名，只收录不会与普通词语混淆的名称
+const WORK_TITLES: &[&str] = &[
+    "红楼梦",
+    "三国演义",
+    "水浒传",
+    "西游记",
+    "儒林外史",
+    "聊斋志异",
+    "资治通鉴",
+    "道德经",
+    "孙子兵法",
+    "本草纲目",
+    "天工开物",
+    "朝花夕拾",
+    "骆驼祥子",
+    "平凡的世界",
+    "白鹿原",
+    "百年孤独",
+    "战争与和平",
+    "哈姆雷特",
+    "老人与海",
+    "悲惨世界",
+    "巴黎圣母院",
+    "钢铁是怎样炼成的",
+    "傲慢与偏见",
+    "追风筝的人",
+    "狂人日记",
+    "阿Q正传",
+];
+
+// 判断某个位置是否处在《》之内
+fn inside_title_marks(line: &str, pos: usize) -> bool {
+    let before = &line[..pos];
+    match (before.rfind('《'), before.rfind('》')) {
+        (Some(open), Some(close)) => open > close,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+// Check for well-known work titles that are not wrapped in 《》
+pub fn check_missing_title_marks(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    for title in WORK_TITLES {
+        for (pos, _) in line.match_indices(title) {
+            if inside_title_marks(line, pos) {
+                continue;
+            }
+            // 已用引号标出的名称也不再提示
+            let prev = line[..pos].chars().last();
+            let next = line[pos + title.len()..].chars().next();
+            if matches!(prev, Some('“') | Some('"') | Some('「'))
+                && matches!(next, Some('”') | Some('"') | Some('」'))
+            {
+                continue;
+            }
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, pos),
+                end: byte_to_char_index(line, pos + title.len()),
+                issue_type: "书名号".to_string(),
+                message: format!("作品名 '{}' 未使用书名号", title),
+                suggestion: format!("建议使用书名号: '《{}》'", title),
+                rule_id: "title_marks".to_string(),
+                replacement: Some(format!("《{}》", title)),
+                confidence: 0.7,
+                severity: "info".to_string(),
+                language: String::new(),
             });
 
             // Stop if we've found too many issues
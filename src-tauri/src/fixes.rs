@@ -0,0 +1,389 @@
+use crate::TextIssue;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+// diff 中每个改动前后保留的上下文行数
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// 一键修复时自动应用的最低置信度，更低的问题需要用户逐个确认
+pub const AUTO_FIX_MIN_CONFIDENCE: f32 = 0.8;
+
+// 取问题的替换文本：优先使用 replacement，否则从"建议修改为: 'xxx'"这类建议中解析
+fn replacement_for(issue: &TextIssue) -> Option<String> {
+    if let Some(replacement) = &issue.replacement {
+        return Some(replacement.clone());
+    }
+
+    // 建议后面可能跟着"，说明"或"（其他候选: ...）"
+    let re = Regex::new(r"^(?:建议修改为|应为|应使用): '(.*?)'(?:$|，|（)").ok()?;
+    if let Some(m) = re.captures(&issue.suggestion).and_then(|caps| caps.get(1)) {
+        return Some(m.as_str().to_string());
+    }
+
+    // "删除重复的 'xxx'"：问题区间覆盖两处重复，只保留一处
+    let re = Regex::new(r"^(?:检查是否需要)?删除重复的 '(.*)'$").ok()?;
+    re.captures(&issue.suggestion)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
+/// 应用修复后的文本及每处修改的记录，前端可据此撤销
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FixResult {
+    pub new_text: String,
+    // (起始字符, 结束字符, 原文)：区间为替换文本在 new_text 中的字符位置（换行计为一个字符）
+    pub applied: Vec<(usize, usize, String)>,
+}
+
+/// 对文本应用所有可自动修复的问题，重叠的问题只应用最先出现的一个
+///
+/// 置信度低于 AUTO_FIX_MIN_CONFIDENCE 的问题不会自动应用。
+pub fn apply_all_fixes(text: &str, issues: &[TextIssue]) -> FixResult {
+    let accepted: Vec<usize> = issues
+        .iter()
+        .enumerate()
+        .filter(|(_, issue)| issue.confidence >= AUTO_FIX_MIN_CONFIDENCE)
+        .map(|(idx, _)| idx)
+        .collect();
+    apply_fixes_with_history(text, issues, &accepted)
+}
+
+/// 对文本应用单个问题的修复，没有明确替换文本时原样返回
+pub fn apply_fix(text: &str, issue: &TextIssue) -> FixResult {
+    apply_fixes_with_history(text, std::slice::from_ref(issue), &[0])
+}
+
+/// 对文本应用选中的问题修复，accepted 为 issues 中的下标
+///
+/// 没有明确替换文本的问题和与已选修复重叠的问题会被跳过。
+pub fn apply_fixes(text: &str, issues: &[TextIssue], accepted: &[usize]) -> String {
+    apply_fixes_with_history(text, issues, accepted).new_text
+}
+
+/// 与 apply_fixes 相同，但同时返回每处修改在新文本中的位置和原文
+pub fn apply_fixes_with_history(text: &str, issues: &[TextIssue], accepted: &[usize]) -> FixResult {
+    // (行号, 起始字符, 结束字符, 替换文本)
+    let mut fixes: Vec<(usize, usize, usize, String)> = accepted
+        .iter()
+        .filter_map(|idx| issues.get(*idx))
+        .filter_map(|issue| {
+            replacement_for(issue)
+                .map(|replacement| (issue.line_number, issue.start, issue.end, replacement))
+        })
+        .collect();
+    fixes.sort_by_key(|fix| (fix.0, fix.1));
+
+    let mut lines: Vec<String> = text.split('\n').map(|line| line.to_string()).collect();
+    let mut fix_iter = fixes.into_iter().peekable();
+    let mut applied = Vec::new();
+    // 当前行在新文本中的起始字符位置，累积了前面各行修改带来的偏移
+    let mut line_offset = 0;
+
+    for (idx, line) in lines.iter_mut().enumerate() {
+        let mut line_fixes = Vec::new();
+        while let Some(fix) = fix_iter.next_if(|fix| fix.0 == idx + 1) {
+            // 跳过与前一个修复重叠的区间
+            let overlaps = line_fixes
+                .last()
+                .is_some_and(|prev: &(usize, usize, usize, String)| fix.1 < prev.2);
+            if !overlaps {
+                line_fixes.push(fix);
+            }
+        }
+        if line_fixes.is_empty() {
+            line_offset += line.chars().count() + 1;
+            continue;
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut fixed = String::new();
+        // fixed 中已有的字符数
+        let mut fixed_len = 0;
+        let mut pos = 0;
+        for (_, start, end, replacement) in line_fixes {
+            if start < pos || end > chars.len() || start > end {
+                continue;
+            }
+            fixed.extend(&chars[pos..start]);
+            fixed_len += start - pos;
+            let replacement_len = replacement.chars().count();
+            applied.push((
+                line_offset + fixed_len,
+                line_offset + fixed_len + replacement_len,
+                chars[start..end].iter().collect(),
+            ));
+            fixed.push_str(&replacement);
+            fixed_len += replacement_len;
+            pos = end;
+        }
+        fixed.extend(&chars[pos..]);
+        fixed_len += chars.len() - pos;
+        *line = fixed;
+        line_offset += fixed_len + 1;
+    }
+
+    FixResult {
+        new_text: lines.join("\n"),
+        applied,
+    }
+}
+
+/// 撤销 apply_fix 记录的修改，把 applied 中的区间还原为原文
+///
+/// 从后往前还原，前面区间的位置不受影响；越界或相互重叠的记录会被跳过。
+pub fn revert_fixes(text: &str, applied: &[(usize, usize, String)]) -> String {
+    let mut entries: Vec<&(usize, usize, String)> = applied.iter().collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.0));
+
+    let mut chars: Vec<char> = text.chars().collect();
+    let mut limit = chars.len();
+    for (start, end, original) in entries {
+        if start > end || *end > limit {
+            continue;
+        }
+        chars.splice(*start..*end, original.chars());
+        limit = *start;
+    }
+
+    chars.into_iter().collect()
+}
+
+// diff 中的一行：相同、删除或新增
+enum DiffLine<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+// Myers 算法的中间蛇：返回蛇的起点和终点 (x, y, u, v)，两侧的子问题可分别递归求解
+// 只保存两条对角线数组，内存与行数成正比
+fn middle_snake(old: &[&str], new: &[&str]) -> (usize, usize, usize, usize) {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = (n + m + 1) / 2;
+    let offset = max + 1;
+    // forward[k] 为正向在对角线 k 上到达的最远 x，backward[c] 为反向从末尾起算到达的最远距离
+    let mut forward = vec![0isize; (2 * offset + 1) as usize];
+    let mut backward = vec![0isize; (2 * offset + 1) as usize];
+    let at = |k: isize| (k + offset) as usize;
+
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) {
+                forward[at(k + 1)]
+            } else {
+                forward[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            let (start_x, start_y) = (x, y);
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[at(k)] = x;
+
+            // 正向对角线 k 对应反向对角线 delta - k
+            let c = delta - k;
+            if odd && (-(d - 1)..=d - 1).contains(&c) && x + backward[at(c)] >= n {
+                return (start_x as usize, start_y as usize, x as usize, y as usize);
+            }
+        }
+
+        for c in (-d..=d).step_by(2) {
+            let mut x = if c == -d || (c != d && backward[at(c - 1)] < backward[at(c + 1)]) {
+                backward[at(c + 1)]
+            } else {
+                backward[at(c - 1)] + 1
+            };
+            let mut y = x - c;
+            let (end_x, end_y) = (x, y);
+            while x < n && y < m && old[(n - x - 1) as usize] == new[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[at(c)] = x;
+
+            let k = delta - c;
+            if !odd && (-d..=d).contains(&k) && x + forward[at(k)] >= n {
+                return (
+                    (n - x) as usize,
+                    (m - y) as usize,
+                    (n - end_x) as usize,
+                    (m - end_y) as usize,
+                );
+            }
+        }
+    }
+
+    // 两个方向最迟在 max 步内相遇，不会走到这里
+    (0, 0, 0, 0)
+}
+
+// 按线性空间的 Myers 算法递归计算最短的逐行差异，结果追加到 result
+fn diff_into<'a>(old: &[&'a str], new: &[&'a str], result: &mut Vec<DiffLine<'a>>) {
+    // 先去掉相同的首尾，缩小需要计算的范围
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    result.extend(old[..prefix].iter().map(|l| DiffLine::Equal(l)));
+    if old_mid.is_empty() || new_mid.is_empty() {
+        result.extend(old_mid.iter().map(|l| DiffLine::Delete(l)));
+        result.extend(new_mid.iter().map(|l| DiffLine::Insert(l)));
+    } else {
+        let (x, y, u, v) = middle_snake(old_mid, new_mid);
+        diff_into(&old_mid[..x], &new_mid[..y], result);
+        result.extend(old_mid[x..u].iter().map(|l| DiffLine::Equal(l)));
+        diff_into(&old_mid[u..], &new_mid[v..], result);
+    }
+    result.extend(old[old.len() - suffix..].iter().map(|l| DiffLine::Equal(l)));
+}
+
+// 计算逐行差异
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let mut result = Vec::with_capacity(old.len().max(new.len()));
+    diff_into(old, new, &mut result);
+    result
+}
+
+// 生成 unified diff 的范围描述，如 "3,7"；空范围的起始行取前一行
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len),
+    }
+}
+
+/// 生成原文与修改后文本之间的 unified diff，没有差异时返回空字符串
+pub fn unified_diff(old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.split('\n').collect();
+    let new_lines: Vec<&str> = new.split('\n').collect();
+    let diff = diff_lines(&old_lines, &new_lines);
+
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Equal(_)))
+        .map(|(idx, _)| idx)
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // 把相距不超过两倍上下文的改动合并为同一个 hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for idx in changed {
+        let start = idx.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (idx + DIFF_CONTEXT_LINES + 1).min(diff.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut output = String::from("--- 原文\n+++ 修改后\n");
+    // 记录每个 diff 位置之前的新旧行数，用于计算 hunk 头
+    let mut old_before = Vec::with_capacity(diff.len());
+    let mut new_before = Vec::with_capacity(diff.len());
+    let (mut old_count, mut new_count) = (0, 0);
+    for line in &diff {
+        old_before.push(old_count);
+        new_before.push(new_count);
+        match line {
+            DiffLine::Equal(_) => {
+                old_count += 1;
+                new_count += 1;
+            }
+            DiffLine::Delete(_) => old_count += 1,
+            DiffLine::Insert(_) => new_count += 1,
+        }
+    }
+
+    for (start, end) in hunks {
+        let old_len = diff[start..end]
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Insert(_)))
+            .count();
+        let new_len = diff[start..end]
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Delete(_)))
+            .count();
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_before[start], old_len),
+            hunk_range(new_before[start], new_len)
+        ));
+
+        for line in &diff[start..end] {
+            let (prefix, content) = match line {
+                DiffLine::Equal(content) => (' ', content),
+                DiffLine::Delete(content) => ('-', content),
+                DiffLine::Insert(content) => ('+', content),
+            };
+            output.push(prefix);
+            output.push_str(content);
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(start: usize, end: usize, replacement: &str, confidence: f32) -> TextIssue {
+        TextIssue {
+            line_number: 1,
+            start,
+            end,
+            issue_type: "可能的拼写错误".to_string(),
+            message: String::new(),
+            suggestion: String::new(),
+            rule_id: "spelling".to_string(),
+            replacement: Some(replacement.to_string()),
+            confidence,
+            severity: "error".to_string(),
+            language: String::new(),
+        }
+    }
+
+    #[test]
+    fn apply_all_fixes_skips_low_confidence_issues() {
+        let issues = vec![issue(0, 4, "this", 0.9), issue(5, 10, "Grace", 0.6)];
+        let result = apply_all_fixes("thsi Grase", &issues);
+
+        assert_eq!(result.new_text, "this Grase");
+        assert_eq!(result.applied, vec![(0, 4, "thsi".to_string())]);
+    }
+
+    #[test]
+    fn unified_diff_reports_changed_lines_in_large_text() {
+        let old: Vec<String> = (0..50_000).map(|i| format!("line {}", i)).collect();
+        let mut new = old.clone();
+        new[10] = "changed".to_string();
+        new.insert(40_000, "inserted".to_string());
+
+        let diff = unified_diff(&old.join("\n"), &new.join("\n"));
+        assert!(diff.contains("@@ -8,7 +8,7 @@\n"));
+        assert!(diff.contains("-line 10\n+changed\n"));
+        assert!(diff.contains(" line 39999\n+inserted\n line 40000\n"));
+        assert_eq!(diff.lines().filter(|l| l.starts_with('+')).count(), 3);
+        assert_eq!(diff.lines().filter(|l| l.starts_with('-')).count(), 2);
+    }
+
+    #[test]
+    fn apply_fixes_applies_accepted_low_confidence_issue() {
+        let issues = vec![issue(5, 10, "Grace", 0.6)];
+        assert_eq!(apply_fixes("thsi Grase", &issues, &[0]), "thsi Grace");
+    }
+}
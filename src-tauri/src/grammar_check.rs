@@ -1,7 +1,11 @@
 use crate::byte_to_char_index;
+use crate::fix_functions::is_abbreviation_period;
+use crate::spelling_dict::{capitalize_first, match_case};
+use crate::traditional::is_chinese_char;
 use crate::TextIssue;
 use crate::MAX_ISSUES;
 use regex::Regex;
+use std::sync::OnceLock;
 
 // Check for word order issues in Chinese
 pub fn check_word_order(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
@@ -32,6 +36,11 @@ pub fn check_word_order(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>
                 issue_type: "语序问题".to_string(),
                 message: format!("语序结构: {}", mat.as_str()),
                 suggestion: format!("建议使用: {}, {}", correct_form, explanation),
+                rule_id: "word_order".to_string(),
+                replacement: None,
+                confidence: 1.0,
+                severity: "warning".to_string(),
+                language: String::new(),
             });
 
             // Stop if we've found too many issues
@@ -63,6 +72,11 @@ pub fn check_chinese_punctuation(line: &str, line_idx: usize, issues: &mut Vec<T
             issue_type: "标点符号".to_string(),
             message: "连续使用相同的标点符号".to_string(),
             suggestion: "使用单个标点符号".to_string(),
+            rule_id: "chinese_punctuation".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "warning".to_string(),
+            language: String::new(),
         });
 
         // Stop if we've found too many issues
@@ -71,70 +85,201 @@ pub fn check_chinese_punctuation(line: &str, line_idx: usize, issues: &mut Vec<T
         }
     }
 
-    // Check for unpaired Chinese brackets
-    if line.contains("（") && !line.contains("）") {
-        if let Some(pos) = line.find("（") {
-            issues.push(TextIssue {
-                line_number: line_idx + 1,
-                start: byte_to_char_index(line, pos),
-                end: byte_to_char_index(line, pos + "（".len()),
-                issue_type: "标点符号".to_string(),
-                message: "圆括号不配对".to_string(),
-                suggestion: "添加右括号）".to_string(),
-            });
-        }
-    }
+    // Check for unpaired Chinese brackets, quotes and title marks
+    check_chinese_pairs(line, line_idx, issues);
 
     // Check for English bracket issues
     check_english_bracket_issues(line, line_idx, issues);
 }
 
+// 需要成对出现的中文标点：(左半, 右半, 名称)
+const CHINESE_PAIRS: &[(char, char, &str)] = &[
+    ('（', '）', "圆括号"),
+    ('“', '”', "双引号"),
+    ('‘', '’', "单引号"),
+    ('《', '》', "书名号"),
+    ('【', '】', "方括号"),
+];
+
+// 用栈检查成对标点，正确嵌套的如 “他说‘好’” 不报告
+fn check_chinese_pairs(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // (左半所在字节位置, 在 CHINESE_PAIRS 中的下标)
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+    // (字节位置, 标点, 说明, 建议)
+    let mut unpaired: Vec<(usize, char, String, String)> = Vec::new();
+
+    let missing_close = |pos: usize, idx: usize| {
+        let (open, close, name) = CHINESE_PAIRS[idx];
+        (
+            pos,
+            open,
+            format!("{}不配对：缺少{}", name, close),
+            format!("添加对应的{}", close),
+        )
+    };
+
+    for (pos, c) in line.char_indices() {
+        if let Some(idx) = CHINESE_PAIRS.iter().position(|(open, _, _)| *open == c) {
+            stack.push((pos, idx));
+            continue;
+        }
+        let idx = match CHINESE_PAIRS.iter().position(|(_, close, _)| *close == c) {
+            Some(idx) => idx,
+            None => continue,
+        };
+
+        match stack.iter().rposition(|(_, open_idx)| *open_idx == idx) {
+            Some(depth) => {
+                // 中间尚未闭合的左半都缺少右半
+                for (open_pos, open_idx) in stack.drain(depth + 1..) {
+                    unpaired.push(missing_close(open_pos, open_idx));
+                }
+                stack.pop();
+            }
+            None => {
+                let (open, close, name) = CHINESE_PAIRS[idx];
+                unpaired.push((
+                    pos,
+                    close,
+                    format!("{}不配对：缺少{}", name, open),
+                    format!("添加对应的{}，或删除多余的{}", open, close),
+                ));
+            }
+        }
+    }
+    for (open_pos, open_idx) in stack {
+        unpaired.push(missing_close(open_pos, open_idx));
+    }
+
+    unpaired.sort_by_key(|(pos, _, _, _)| *pos);
+    for (pos, mark, message, suggestion) in unpaired {
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, pos),
+            end: byte_to_char_index(line, pos + mark.len_utf8()),
+            issue_type: "标点符号".to_string(),
+            message,
+            suggestion,
+            rule_id: "chinese_punctuation".to_string(),
+            replacement: None,
+            // 跨段落的引文常只有左引号
+            confidence: 0.8,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 过去时间标记
+const PAST_TIME_MARKERS: &str =
+    r"(?i)\b(?:yesterday|last\s+(?:night|week|month|year)|ago|in\s+the\s+past)\b";
+// 与过去时间标记冲突的现在时动词
+const PRESENT_VERBS: &[&str] = &["is", "are", "am", "do", "does", "have", "has"];
+// 这些词引出另一个子句或转述的内容，其后的时态可以与时间标记不同
+const TENSE_CLAUSE_BREAKS: &[&str] = &[
+    "that", "which", "who", "whom", "whose", "because", "although", "though", "while", "when",
+    "whenever", "if", "unless", "since", "and", "but", "or", "so", "said", "say", "says",
+    "thought", "think", "believe", "believed", "know", "knew", "told", "tell", "claimed",
+    "reported", "found",
+];
+// 只在时间标记前后这么多个单词内查找现在时动词
+const TENSE_MARKER_WINDOW: usize = 5;
+
 // Check for tense consistency in English
+// 只有现在时动词与过去时间标记在同一子句且相距不远时才提示
 pub fn check_tense_consistency(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
     // Skip if we've already found too many issues
     if issues.len() >= MAX_ISSUES {
         return;
     }
 
-    // Past tense markers
-    let past_tense_markers = [
-        "yesterday",
-        "last week",
-        "last month",
-        "last year",
-        "ago",
-        "in the past",
-    ];
+    let (marker_regex, word_regex) = match (
+        Regex::new(PAST_TIME_MARKERS),
+        Regex::new(r"[A-Za-z]+(?:'[A-Za-z]+)?"),
+    ) {
+        (Ok(marker), Ok(word)) => (marker, word),
+        _ => return,
+    };
 
-    // Present tense verbs
-    let present_verbs = [r"\b(is|are|am)\b", r"\b(do|does)\b", r"\b(have|has)\b"];
+    let words: Vec<regex::Match> = word_regex.find_iter(line).collect();
+    // clause_start[i]：第 i 个词与前一个词之间有句号、分号等子句分隔标点
+    let clause_start: Vec<bool> = words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let gap_start = if i == 0 { 0 } else { words[i - 1].end() };
+            line[gap_start..word.start()].contains(['.', ';', ':', '!', '?'])
+        })
+        .collect();
+    let is_break_word =
+        |i: usize| TENSE_CLAUSE_BREAKS.contains(&words[i].as_str().to_lowercase().as_str());
+    let is_present_verb =
+        |i: usize| PRESENT_VERBS.contains(&words[i].as_str().to_lowercase().as_str());
 
-    // Check for past tense markers with present tense verbs
-    for marker in past_tense_markers {
-        if line.to_lowercase().contains(marker) {
-            for verb_pattern in present_verbs {
-                let regex = match Regex::new(verb_pattern) {
-                    Ok(re) => re,
-                    Err(_) => continue, // Skip this pattern if regex creation fails
-                };
+    for marker in marker_regex.find_iter(line) {
+        let first = match words.iter().position(|word| word.start() >= marker.start()) {
+            Some(first) => first,
+            None => continue,
+        };
+        let last = words
+            .iter()
+            .rposition(|word| word.end() <= marker.end())
+            .unwrap_or(first);
 
-                if let Some(mat) = regex.find(line) {
-                    issues.push(TextIssue {
-                        line_number: line_idx + 1,
-                        start: byte_to_char_index(line, mat.start()),
-                        end: byte_to_char_index(line, mat.end()),
-                        issue_type: "时态一致性".to_string(),
-                        message: "过去时间标记与现在时态动词".to_string(),
-                        suggestion: "使用过去时态动词".to_string(),
-                    });
-
-                    // Stop if we've found too many issues
-                    if issues.len() >= MAX_ISSUES {
-                        return;
-                    }
+        // 先向后找，再向前找；遇到子句分隔就停止
+        let mut found = None;
+        for i in (last + 1..words.len()).take(TENSE_MARKER_WINDOW) {
+            if clause_start[i] || is_break_word(i) {
+                break;
+            }
+            if is_present_verb(i) {
+                found = Some(i);
+                break;
+            }
+        }
+        if found.is_none() {
+            for i in (0..first).rev().take(TENSE_MARKER_WINDOW) {
+                if clause_start[i + 1] || is_break_word(i) {
+                    break;
+                }
+                if is_present_verb(i) {
+                    found = Some(i);
+                    break;
                 }
             }
         }
+
+        let verb = match found {
+            Some(i) => words[i],
+            None => continue,
+        };
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, verb.start()),
+            end: byte_to_char_index(line, verb.end()),
+            issue_type: "时态一致性".to_string(),
+            message: format!(
+                "过去时间标记 '{}' 与现在时态动词 '{}'",
+                marker.as_str(),
+                verb.as_str()
+            ),
+            suggestion: "使用过去时态动词".to_string(),
+            rule_id: "tense_consistency".to_string(),
+            replacement: None,
+            confidence: 0.8,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
     }
 }
 
@@ -166,6 +311,11 @@ pub fn check_preposition_usage(line: &str, line_idx: usize, issues: &mut Vec<Tex
                 issue_type: "介词用法".to_string(),
                 message: format!("介词用法不当: {}", mat.as_str()),
                 suggestion: format!("建议使用: {}, {}", correct_form, explanation),
+                rule_id: "preposition_usage".to_string(),
+                replacement: None,
+                confidence: 1.0,
+                severity: "warning".to_string(),
+                language: String::new(),
             });
 
             // Stop if we've found too many issues
@@ -192,6 +342,11 @@ fn check_english_bracket_issues(line: &str, line_idx: usize, issues: &mut Vec<Te
             issue_type: "标点符号".to_string(),
             message: "空括号".to_string(),
             suggestion: "删除空括号或添加内容".to_string(),
+            rule_id: "chinese_punctuation".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "warning".to_string(),
+            language: String::new(),
         });
 
         if issues.len() >= MAX_ISSUES {
@@ -214,6 +369,11 @@ fn check_english_bracket_issues(line: &str, line_idx: usize, issues: &mut Vec<Te
                     issue_type: "标点符号".to_string(),
                     message: "括号不配对，缺少右括号".to_string(),
                     suggestion: "添加右括号 )".to_string(),
+                    rule_id: "chinese_punctuation".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "warning".to_string(),
+                    language: String::new(),
                 });
             }
         } else {
@@ -226,6 +386,11 @@ fn check_english_bracket_issues(line: &str, line_idx: usize, issues: &mut Vec<Te
                     issue_type: "标点符号".to_string(),
                     message: "括号不配对，缺少左括号".to_string(),
                     suggestion: "添加左括号 (".to_string(),
+                    rule_id: "chinese_punctuation".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "warning".to_string(),
+                    language: String::new(),
                 });
             }
         }
@@ -251,10 +416,2328 @@ fn check_english_bracket_issues(line: &str, line_idx: usize, issues: &mut Vec<Te
             issue_type: "标点符号".to_string(),
             message: "括号周围有多余空格".to_string(),
             suggestion: "使用单个空格或删除多余空格".to_string(),
+            rule_id: "chinese_punctuation".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// Check for lists of three or more items missing the Oxford comma
+pub fn check_oxford_comma(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // 只匹配单词构成的简单列举 "A, B, C and D"，降低误报；
+    // 第一个逗号前的词可能属于前面的从句（如 "He said, John and Mary"），
+    // 所以逗号之后至少还要有三项才算列举
+    let list_regex =
+        match Regex::new(r"\b([A-Za-z]+), (?:[A-Za-z]+, )+([A-Za-z]+) (and|or) [A-Za-z]+\b") {
+            Ok(re) => re,
+            Err(_) => return,
+        };
+
+    // 以这些词开头的从句或状语后面的逗号不是列举
+    let intro_words = [
+        "when",
+        "if",
+        "although",
+        "though",
+        "because",
+        "after",
+        "before",
+        "while",
+        "since",
+        "unless",
+        "once",
+        "however",
+        "therefore",
+        "moreover",
+        "furthermore",
+        "in",
+        "on",
+        "at",
+        "for",
+        "by",
+        "as",
+        "yes",
+        "no",
+        "well",
+        "first",
+        "second",
+        "finally",
+        "also",
+        "then",
+        "thus",
+        "hence",
+        "meanwhile",
+        "so",
+        "but",
+        "and",
+        "or",
+    ];
+
+    for caps in list_regex.captures_iter(line) {
+        let (first, last_item) = match (caps.get(1), caps.get(2)) {
+            (Some(first), Some(last_item)) => (first, last_item),
+            _ => continue,
+        };
+        let conjunction = &caps[3];
+
+        // 找到列举所在从句的开头，判断是否为引导性从句
+        let clause_start = line[..first.start()]
+            .rfind(['.', ',', ';', ':', '!', '?', '(', '"'])
+            .map(|pos| pos + 1)
+            .unwrap_or(0);
+        let clause = line[clause_start..first.end()].trim();
+
+        // 第一项必须跟在其他词之后（如 "bought apples, ..."），避免把句首状语当作列举项
+        if !clause.contains(' ') {
+            continue;
+        }
+        let clause_first_word = clause
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        if intro_words.contains(&clause_first_word.as_str()) {
+            continue;
+        }
+
+        let end = last_item.end() + 1 + conjunction.len();
+        let replacement = format!("{}, {}", last_item.as_str(), conjunction);
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, last_item.start()),
+            end: byte_to_char_index(line, end),
+            issue_type: "牛津逗号".to_string(),
+            message: "建议使用牛津逗号".to_string(),
+            suggestion: format!("建议修改为: '{}'", replacement),
+            rule_id: "oxford_comma".to_string(),
+            replacement: Some(replacement),
+            confidence: 1.0,
+            severity: "info".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// Check for "between" used with three or more items and "among" used with two
+pub fn check_between_among(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // (模式, 出错的介词所在的捕获组, 应替换为, 说明)
+    let patterns = [
+        // between A, B and C：三个以上并列项
+        (
+            r"(?i)\b(between) (?:the )?[A-Za-z]+, (?:[A-Za-z]+, )*[A-Za-z]+,? and [A-Za-z]+\b",
+            "among",
+            "三个及以上对象之间应使用 among",
+        ),
+        // between three options：数量明确为三个以上
+        (
+            r"(?i)\b(between) (?:the )?(?:three|four|five|six|seven|eight|nine|ten|several|multiple|many) [A-Za-z]+s\b",
+            "among",
+            "三个及以上对象之间应使用 among",
+        ),
+        // among the two / among both：只有两个对象
+        (
+            r"(?i)\b(among) (?:the )?(?:two|both)\b",
+            "between",
+            "两个对象之间应使用 between",
+        ),
+        // among A and B：只有两个单数并列项，后面不再跟其他并列项
+        (
+            r"\b([Aa]mong) ([A-Z][a-z]+|[a-z]+[^s\W]) and (?:[A-Z][a-z]+|[a-z]+[^s\W])\b(?:[^,A-Za-z]|$)",
+            "between",
+            "两个对象之间应使用 between",
+        ),
+    ];
+
+    for (pattern, correct_word, explanation) in patterns {
+        let regex = match Regex::new(pattern) {
+            Ok(re) => re,
+            Err(_) => continue, // Skip this pattern if regex creation fails
+        };
+
+        for caps in regex.captures_iter(line) {
+            let word = match caps.get(1) {
+                Some(word) => word,
+                None => continue,
+            };
+
+            let replacement = match_case(word.as_str(), correct_word);
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, word.start()),
+                end: byte_to_char_index(line, word.end()),
+                issue_type: "介词用法".to_string(),
+                message: format!("介词用法不当: {}", word.as_str()),
+                suggestion: format!("建议使用: {}, {}", replacement, explanation),
+                rule_id: "between_among".to_string(),
+                replacement: Some(replacement),
+                confidence: 1.0,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+// Check for likely affect/effect confusion based on simple context
+pub fn check_affect_effect(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // (模式, 应替换为)，第一个捕获组为可疑的词
+    let patterns = [
+        // 冠词、限定词或形容词之后应为名词 effect
+        (
+            r"(?i)\b(?:the|a|an|this|that|its|their|his|her|our|your|no|any|positive|negative|significant|side|adverse|great|major|minor|strong|little|long-term|greenhouse)\s+(affects?)\b",
+            "effect",
+        ),
+        // 情态动词、助动词或 to 之后应为动词 affect
+        (
+            r"(?i)\b(?:will|would|can|could|may|might|must|should|to|does|did|not|also|directly|greatly|seriously|negatively|positively|adversely)\s+(effect)\b",
+            "affect",
+        ),
+        // 代词主语之后的第三人称单数动词
+        (
+            r"(?i)\b(?:it|this|that|he|she|which|who)\s+(effects)\b",
+            "affects",
+        ),
+    ];
+
+    // "effect change" 意为"促成改变"，是正确用法
+    let effect_change_regex =
+        match Regex::new(r"(?i)^\s+(?:a |the )?(?:change|changes|reform|reforms)\b") {
+            Ok(re) => re,
+            Err(_) => return,
+        };
+
+    for (pattern, correct_word) in patterns {
+        let regex = match Regex::new(pattern) {
+            Ok(re) => re,
+            Err(_) => continue, // Skip this pattern if regex creation fails
+        };
+
+        for caps in regex.captures_iter(line) {
+            let word = match caps.get(1) {
+                Some(word) => word,
+                None => continue,
+            };
+
+            if word.as_str().eq_ignore_ascii_case("effect")
+                && effect_change_regex.is_match(&line[word.end()..])
+            {
+                continue;
+            }
+
+            let replacement = match_case(word.as_str(), correct_word);
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, word.start()),
+                end: byte_to_char_index(line, word.end()),
+                issue_type: "词语混用".to_string(),
+                message: format!("affect/effect 可能混用: '{}'", word.as_str()),
+                suggestion: format!(
+                    "建议修改为: '{}'，affect 多作动词（影响），effect 多作名词（效果、结果）",
+                    replacement
+                ),
+                rule_id: "affect_effect".to_string(),
+                replacement: Some(replacement),
+                confidence: 0.5,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+// Check colon and ending punctuation around Chinese quotations
+pub fn check_quote_colon_punct(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // (模式, 需要替换的捕获组, 替换文本, 问题描述)
+    // 替换文本中的 {0} 表示捕获组 2 的内容
+    let patterns = [
+        // 说“我来了” -> 说：“我来了”
+        (
+            r"(?:说|问|道|答|喊|叫道|写道|回答|表示|指出|强调)(“)[^”]{2,}[。！？]?”",
+            "：“",
+            "引语前缺少冒号",
+        ),
+        // 他说:“我来了” -> 他说：“我来了”
+        (r"\p{Han}(:\s*)“", "：", "中文引语前应使用全角冒号"),
+        // 他说：“我来了”。 -> 他说：“我来了。”
+        (
+            r"：“[^“”]*[^。！？…“”](”[。！？])",
+            "{0}”",
+            "完整引语的句末标点应放在引号内",
+        ),
+        // 他说：“我来了。”。 -> 他说：“我来了。”
+        (r"[。！？](”[。！？])", "”", "引号内外重复使用句末标点"),
+        // 他说：'我来了' -> 他说：“我来了”
+        (
+            r"[：:]\s*(['\x22]([^'\x22]*\p{Han}[^'\x22]*)['\x22])",
+            "“{0}”",
+            "中文引语应使用中文引号",
+        ),
+    ];
+
+    for (pattern, replacement_template, message) in patterns {
+        let regex = match Regex::new(pattern) {
+            Ok(re) => re,
+            Err(_) => continue, // Skip this pattern if regex creation fails
+        };
+
+        for caps in regex.captures_iter(line) {
+            let target = match caps.get(1) {
+                Some(target) => target,
+                None => continue,
+            };
+
+            let replacement = if replacement_template.contains("{0}") {
+                // 句末标点移入引号时取标点，替换引号时取引号内的内容
+                let inner = match caps.get(2) {
+                    Some(inner) => inner.as_str().to_string(),
+                    None => target.as_str().chars().skip(1).collect(),
+                };
+                replacement_template.replace("{0}", &inner)
+            } else {
+                replacement_template.to_string()
+            };
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, target.start()),
+                end: byte_to_char_index(line, target.end()),
+                issue_type: "引号标点".to_string(),
+                message: message.to_string(),
+                suggestion: format!("建议修改为: '{}'", replacement),
+                rule_id: "quote_punctuation".to_string(),
+                replacement: Some(replacement),
+                confidence: 1.0,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+// Check the format of "i.e." / "e.g." and hint at likely confusion
+pub fn check_ie_eg(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // 不带点的写法：ie / eg
+    let bare_regex = match Regex::new(r"\b(ie|eg)(?:,|\s)") {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+    // 带点的写法，允许缺少末尾的点、中间有空格或缺少后续逗号
+    let dotted_regex = match Regex::new(r"\b([ie])\.\s?([eg])\b(\.)?(,)?") {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    for caps in bare_regex.captures_iter(line) {
+        let word = match caps.get(1) {
+            Some(word) => word,
+            None => continue,
+        };
+        let canonical = if word.as_str() == "ie" {
+            "i.e."
+        } else {
+            "e.g."
+        };
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, word.start()),
+            end: byte_to_char_index(line, word.end()),
+            issue_type: "缩写格式".to_string(),
+            message: format!("缩写格式不规范: '{}'", word.as_str()),
+            suggestion: format!("建议修改为: '{}'", canonical),
+            rule_id: "ie_eg".to_string(),
+            replacement: Some(canonical.to_string()),
+            confidence: 1.0,
+            severity: "warning".to_string(),
+            language: String::new(),
         });
 
+        // Stop if we've found too many issues
         if issues.len() >= MAX_ISSUES {
             return;
         }
     }
+
+    for caps in dotted_regex.captures_iter(line) {
+        let (whole, first, second) = match (caps.get(0), caps.get(1), caps.get(2)) {
+            (Some(whole), Some(first), Some(second)) => (whole, first, second),
+            _ => continue,
+        };
+        let canonical = match (first.as_str(), second.as_str()) {
+            ("i", "e") => "i.e.",
+            ("e", "g") => "e.g.",
+            _ => continue,
+        };
+        let has_comma = caps.get(4).is_some();
+        let abbreviation_end = caps.get(3).map_or(second.end(), |dot| dot.end());
+        let abbreviation = &line[whole.start()..abbreviation_end];
+        let rest = &line[whole.end()..];
+
+        // 缺少末尾的点或中间多了空格
+        let format_issue = if abbreviation != canonical {
+            Some((
+                format!("缩写格式不规范: '{}'", abbreviation),
+                canonical.to_string(),
+            ))
+        } else if !has_comma
+            && rest.starts_with(' ')
+            && rest.trim_start().starts_with(char::is_alphanumeric)
+        {
+            // 后面直接跟内容时通常需要逗号
+            Some((
+                format!("'{}' 后通常需要逗号", canonical),
+                format!("{},", canonical),
+            ))
+        } else {
+            None
+        };
+
+        if let Some((message, replacement)) = format_issue {
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, whole.start()),
+                end: byte_to_char_index(line, abbreviation_end),
+                issue_type: "缩写格式".to_string(),
+                message,
+                suggestion: format!("建议修改为: '{}'", replacement),
+                rule_id: "ie_eg".to_string(),
+                replacement: Some(replacement),
+                confidence: 1.0,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+
+        // i.e. 表示"即"，后面跟多项列举或 etc. 时更可能想表达 e.g.
+        if canonical == "i.e." {
+            let clause_end = rest.find([')', '.', ';']).unwrap_or(rest.len());
+            let clause = &rest[..clause_end];
+            let is_list = clause.matches(',').count() >= 2 || clause.contains("etc");
+            if is_list {
+                issues.push(TextIssue {
+                    line_number: line_idx + 1,
+                    start: byte_to_char_index(line, whole.start()),
+                    end: byte_to_char_index(line, abbreviation_end),
+                    issue_type: "缩写混用".to_string(),
+                    message: "i.e. 后接多项列举，可能应为 e.g.".to_string(),
+                    suggestion: "i.e. 表示\"即\"（等同解释），e.g. 表示\"例如\"（举例）"
+                        .to_string(),
+                    rule_id: "ie_eg".to_string(),
+                    replacement: Some("e.g.".to_string()),
+                    confidence: 0.4,
+                    severity: "warning".to_string(),
+                    language: String::new(),
+                });
+
+                // Stop if we've found too many issues
+                if issues.len() >= MAX_ISSUES {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+// 常见的名词（单数形式），用于名词堆叠检测
+const COMMON_NOUNS: &[&str] = &[
+    "customer",
+    "survey",
+    "result",
+    "data",
+    "system",
+    "user",
+    "model",
+    "process",
+    "report",
+    "policy",
+    "design",
+    "quality",
+    "service",
+    "product",
+    "market",
+    "cost",
+    "risk",
+    "time",
+    "security",
+    "test",
+    "project",
+    "team",
+    "work",
+    "business",
+    "price",
+    "rate",
+    "level",
+    "value",
+    "file",
+    "network",
+    "software",
+    "hardware",
+    "program",
+    "database",
+    "school",
+    "student",
+    "employee",
+    "staff",
+    "health",
+    "care",
+    "energy",
+    "water",
+    "safety",
+    "control",
+    "support",
+    "research",
+    "study",
+    "budget",
+    "sales",
+    "growth",
+    "plan",
+    "strategy",
+    "platform",
+    "tool",
+    "task",
+    "goal",
+    "feedback",
+    "input",
+    "output",
+    "error",
+    "access",
+    "traffic",
+    "website",
+    "page",
+    "content",
+    "image",
+    "video",
+    "text",
+    "word",
+    "language",
+    "campaign",
+    "brand",
+    "interface",
+    "protocol",
+    "server",
+    "client",
+    "account",
+    "password",
+    "login",
+    "manager",
+    "developer",
+    "provider",
+    "vendor",
+    "computer",
+    "factor",
+    "sector",
+];
+
+// 不应被词尾规则误判为名词的常见词
+const NON_NOUN_WORDS: &[&str] = &[
+    "never", "ever", "other", "another", "over", "after", "under", "however", "whether", "rather",
+    "either", "neither", "further", "together", "whenever", "wherever", "better", "later", "eager",
+    "sure", "pure", "mature", "secure", "obscure", "very", "only",
+];
+
+// 带名词词尾、但通常用作动词的词
+const VERB_LIKE_WORDS: &[&str] = &[
+    "implement",
+    "supplement",
+    "complement",
+    "compliment",
+    "enhance",
+    "advance",
+    "influence",
+    "commence",
+    "envision",
+];
+
+// 基于常见名词表和名词词尾的简单名词判断
+fn looks_like_noun(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    if !lower.chars().all(|c| c.is_ascii_alphabetic()) || NON_NOUN_WORDS.contains(&lower.as_str()) {
+        return false;
+    }
+
+    let singular = lower.strip_suffix('s').unwrap_or(&lower);
+    if COMMON_NOUNS.contains(&lower.as_str()) || COMMON_NOUNS.contains(&singular) {
+        return true;
+    }
+
+    if VERB_LIKE_WORDS.contains(&lower.as_str()) || VERB_LIKE_WORDS.contains(&singular) {
+        return false;
+    }
+
+    // 只用名词特有的词尾；-er/-or/-age/-ure 等词尾也常见于动词（consider、manage、ensure），
+    // 这类名词需要在常见名词表中列出
+    let suffixes = [
+        "tion", "sion", "ment", "ness", "ity", "ance", "ence", "ship", "ism", "sis",
+    ];
+    suffixes.iter().any(|suffix| {
+        // 过短的词只靠词尾判断容易误报
+        (lower.ends_with(suffix) || singular.ends_with(suffix)) && lower.len() >= suffix.len() + 3
+    })
+}
+
+// Check for four or more nouns stacked in a row
+pub fn check_noun_stacking(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // 只统计以单个空格相连的单词，标点会打断序列
+    let word_regex = match Regex::new(r"[A-Za-z]+") {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    let mut run: Vec<(usize, usize)> = Vec::new();
+    let flush = |run: &mut Vec<(usize, usize)>, issues: &mut Vec<TextIssue>| {
+        if run.len() >= 4 {
+            let (start, end) = (run[0].0, run[run.len() - 1].1);
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, start),
+                end: byte_to_char_index(line, end),
+                issue_type: "名词堆叠".to_string(),
+                message: format!("名词堆叠过多（连续 {} 个名词）", run.len()),
+                suggestion: "名词堆叠过多，建议用介词短语拆分".to_string(),
+                rule_id: "noun_stacking".to_string(),
+                replacement: None,
+                confidence: 0.6,
+                severity: "info".to_string(),
+                language: String::new(),
+            });
+        }
+        run.clear();
+    };
+
+    for mat in word_regex.find_iter(line) {
+        let connected = match run.last() {
+            Some(&(_, prev_end)) => &line[prev_end..mat.start()] == " ",
+            None => true,
+        };
+        if !connected {
+            flush(&mut run, issues);
+        }
+
+        if looks_like_noun(mat.as_str()) {
+            run.push((mat.start(), mat.end()));
+        } else {
+            flush(&mut run, issues);
+        }
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+    flush(&mut run, issues);
+}
+
+// 逗号后出现"代词主语 + 谓语动词"时，可能是逗号连接了两个独立句
+const SPLICE_SUBJECTS: &str = "i|we|they|he|she|it|you";
+const SPLICE_VERBS: &str = "am|is|are|was|were|have|has|had|will|would|can|could|should|\
+                            must|did|do|does|went|got|made|took|came|saw|felt|left|found|want|\
+                            wanted|need|needed|decided|started|tried";
+// 从句引导词，带这些词的前半句不是独立句
+const SUBORDINATORS: &[&str] = &[
+    "if", "when", "whenever", "because", "although", "though", "while", "since", "after", "before",
+    "as", "unless", "until", "once", "whether", "where", "even",
+];
+// 转述动词后的逗号通常用于引出直接引语
+const REPORTING_VERBS: &[&str] = &[
+    "said", "says", "say", "think", "thought", "believe", "know", "hope", "guess", "mean",
+];
+
+// Check for comma splices: two independent clauses joined only by a comma
+pub fn check_comma_splice(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // 逗号后紧跟代词主语和常见谓语动词
+    let after_regex = match Regex::new(&format!(
+        r"(?i),\s+(?:{})\s+(?:{})\b",
+        SPLICE_SUBJECTS, SPLICE_VERBS
+    )) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+    // 前半句同样需要包含主语和谓语
+    let before_regex = match Regex::new(&format!(
+        r"(?i)\b(?:{})\s+(?:{})\b",
+        SPLICE_SUBJECTS, SPLICE_VERBS
+    )) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    for mat in after_regex.find_iter(line) {
+        let comma = mat.start();
+
+        // 前半句从上一个句末标点或逗号之后开始
+        let clause_start = line[..comma]
+            .rfind(['.', '!', '?', ';', ':', ','])
+            .map_or(0, |pos| pos + 1);
+        let clause = line[clause_start..comma].trim();
+        let words: Vec<String> = clause
+            .split_whitespace()
+            .map(|w| w.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase())
+            .collect();
+
+        let first_word = match words.first() {
+            Some(word) => word.as_str(),
+            None => continue,
+        };
+        let last_word = words.last().map_or("", |w| w.as_str());
+        if SUBORDINATORS.contains(&first_word)
+            || REPORTING_VERBS.contains(&last_word)
+            || !before_regex.is_match(clause)
+        {
+            continue;
+        }
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, comma),
+            end: byte_to_char_index(line, comma + 1),
+            issue_type: "逗号连接独立句".to_string(),
+            message: "逗号连接独立句".to_string(),
+            suggestion: "逗号连接独立句，建议用句号或分号".to_string(),
+            rule_id: "comma_splice".to_string(),
+            replacement: Some(";".to_string()),
+            confidence: 0.7,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// Check for "could of" / "should of" etc. used instead of "could have"
+pub fn check_modal_of(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let modal_regex = match Regex::new(r"(?i)\b(could|should|would|must|might)\s+(of)\b") {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    for caps in modal_regex.captures_iter(line) {
+        let (whole, modal, of) = match (caps.get(0), caps.get(1), caps.get(2)) {
+            (Some(whole), Some(modal), Some(of)) => (whole, modal, of),
+            _ => continue,
+        };
+        let corrected = format!("{} {}", modal.as_str(), match_case(of.as_str(), "have"));
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, whole.start()),
+            end: byte_to_char_index(line, whole.end()),
+            issue_type: "情态动词误用".to_string(),
+            message: format!("'{}' 为常见误用", whole.as_str()),
+            suggestion: format!("应为: '{}'", corrected),
+            rule_id: "modal_of".to_string(),
+            replacement: Some(corrected),
+            confidence: 1.0,
+            severity: "error".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 反身代词与对应的主语、宾格代词
+const REFLEXIVE_PRONOUNS: &[(&str, &[&str], &str)] = &[
+    ("myself", &["i"], "me"),
+    ("yourself", &["you"], "you"),
+    ("himself", &["he"], "him"),
+    ("herself", &["she"], "her"),
+    ("ourselves", &["we"], "us"),
+    ("yourselves", &["you"], "you"),
+    ("themselves", &["they"], "them"),
+];
+
+// Check for reflexive pronouns used as plain objects, e.g. "contact myself"
+pub fn check_reflexive_misuse(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // 只覆盖宾语几乎不可能是说话人自己的动词
+    let reflexive_regex = match Regex::new(
+        r"(?i)\b(contact|email|call|inform|notify|cc|tell|ask|send|give|join|thank|invite)\s+(myself|yourself|himself|herself|ourselves|yourselves|themselves)\b",
+    ) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    for caps in reflexive_regex.captures_iter(line) {
+        let (verb, pronoun) = match (caps.get(1), caps.get(2)) {
+            (Some(verb), Some(pronoun)) => (verb, pronoun),
+            _ => continue,
+        };
+        let lower = pronoun.as_str().to_lowercase();
+        let (subjects, object) = match REFLEXIVE_PRONOUNS.iter().find(|(r, _, _)| *r == lower) {
+            Some((_, subjects, object)) => (*subjects, *object),
+            None => continue,
+        };
+
+        // 同一句中前面出现对应主语时是真正的反身用法
+        let sentence_start = line[..verb.start()]
+            .rfind(['.', '!', '?'])
+            .map_or(0, |pos| pos + 1);
+        let before: Vec<String> = line[sentence_start..verb.start()]
+            .split(|c: char| !c.is_alphabetic())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_lowercase())
+            .collect();
+        if before.iter().any(|word| subjects.contains(&word.as_str())) {
+            continue;
+        }
+        // 祈使句的主语是 you，yourself 属于正常用法
+        if subjects.contains(&"you")
+            && before
+                .iter()
+                .all(|word| word == "please" || word == "kindly")
+        {
+            continue;
+        }
+
+        let replacement = match_case(pronoun.as_str(), object);
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, pronoun.start()),
+            end: byte_to_char_index(line, pronoun.end()),
+            issue_type: "反身代词误用".to_string(),
+            message: format!("反身代词误用: '{} {}'", verb.as_str(), pronoun.as_str()),
+            suggestion: format!("建议修改为: '{}'", replacement),
+            rule_id: "reflexive_pronoun".to_string(),
+            replacement: Some(replacement),
+            confidence: 0.8,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// Check for full-width Chinese punctuation inside English sentences
+pub fn check_fullwidth_punct_in_english(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    for (idx, &c) in chars.iter().enumerate() {
+        let halfwidth = match c {
+            '，' => ",",
+            '。' => ".",
+            '！' => "!",
+            '？' => "?",
+            '；' => ";",
+            '：' => ":",
+            '（' => "(",
+            '）' => ")",
+            '“' | '”' => "\"",
+            '‘' | '’' => "'",
+            _ => continue,
+        };
+
+        // 紧挨着汉字的全角标点属于行内的中文片段，不提示
+        let prev = chars[..idx].iter().rev().find(|c| !c.is_whitespace());
+        let next = chars[idx + 1..].iter().find(|c| !c.is_whitespace());
+        if prev.is_some_and(|c| is_chinese_char(*c)) || next.is_some_and(|c| is_chinese_char(*c)) {
+            continue;
+        }
+
+        // 英文句读标点后紧跟文字时补一个空格
+        let needs_space =
+            "，。！？；：".contains(c) && chars.get(idx + 1).is_some_and(|n| n.is_alphanumeric());
+        let replacement = if needs_space {
+            format!("{} ", halfwidth)
+        } else {
+            halfwidth.to_string()
+        };
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: idx,
+            end: idx + 1,
+            issue_type: "全角标点".to_string(),
+            message: format!("英文句子中使用了全角标点 '{}'", c),
+            suggestion: format!("建议修改为: '{}'", replacement),
+            rule_id: "fullwidth_punct".to_string(),
+            replacement: Some(replacement),
+            confidence: 1.0,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 全角字母和数字（Ａ-Ｚ、ａ-ｚ、０-９），全角标点由其它规则处理
+fn is_fullwidth_alnum(c: char) -> bool {
+    matches!(c, '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ')
+}
+
+// 全角 ASCII 字符与对应的半角字符相差 0xFEE0
+fn to_halfwidth(c: char) -> char {
+    char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)
+}
+
+// Check for full-width letters and digits, e.g. "ＡＢＣ１２３"
+pub fn check_fullwidth_alnum(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    let mut idx = 0;
+    while idx < chars.len() {
+        if !is_fullwidth_alnum(chars[idx]) {
+            idx += 1;
+            continue;
+        }
+
+        // 连续的全角字母数字合并为一处，便于一次替换
+        let start = idx;
+        while idx < chars.len() && is_fullwidth_alnum(chars[idx]) {
+            idx += 1;
+        }
+        let original: String = chars[start..idx].iter().collect();
+        let halfwidth: String = chars[start..idx].iter().map(|&c| to_halfwidth(c)).collect();
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start,
+            end: idx,
+            issue_type: "全角字母数字".to_string(),
+            message: format!("使用了全角字母或数字 '{}'", original),
+            suggestion: format!("建议转换为半角: '{}'", halfwidth),
+            rule_id: "fullwidth_alnum".to_string(),
+            replacement: Some(halfwidth),
+            confidence: 0.9,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 一个短句中默认允许的“的”字数量，超过时提示精简
+pub const DEFAULT_MAX_DE_PER_CLAUSE: usize = 2;
+
+// 短句之间的标点
+const CLAUSE_PUNCTUATION: &[char] = &[
+    '，', '。', '！', '？', '；', '：', '、', '（', '）', '“', '”', '…', ',', '.', '!', '?', ';',
+    ':',
+];
+
+// “的”是否属于 目的、标的、的确、的士 这类词语，而不是结构助词
+fn is_de_in_word(chars: &[char], idx: usize) -> bool {
+    let prev = idx.checked_sub(1).map(|i| chars[i]);
+    let next = chars.get(idx + 1).copied();
+    matches!(prev, Some('目' | '标' | '之')) || matches!(next, Some('确' | '士'))
+}
+
+// Check for stacked "的" in Chinese, e.g. "我的的书" or "我的朋友的同事的孩子的老师"
+pub fn check_excessive_de(
+    line: &str,
+    line_idx: usize,
+    issues: &mut Vec<TextIssue>,
+    max_per_clause: usize,
+) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+
+    // 相邻的“的的”，“目的的”、“的的确确”除外
+    for idx in 0..chars.len().saturating_sub(1) {
+        if chars[idx] != '的' || chars[idx + 1] != '的' {
+            continue;
+        }
+        if is_de_in_word(&chars, idx) || is_de_in_word(&chars, idx + 1) {
+            continue;
+        }
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: idx,
+            end: idx + 2,
+            issue_type: "的字堆叠".to_string(),
+            message: "连用两个“的”".to_string(),
+            suggestion: "删除多余的“的”".to_string(),
+            rule_id: "excessive_de".to_string(),
+            replacement: Some("的".to_string()),
+            confidence: 0.9,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+
+    // 两个标点之间的短句中“的”过多
+    let mut clause_start = 0;
+    for end in 0..=chars.len() {
+        if end < chars.len() && !CLAUSE_PUNCTUATION.contains(&chars[end]) {
+            continue;
+        }
+        let count = (clause_start..end)
+            .filter(|&idx| chars[idx] == '的' && !is_de_in_word(&chars, idx))
+            .count();
+        let start = clause_start;
+        clause_start = end + 1;
+        if count <= max_per_clause {
+            continue;
+        }
+
+        // 多个定语有时难以避免，只作提示
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start,
+            end,
+            issue_type: "的字堆叠".to_string(),
+            message: format!("一个短句中用了 {} 个“的”，读起来拖沓", count),
+            suggestion: "考虑删去部分“的”，或把长定语拆成短句".to_string(),
+            rule_id: "excessive_de".to_string(),
+            replacement: None,
+            confidence: 0.5,
+            severity: "info".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 从属连词和关系代词，用于估计从句数量
+const CLAUSE_MARKERS: &[&str] = &[
+    "which", "that", "who", "whom", "whose", "where", "when", "because", "although", "though",
+    "while", "whereas", "if", "unless", "since", "until", "whether",
+];
+
+// 一句中默认允许的从句标志词数量
+pub const DEFAULT_MAX_CLAUSE_MARKERS: usize = 3;
+
+// Check for sentences with too many nested subordinate clauses
+pub fn check_clause_nesting(
+    line: &str,
+    line_idx: usize,
+    issues: &mut Vec<TextIssue>,
+    max_markers: usize,
+) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let mut sentence_start = 0;
+    for (pos, c) in line
+        .char_indices()
+        .chain(std::iter::once((line.len(), '.')))
+    {
+        if !matches!(c, '.' | '!' | '?' | ';') {
+            continue;
+        }
+        let sentence = &line[sentence_start..pos];
+        let sentence_offset = sentence_start;
+        sentence_start = (pos + c.len_utf8()).min(line.len());
+
+        let markers = sentence
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .filter(|word| CLAUSE_MARKERS.contains(&word.to_lowercase().as_str()))
+            .count();
+        if markers <= max_markers {
+            continue;
+        }
+
+        // 定位到整句，去掉首尾空白
+        let trimmed_start = sentence_offset + (sentence.len() - sentence.trim_start().len());
+        let trimmed_end = sentence_offset + sentence.trim_end().len();
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, trimmed_start),
+            end: byte_to_char_index(line, trimmed_end),
+            issue_type: "从句嵌套".to_string(),
+            message: format!("从句嵌套过深（{} 个从句标志词）", markers),
+            suggestion: "从句嵌套过深，建议拆分".to_string(),
+            rule_id: "clause_nesting".to_string(),
+            replacement: None,
+            confidence: 0.7,
+            severity: "info".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 行末是句末标点（后面可以跟右引号或右括号）
+fn ends_sentence(line: &str) -> bool {
+    line.trim_end_matches(['"', '\'', ')', '”', '’', '）'])
+        .ends_with(['.', '?', '!', '。', '？', '！'])
+}
+
+// Check for English sentences that start with a lowercase letter
+// prev_line 是原文中的上一行，用来判断行首是新句子还是上一行折行的延续
+pub fn check_sentence_capitalization(
+    line: &str,
+    line_idx: usize,
+    prev_line: Option<&str>,
+    issues: &mut Vec<TextIssue>,
+) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // 网址、文件名、数字中的句点不算句末；遮盖时按原字符的字节长度补空格，字节位置与原文一致
+    let masked: String = line
+        .chars()
+        .zip(crate::strip_technical_tokens(line).chars())
+        .map(|(original, stripped)| {
+            if stripped == ' ' && original != ' ' {
+                " ".repeat(original.len_utf8())
+            } else {
+                original.to_string()
+            }
+        })
+        .collect();
+    let word_re = match Regex::new(r"^[a-z][A-Za-z'-]*") {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    let mut candidates: Vec<usize> = Vec::new();
+
+    // 行首：上一行已结束时才是新句子；列表项、标题和代码行不检查
+    let trimmed = line.trim();
+    let starts_sentence = match prev_line.map(str::trim) {
+        None | Some("") => true,
+        Some(prev) => ends_sentence(prev) || crate::structure::is_heading_line(prev),
+    };
+    if starts_sentence
+        && !is_list_item(trimmed)
+        && !crate::structure::is_heading_line(line)
+        && !looks_like_code(line)
+    {
+        candidates.push(line.len() - line.trim_start().len());
+    }
+
+    // 行内：句末标点后接空白再接小写字母的位置
+    for (pos, c) in masked.char_indices() {
+        if !matches!(c, '.' | '?' | '!') {
+            continue;
+        }
+        // 省略号和 e.g. / Dr. 等缩写后的小写不算
+        if c == '.' && (masked[..pos].ends_with('.') || is_abbreviation_period(&masked, pos)) {
+            continue;
+        }
+
+        let after = &masked[pos + 1..];
+        let rest = after.trim_start();
+        let word_start = masked.len() - rest.len();
+        // 句末标点与单词之间隔着被遮盖的网址或代码时不算
+        if rest.len() == after.len() || !line[pos + 1..word_start].trim().is_empty() {
+            continue;
+        }
+        candidates.push(word_start);
+    }
+
+    for pos in candidates {
+        let word = match word_re.find(&masked[pos..]) {
+            Some(word) => word.as_str(),
+            None => continue,
+        };
+        // e.g. 等缩写、a) 这类列表编号，以及 iPhone 这类特殊大小写的名称
+        let next = masked[pos + word.len()..].chars().next();
+        if matches!(next, Some('.') | Some(')')) || word.chars().skip(1).any(char::is_uppercase) {
+            continue;
+        }
+        // 单词紧接被遮盖的内容时，说明它是网址或路径的一部分
+        if next == Some(' ') && !line[pos + word.len()..].starts_with(' ') {
+            continue;
+        }
+        let replacement = capitalize_first(word);
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, pos),
+            end: byte_to_char_index(line, pos + word.len()),
+            issue_type: "大小写".to_string(),
+            message: format!("句首应大写: {}", word),
+            suggestion: format!("建议修改为: '{}'", replacement),
+            rule_id: "sentence_capitalization".to_string(),
+            replacement: Some(replacement),
+            confidence: 0.9,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 常见名词及其适用的量词，较长的名词（如“书桌”）单独列出，避免按“书”误判
+const MEASURE_WORDS: &[(&str, &[&str])] = &[
+    ("人", &["个", "位", "名", "口", "群", "批"]),
+    ("人才", &["位", "名", "批"]),
+    ("人员", &["名", "位", "个", "批"]),
+    ("人物", &["位", "个", "名"]),
+    ("老师", &["位", "名", "个"]),
+    ("医生", &["位", "名", "个"]),
+    ("专家", &["位", "名", "个", "批"]),
+    ("学生", &["名", "个", "位", "批"]),
+    ("客人", &["位", "个", "批"]),
+    ("书", &["本", "册", "部", "套"]),
+    ("书桌", &["张", "个"]),
+    ("书架", &["个", "排"]),
+    ("书包", &["个", "只"]),
+    ("杂志", &["本", "期", "份"]),
+    ("报纸", &["份", "张"]),
+    ("车", &["辆", "台", "部"]),
+    ("汽车", &["辆", "台", "部"]),
+    ("自行车", &["辆"]),
+    ("车站", &["个", "座"]),
+    ("飞机", &["架"]),
+    ("船", &["艘", "条", "只"]),
+    ("马", &["匹"]),
+    ("马路", &["条"]),
+    ("马车", &["辆"]),
+    ("牛", &["头", "只"]),
+    ("牛奶", &["杯", "瓶", "盒", "袋"]),
+    ("猪", &["头", "只"]),
+    ("羊", &["只", "头", "群"]),
+    ("羊毛衫", &["件"]),
+    ("鸡", &["只"]),
+    ("鸟", &["只", "群"]),
+    ("猫", &["只"]),
+    ("狗", &["条", "只"]),
+    ("鱼", &["条", "尾"]),
+    ("鱼缸", &["个", "只"]),
+    ("蛇", &["条"]),
+    ("树", &["棵", "株", "排"]),
+    ("树叶", &["片"]),
+    ("花", &["朵", "束", "枝", "支", "盆"]),
+    ("花园", &["个", "座"]),
+    ("花瓶", &["个", "只"]),
+    ("房子", &["栋", "座", "间", "套", "所"]),
+    ("学校", &["所", "个"]),
+    ("桥", &["座"]),
+    ("山", &["座"]),
+    ("河", &["条"]),
+    ("信", &["封"]),
+    ("信封", &["个", "只"]),
+    ("诗", &["首"]),
+    ("歌", &["首", "支"]),
+    ("文章", &["篇"]),
+    ("论文", &["篇"]),
+    ("电脑", &["台", "部"]),
+    ("电视", &["台", "部"]),
+    ("手机", &["部", "台", "个", "只"]),
+    ("桌子", &["张"]),
+    ("椅子", &["把"]),
+    ("床", &["张"]),
+    ("笔", &["支", "枝"]),
+    ("衣服", &["件", "套", "身"]),
+    ("鞋", &["双", "只"]),
+];
+
+// 参与判断的量词，不在此列的（如“种”“些”）不作检查
+const KNOWN_MEASURE_WORDS: &[&str] = &[
+    "个", "位", "名", "口", "只", "头", "匹", "条", "尾", "本", "册", "部", "辆", "台", "架", "艘",
+    "件", "张", "把", "棵", "株", "朵", "座", "所", "栋", "间", "封", "首", "篇", "支", "枝", "根",
+    "片", "双", "颗", "粒",
+];
+
+// Check for measure words that do not fit the following noun, e.g. "三只人"
+pub fn check_measure_words(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let mut nouns: Vec<&str> = MEASURE_WORDS.iter().map(|(noun, _)| *noun).collect();
+    // 长的名词优先匹配
+    nouns.sort_by_key(|noun| std::cmp::Reverse(noun.chars().count()));
+    let pattern = format!(
+        r"([0-9０-９]+|[一二两三四五六七八九十百千万几]+)({})({})",
+        KNOWN_MEASURE_WORDS.join("|"),
+        nouns.join("|")
+    );
+    let regex = match Regex::new(&pattern) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    for caps in regex.captures_iter(line) {
+        let (whole, number, measure, noun) =
+            match (caps.get(0), caps.get(1), caps.get(2), caps.get(3)) {
+                (Some(whole), Some(number), Some(measure), Some(noun)) => {
+                    (whole, number, measure, noun)
+                }
+                _ => continue,
+            };
+        let allowed = match MEASURE_WORDS
+            .iter()
+            .find(|(name, _)| *name == noun.as_str())
+        {
+            Some((_, allowed)) => *allowed,
+            None => continue,
+        };
+        if allowed.contains(&measure.as_str()) {
+            continue;
+        }
+        // 单字名词后面还有汉字时多半是复合词，如“一个鸡蛋”“一张车票”；“人”除外
+        let next_is_chinese = line[whole.end()..]
+            .chars()
+            .next()
+            .is_some_and(is_chinese_char);
+        if noun.as_str().chars().count() == 1 && noun.as_str() != "人" && next_is_chinese {
+            continue;
+        }
+        // “一个人才能完成”中的“人才”不是名词
+        if noun.as_str() == "人才"
+            && line[whole.end()..]
+                .starts_with(['能', '会', '可', '是', '有', '知', '行', '肯', '敢'])
+        {
+            continue;
+        }
+
+        let replacement = format!("{}{}{}", number.as_str(), allowed[0], noun.as_str());
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, whole.start()),
+            end: byte_to_char_index(line, whole.end()),
+            issue_type: "量词搭配".to_string(),
+            message: format!("量词搭配不当: '{}'", whole.as_str()),
+            suggestion: format!(
+                "建议修改为: '{}'（'{}'常用的量词：{}）",
+                replacement,
+                noun.as_str(),
+                allowed.join("、")
+            ),
+            rule_id: "measure_words".to_string(),
+            replacement: Some(replacement),
+            confidence: 0.7,
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// 应连写的短语：(拆开的写法, 正确写法)
+const SHOULD_MERGE: &[(&str, &str)] = &[
+    ("can not", "cannot"),
+    ("along side", "alongside"),
+    ("with out", "without"),
+    ("through out", "throughout"),
+    ("there fore", "therefore"),
+    ("further more", "furthermore"),
+    ("more over", "moreover"),
+    ("never the less", "nevertheless"),
+    ("none the less", "nonetheless"),
+    ("not with standing", "notwithstanding"),
+    ("it self", "itself"),
+    ("my self", "myself"),
+    ("your self", "yourself"),
+    ("him self", "himself"),
+    ("her self", "herself"),
+    ("them selves", "themselves"),
+    ("frame work", "framework"),
+    ("data base", "database"),
+    ("work flow", "workflow"),
+    ("web site", "website"),
+];
+
+// 应拆开的写法：(连写的错误写法, 正确写法)
+const SHOULD_SPLIT: &[(&str, &str)] = &[
+    ("aswell", "as well"),
+    ("alot", "a lot"),
+    ("infact", "in fact"),
+    ("eachother", "each other"),
+    ("atleast", "at least"),
+    ("inorder", "in order"),
+    ("ofcourse", "of course"),
+    ("incase", "in case"),
+    ("inspite", "in spite"),
+    ("noone", "no one"),
+    ("thankyou", "thank you"),
+    ("upto", "up to"),
+    ("forexample", "for example"),
+];
+
+// Check for English words that are wrongly split ("can not") or joined ("aswell")
+pub fn check_word_boundaries(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    // (正则, 正确写法, 是否为应连写的短语)
+    let mut patterns = Vec::new();
+    for (split, merged) in SHOULD_MERGE {
+        let words: Vec<String> = split.split(' ').map(regex::escape).collect();
+        patterns.push((format!(r"(?i)\b{}\b", words.join(r"\s+")), *merged, true));
+    }
+    for (joined, correct) in SHOULD_SPLIT {
+        patterns.push((
+            format!(r"(?i)\b{}\b", regex::escape(joined)),
+            *correct,
+            false,
+        ));
+    }
+
+    for (pattern, correct, is_merge) in patterns {
+        let regex = match Regex::new(&pattern) {
+            Ok(re) => re,
+            Err(_) => continue,
+        };
+
+        for mat in regex.find_iter(line) {
+            // "can not only ... but also" 中的 not 属于 not only，不应连写
+            if correct == "cannot" {
+                let next_word = line[mat.end()..].split_whitespace().next().unwrap_or("");
+                if next_word.eq_ignore_ascii_case("only") {
+                    continue;
+                }
+            }
+
+            let replacement = match_case(mat.as_str(), correct);
+            let message = if is_merge {
+                format!("'{}' 应连写", mat.as_str())
+            } else {
+                format!("'{}' 应拆开写", mat.as_str())
+            };
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, mat.start()),
+                end: byte_to_char_index(line, mat.end()),
+                issue_type: "单词拆分连写".to_string(),
+                message,
+                suggestion: format!("建议修改为: '{}'", replacement),
+                rule_id: "word_boundaries".to_string(),
+                replacement: Some(replacement),
+                confidence: 0.9,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+// 前面不应有空格的标点
+const NO_SPACE_BEFORE: &[char] = &[',', ';', '，', '；', '。', '、'];
+
+fn is_ascii_letter(c: char) -> bool {
+    c.is_ascii_alphabetic()
+}
+
+// 中文与英文字母是否相邻（顺序不限）
+fn is_cjk_latin_boundary(a: char, b: char) -> bool {
+    (is_chinese_char(a) && is_ascii_letter(b)) || (is_ascii_letter(a) && is_chinese_char(b))
+}
+
+fn whitespace_issue(
+    line_idx: usize,
+    start: usize,
+    end: usize,
+    message: &str,
+    suggestion: &str,
+    replacement: String,
+    confidence: f32,
+) -> TextIssue {
+    TextIssue {
+        line_number: line_idx + 1,
+        start,
+        end,
+        issue_type: "空格".to_string(),
+        message: message.to_string(),
+        suggestion: suggestion.to_string(),
+        rule_id: "extra_whitespace".to_string(),
+        replacement: Some(replacement),
+        confidence,
+        severity: "info".to_string(),
+        language: String::new(),
+    }
+}
+
+// Check for extra or missing spaces
+// cjk_latin_space：中英文之间 Some(true) 要求加空格，Some(false) 要求不加，None 不检查
+pub fn check_extra_whitespace(
+    line: &str,
+    line_idx: usize,
+    issues: &mut Vec<TextIssue>,
+    cjk_latin_space: Option<bool>,
+) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    // 网址、路径等中的写法不检查，遮盖后字符数不变
+    let masked: Vec<char> = crate::strip_technical_tokens(line).chars().collect();
+    let mut found = Vec::new();
+
+    let mut idx = 0;
+    while idx < chars.len() {
+        if !matches!(chars[idx], ' ' | '\t') {
+            idx += 1;
+            continue;
+        }
+        let start = idx;
+        while idx < chars.len() && matches!(chars[idx], ' ' | '\t') {
+            idx += 1;
+        }
+        // 行首缩进和行尾空白不算
+        if start == 0 || idx == chars.len() {
+            continue;
+        }
+
+        let (prev, next) = (chars[start - 1], chars[idx]);
+        if NO_SPACE_BEFORE.contains(&next) {
+            found.push(whitespace_issue(
+                line_idx,
+                start,
+                idx,
+                &format!("'{}' 前有多余空格", next),
+                "删除标点前的空格",
+                String::new(),
+                0.9,
+            ));
+        } else if cjk_latin_space == Some(false) && is_cjk_latin_boundary(prev, next) {
+            found.push(whitespace_issue(
+                line_idx,
+                start,
+                idx,
+                "中英文之间不加空格",
+                "删除中英文之间的空格",
+                String::new(),
+                0.8,
+            ));
+        } else if idx - start >= 2 {
+            // 括号周围的多余空格由标点检查报告
+            if next == '(' || prev == ')' {
+                continue;
+            }
+            let message = if chars[start..idx].contains(&'\t') {
+                "单词之间有制表符或连续空格"
+            } else {
+                "连续多个空格"
+            };
+            found.push(whitespace_issue(
+                line_idx,
+                start,
+                idx,
+                message,
+                "使用单个空格",
+                " ".to_string(),
+                0.9,
+            ));
+        }
+    }
+
+    for idx in 1..masked.len().saturating_sub(1) {
+        let (prev, c, next) = (masked[idx - 1], masked[idx], masked[idx + 1]);
+
+        // 英文句号后直接跟下一句，如 "the end.Next"
+        // 前一个词至少两个字母，排除 e.g.、U.S. 这类缩写
+        if c == '.'
+            && prev.is_ascii_lowercase()
+            && next.is_ascii_uppercase()
+            && idx >= 2
+            && is_ascii_letter(masked[idx - 2])
+        {
+            found.push(whitespace_issue(
+                line_idx,
+                idx,
+                idx + 1,
+                "句号后缺少空格",
+                "在句号后添加一个空格",
+                ". ".to_string(),
+                0.7,
+            ));
+        }
+    }
+
+    if cjk_latin_space == Some(true) {
+        for idx in 0..masked.len().saturating_sub(1) {
+            let (a, b) = (masked[idx], masked[idx + 1]);
+            if is_cjk_latin_boundary(a, b) {
+                found.push(whitespace_issue(
+                    line_idx,
+                    idx,
+                    idx + 2,
+                    "中英文之间缺少空格",
+                    "在中文与英文之间添加一个空格",
+                    format!("{} {}", a, b),
+                    0.8,
+                ));
+            }
+        }
+    }
+
+    found.sort_by_key(|issue| issue.start);
+    for issue in found {
+        issues.push(issue);
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+/// 错别字表中的一条：排除上下文中出现的匹配是正确用法（如“自己经常”中的“己经”）
+struct ChineseTypo {
+    wrong: String,
+    correct: String,
+    explanation: String,
+    exclusions: Vec<String>,
+}
+
+// 中文错别字表，只加载一次
+static CHINESE_TYPOS: OnceLock<Vec<ChineseTypo>> = OnceLock::new();
+
+// 随程序打包的错别字表，找不到外部文件时使用，两处数据保持同一来源
+const BUILTIN_CHINESE_TYPOS: &str = include_str!("../../typos.tsv");
+
+// 没有给出说明时的默认说明
+const DEFAULT_TYPO_EXPLANATION: &str = "同音字或形近字误用";
+
+// 有排除上下文的条目可能出现在正确的词语中，只提示、不自动替换
+const AMBIGUOUS_TYPO_CONFIDENCE: f32 = 0.6;
+
+// 解析错别字表：每行 "错误写法<Tab>正确写法[<Tab>说明[<Tab>排除上下文]]"，忽略空行和 # 注释
+// 排除上下文用 | 分隔，每项都必须包含错误写法
+fn parse_chinese_typos(content: &str) -> Vec<ChineseTypo> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split('\t').map(str::trim);
+            let wrong = fields.next().filter(|field| !field.is_empty())?;
+            let correct = fields.next().filter(|field| !field.is_empty())?;
+            let explanation = fields
+                .next()
+                .filter(|field| !field.is_empty())
+                .unwrap_or(DEFAULT_TYPO_EXPLANATION);
+            let exclusions = fields
+                .next()
+                .unwrap_or("")
+                .split('|')
+                .map(str::trim)
+                .filter(|context| context.contains(wrong))
+                .map(str::to_string)
+                .collect();
+            Some(ChineseTypo {
+                wrong: wrong.to_string(),
+                correct: correct.to_string(),
+                explanation: explanation.to_string(),
+                exclusions,
+            })
+        })
+        .collect()
+}
+
+// 加载错别字表文件
+fn load_chinese_typos() -> &'static Vec<ChineseTypo> {
+    CHINESE_TYPOS.get_or_init(|| {
+        let typos = crate::dictionary::find_resource_file("typos.tsv", "错别字表", |path| {
+            let typos = parse_chinese_typos(&std::fs::read_to_string(path).ok()?);
+            (!typos.is_empty()).then_some(typos)
+        });
+        if let Some(typos) = typos {
+            return typos;
+        }
+
+        eprintln!("未找到错别字表文件，使用内置的错别字列表");
+        parse_chinese_typos(BUILTIN_CHINESE_TYPOS)
+    })
+}
+
+// 判断 [start, end) 处的匹配是否落在某个排除上下文中
+fn in_excluded_context(line: &str, start: usize, end: usize, exclusions: &[String]) -> bool {
+    exclusions.iter().any(|context| {
+        line.match_indices(context.as_str())
+            .any(|(pos, _)| pos <= start && pos + context.len() >= end)
+    })
+}
+
+// Check Chinese homophone / look-alike typos against a fixed phrase table ("做为" -> "作为")
+pub fn check_chinese_typos(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    for typo in load_chinese_typos() {
+        for (pos, _) in line.match_indices(typo.wrong.as_str()) {
+            let end = pos + typo.wrong.len();
+            if in_excluded_context(line, pos, end, &typo.exclusions) {
+                continue;
+            }
+
+            // 可能出现在正确词语中的条目只给出提示，由用户确认
+            let ambiguous = !typo.exclusions.is_empty();
+            let (suggestion, replacement, confidence, severity) = if ambiguous {
+                (
+                    format!(
+                        "可能应为 '{}'，{}，请结合上下文确认",
+                        typo.correct, typo.explanation
+                    ),
+                    None,
+                    AMBIGUOUS_TYPO_CONFIDENCE,
+                    "warning",
+                )
+            } else {
+                (
+                    format!("建议修改为: '{}'，{}", typo.correct, typo.explanation),
+                    Some(typo.correct.clone()),
+                    0.9,
+                    "error",
+                )
+            };
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, pos),
+                end: byte_to_char_index(line, end),
+                issue_type: "错别字".to_string(),
+                message: format!("错别字: '{}'", typo.wrong),
+                suggestion,
+                rule_id: "chinese_typos".to_string(),
+                replacement,
+                confidence,
+                severity: severity.to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+// 英文完整句至少包含的单词数，更短的行多为标签或短语
+const MIN_SENTENCE_WORDS: usize = 6;
+// 中文长句至少包含的汉字数
+const MIN_CHINESE_SENTENCE_CHARS: usize = 20;
+// 英文助动词、系动词和论文中常见的谓语动词，出现时认为行内有谓语
+const COMMON_VERBS: &[&str] = &[
+    "is",
+    "are",
+    "was",
+    "were",
+    "be",
+    "been",
+    "has",
+    "have",
+    "had",
+    "do",
+    "does",
+    "did",
+    "will",
+    "would",
+    "can",
+    "could",
+    "should",
+    "may",
+    "might",
+    "must",
+    "shall",
+    "show",
+    "shows",
+    "use",
+    "uses",
+    "make",
+    "makes",
+    "provide",
+    "provides",
+    "include",
+    "includes",
+    "suggest",
+    "suggests",
+    "indicate",
+    "indicates",
+    "present",
+    "presents",
+    "propose",
+    "proposes",
+    "remain",
+    "remains",
+];
+// 以这些词结尾的行通常是手动换行的半句，不是缺少标点
+const CONTINUATION_WORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "but", "of", "to", "in", "on", "at", "by", "for", "with",
+    "from", "as", "that", "which", "who", "whose", "than", "if", "because", "is", "are", "was",
+    "were",
+];
+
+// 列表项、引用和表格行：项目符号、"1." "a)" "(1)" "（1）" "①" 等编号开头
+fn is_list_item(trimmed: &str) -> bool {
+    if trimmed.starts_with(['-', '*', '+', '•', '·', '>', '|']) {
+        return true;
+    }
+    Regex::new(r"^(?:\d+|[A-Za-z])[.)、]\s|^[(（]\d+[)）]|^[①-⑳]")
+        .map(|re| re.is_match(trimmed))
+        .unwrap_or(false)
+}
+
+// 代码行：英文行缩进四个空格或制表符，或含有常见的代码符号
+fn looks_like_code(line: &str) -> bool {
+    let indented =
+        (line.starts_with("    ") || line.starts_with('\t')) && !line.chars().any(is_chinese_char);
+    indented
+        || ["{", "}", "=>", "==", "//", "/*", "</", "::", "`", "();"]
+            .iter()
+            .any(|token| line.contains(token))
+}
+
+// Check for complete-looking sentences whose line does not end with terminal punctuation
+// 只能看到单行，手动换行的段落可能误报，因此置信度较低
+pub fn check_missing_end_punctuation(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let trimmed = line.trim();
+    if trimmed.is_empty()
+        || crate::structure::is_heading_line(line)
+        || is_list_item(trimmed)
+        || looks_like_code(line)
+    {
+        return;
+    }
+    let last = match trimmed.chars().next_back() {
+        Some(c) => c,
+        None => return,
+    };
+
+    let (mark, message) = if trimmed.chars().any(is_chinese_char) {
+        // 以汉字结尾、含逗号的长句才视为缺少句号
+        let chinese_chars = trimmed.chars().filter(|c| is_chinese_char(*c)).count();
+        if !is_chinese_char(last)
+            || chinese_chars < MIN_CHINESE_SENTENCE_CHARS
+            || !trimmed.contains('，')
+        {
+            return;
+        }
+        ('。', "长句末尾缺少句号")
+    } else {
+        // 首字母大写、单词足够多、含有谓语且以字母或数字结尾
+        if !last.is_ascii_alphanumeric() || !trimmed.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return;
+        }
+        let words: Vec<String> = trimmed
+            .split_whitespace()
+            .map(|word| {
+                word.trim_matches(|c: char| !c.is_alphanumeric())
+                    .to_lowercase()
+            })
+            .collect();
+        let has_verb = words.iter().any(|word| {
+            COMMON_VERBS.contains(&word.as_str()) || (word.len() > 4 && word.ends_with("ed"))
+        });
+        let continues = words
+            .last()
+            .is_some_and(|word| CONTINUATION_WORDS.contains(&word.as_str()));
+        if words.len() < MIN_SENTENCE_WORDS || !has_verb || continues {
+            return;
+        }
+        ('.', "句子末尾缺少结束标点")
+    };
+
+    let end = line.trim_end().chars().count();
+    issues.push(TextIssue {
+        line_number: line_idx + 1,
+        start: end - 1,
+        end,
+        issue_type: "句末标点缺失".to_string(),
+        message: message.to_string(),
+        suggestion: format!("建议在句末添加 '{}'", mark),
+        rule_id: "missing_end_punctuation".to_string(),
+        replacement: Some(format!("{}{}", last, mark)),
+        confidence: 0.5,
+        severity: "info".to_string(),
+        language: String::new(),
+    });
+}
+
+// 搭配检查用到的动词变形：原形、第三人称单数、过去式、现在分词、过去分词
+const COLLOCATION_VERB_FORMS: &[[&str; 5]] = &[
+    ["make", "makes", "made", "making", "made"],
+    ["do", "does", "did", "doing", "done"],
+    ["take", "takes", "took", "taking", "taken"],
+    ["have", "has", "had", "having", "had"],
+    [
+        "conduct",
+        "conducts",
+        "conducted",
+        "conducting",
+        "conducted",
+    ],
+    ["give", "gives", "gave", "giving", "given"],
+    ["pay", "pays", "paid", "paying", "paid"],
+    ["draw", "draws", "drew", "drawing", "drawn"],
+];
+
+// 英文常见错误搭配：(错误动词, 宾语正则, 正确动词, 正确宾语)
+const COLLOCATIONS: &[(&str, &str, &str, &str)] = &[
+    ("make", r"(?:a\s+)?research", "conduct", "research"),
+    ("make", r"an\s+experiment", "conduct", "an experiment"),
+    ("make", r"experiments", "conduct", "experiments"),
+    (
+        "make",
+        r"(?:my|your|his|her|our|their|the)?\s*homework",
+        "do",
+        "homework",
+    ),
+    ("make", r"a\s+favou?r", "do", "a favor"),
+    ("make", r"damage", "do", "damage"),
+    ("make", r"harm", "do", "harm"),
+    ("make", r"a\s+photo", "take", "a photo"),
+    ("make", r"a\s+look", "take", "a look"),
+    ("make", r"a\s+break", "take", "a break"),
+    ("make", r"a\s+walk", "take", "a walk"),
+    ("make", r"a\s+rest", "take", "a rest"),
+    ("make", r"a\s+conclusion", "draw", "a conclusion"),
+    ("make", r"a\s+party", "have", "a party"),
+    ("make", r"a\s+dream", "have", "a dream"),
+    ("make", r"attention", "pay", "attention"),
+    ("do", r"a\s+mistake", "make", "a mistake"),
+    ("do", r"mistakes", "make", "mistakes"),
+    ("do", r"a\s+decision", "make", "a decision"),
+    ("do", r"decisions", "make", "decisions"),
+    ("do", r"progress", "make", "progress"),
+    ("do", r"an\s+effort", "make", "an effort"),
+    ("do", r"efforts", "make", "efforts"),
+    ("do", r"a\s+choice", "make", "a choice"),
+    ("do", r"a\s+suggestion", "make", "a suggestion"),
+    ("do", r"a\s+contribution", "make", "a contribution"),
+    ("do", r"a\s+speech", "give", "a speech"),
+    ("do", r"a\s+look", "take", "a look"),
+    ("do", r"attention", "pay", "attention"),
+    (
+        "do",
+        r"a\s+(?:conversation|discussion)",
+        "have",
+        "a discussion",
+    ),
+    ("take", r"a\s+mistake", "make", "a mistake"),
+    ("take", r"progress", "make", "progress"),
+];
+
+// make 的使役用法 "make research easier" 不是搭配错误：宾语后接形容词或比较级
+fn is_causative_complement(word: &str) -> bool {
+    let word = word.to_lowercase();
+    matches!(word.as_str(), "more" | "less" | "possible" | "impossible")
+        || ["er", "able", "ible", "ful", "ive", "ous"]
+            .iter()
+            .any(|suffix| word.len() > suffix.len() + 2 && word.ends_with(suffix))
+}
+
+// 查找动词的全部变形
+fn verb_forms(verb: &str) -> Option<&'static [&'static str; 5]> {
+    COLLOCATION_VERB_FORMS.iter().find(|forms| forms[0] == verb)
+}
+
+// Check for common English verb-noun collocation errors ("make research", "do a mistake")
+pub fn check_collocations(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    for (wrong_verb, object, correct_verb, correct_object) in COLLOCATIONS {
+        let (wrong_forms, correct_forms) = match (verb_forms(wrong_verb), verb_forms(correct_verb))
+        {
+            (Some(wrong), Some(correct)) => (wrong, correct),
+            _ => continue,
+        };
+        let pattern = format!(r"(?i)\b({})\s+{}\b", wrong_forms.join("|"), object);
+        let regex = match Regex::new(&pattern) {
+            Ok(re) => re,
+            Err(_) => continue,
+        };
+
+        for caps in regex.captures_iter(line) {
+            let (whole, verb) = match (caps.get(0), caps.get(1)) {
+                (Some(whole), Some(verb)) => (whole, verb),
+                _ => continue,
+            };
+
+            if *wrong_verb == "make" {
+                let next_word = line[whole.end()..]
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or("")
+                    .trim_matches(|c: char| !c.is_alphanumeric());
+                if is_causative_complement(next_word) {
+                    continue;
+                }
+            }
+
+            let previous_word = line[..whole.start()]
+                .split_whitespace()
+                .next_back()
+                .unwrap_or("")
+                .to_lowercase();
+
+            // 句首或疑问词后的 do 引导疑问句 "Did a mistake happen?"，不是动宾搭配
+            if *wrong_verb == "do" {
+                let sentence_start = previous_word.is_empty()
+                    || previous_word.ends_with(['.', '!', '?'])
+                    || matches!(
+                        previous_word.as_str(),
+                        "what" | "how" | "why" | "when" | "where" | "which" | "who"
+                    );
+                let question = line[whole.end()..]
+                    .split(['.', '!'])
+                    .next()
+                    .is_some_and(|rest| rest.contains('?'));
+                if sentence_start && question {
+                    continue;
+                }
+            }
+
+            // 保留原动词的时态；made、had 等过去式与过去分词同形，前面是 have 时取过去分词
+            let verb_lower = verb.as_str().to_lowercase();
+            let mut form = wrong_forms
+                .iter()
+                .position(|form| *form == verb_lower)
+                .unwrap_or(0);
+            if form == 2 && matches!(previous_word.as_str(), "have" | "has" | "had" | "having") {
+                form = 4;
+            }
+            let corrected = format!(
+                "{} {}",
+                match_case(verb.as_str(), correct_forms[form]),
+                correct_object
+            );
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, whole.start()),
+                end: byte_to_char_index(line, whole.end()),
+                issue_type: "搭配错误".to_string(),
+                message: format!("'{}' 是不地道的搭配", whole.as_str()),
+                suggestion: format!("建议修改为: '{}'", corrected),
+                rule_id: "collocations".to_string(),
+                replacement: Some(corrected),
+                confidence: 0.8,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+// 英文易混词：(正则，第 1 组为误用的词, 正确写法, 说明)，只收录上下文明确的搭配
+const CONFUSABLES: &[(&str, &str, &str)] = &[
+    (
+        r"(?i)\b(its)\s+(?:a|an|the|not|been|going|so|too|also|just|because)\b",
+        "it's",
+        "此处表示 it is / it has，应使用缩写 it's",
+    ),
+    (r"(?i)\b(it's)\s+own\b", "its", "物主代词 its 不加撇号"),
+    (
+        r"(?i)\b(there|they're)\s+own\b",
+        "their",
+        "表示“他们的”应使用物主代词 their",
+    ),
+    (
+        r"(?i)\b(their|they're)\s+(?:is|are|was|were)\b",
+        "there",
+        "表示“有”应使用 there be 句型",
+    ),
+    (
+        r"(?i)\b(there)\s+(?:car|house|home|family|friends|parents|children|names|goal|goals)\b",
+        "their",
+        "表示“他们的”应使用物主代词 their",
+    ),
+    (
+        r"(?i)\b(your)\s+(?:welcome|not)\b",
+        "you're",
+        "此处表示 you are，应使用缩写 you're",
+    ),
+    (
+        r"(?i)\b(you're)\s+own\b",
+        "your",
+        "表示“你的”应使用物主代词 your",
+    ),
+    (
+        r"(?i)\b(?:more|less|rather|other|better|worse|greater)\s+(then)\b",
+        "than",
+        "比较句中应使用 than",
+    ),
+    (
+        r"(?i)\b(whose)\s+(?:going|been)\b",
+        "who's",
+        "此处表示 who is / who has，应使用缩写 who's",
+    ),
+];
+
+// "Is there family nearby?" 中 there 前是系动词，不是物主代词误用
+const THERE_PRECEDING_WORDS: &[&str] = &["is", "are", "was", "were", "be", "been", "here", "out"];
+
+static CONFUSABLE_REGEXES: OnceLock<Vec<(Regex, &str, &str)>> = OnceLock::new();
+
+// 易混词的正则只在首次使用时编译一次
+fn confusable_regexes() -> &'static [(Regex, &'static str, &'static str)] {
+    CONFUSABLE_REGEXES.get_or_init(|| {
+        CONFUSABLES
+            .iter()
+            .filter_map(|&(pattern, correct, explanation)| {
+                Some((Regex::new(pattern).ok()?, correct, explanation))
+            })
+            .collect()
+    })
+}
+
+// Check for commonly confused English words (its/it's, their/there/they're, then/than)
+pub fn check_confusables(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    for (regex, correct, explanation) in confusable_regexes() {
+        for caps in regex.captures_iter(line) {
+            let wrong = match caps.get(1) {
+                Some(wrong) => wrong,
+                None => continue,
+            };
+
+            if wrong.as_str().eq_ignore_ascii_case("there") {
+                let previous_word = line[..wrong.start()]
+                    .split_whitespace()
+                    .next_back()
+                    .unwrap_or("")
+                    .to_lowercase();
+                if THERE_PRECEDING_WORDS.contains(&previous_word.as_str()) {
+                    continue;
+                }
+            }
+
+            let replacement = match_case(wrong.as_str(), correct);
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, wrong.start()),
+                end: byte_to_char_index(line, wrong.end()),
+                issue_type: "易混词".to_string(),
+                message: format!("'{}' 可能误用", wrong.as_str()),
+                suggestion: format!("建议修改为: '{}'，{}", replacement, explanation),
+                rule_id: "confusables".to_string(),
+                replacement: Some(replacement),
+                confidence: 0.9,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_nouns_are_listed_once() {
+        let mut nouns = COMMON_NOUNS.to_vec();
+        nouns.sort();
+        nouns.dedup();
+        assert_eq!(nouns.len(), COMMON_NOUNS.len());
+    }
+
+    #[test]
+    fn noun_heuristic_does_not_match_verbs() {
+        for word in [
+            "customer",
+            "analysis",
+            "results",
+            "management",
+            "satisfaction",
+            "security",
+            "manager",
+        ] {
+            assert!(looks_like_noun(word), "{}", word);
+        }
+        for word in [
+            "consider",
+            "manage",
+            "ensure",
+            "measure",
+            "implement",
+            "enhances",
+            "deliver",
+            "monitor",
+            "answer",
+        ] {
+            assert!(!looks_like_noun(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn oxford_comma_requires_three_list_items() {
+        let mut issues = Vec::new();
+        check_oxford_comma("He said, John and Mary left.", 0, &mut issues);
+        check_oxford_comma("After lunch, Tom and Ann went home.", 1, &mut issues);
+        assert!(issues.is_empty());
+
+        check_oxford_comma("He said, John, Mary and Sue left.", 2, &mut issues);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line_number, 3);
+        assert_eq!(issues[0].replacement.as_deref(), Some("Mary, and"));
+    }
+
+    #[test]
+    fn sentence_capitalization_checks_line_starts() {
+        let mut issues = Vec::new();
+        check_sentence_capitalization("this is a sentence.", 0, None, &mut issues);
+        check_sentence_capitalization("then it ends. next one.", 1, Some("Done."), &mut issues);
+        let starts: Vec<(usize, usize)> = issues
+            .iter()
+            .map(|issue| (issue.line_number, issue.start))
+            .collect();
+        assert_eq!(starts, vec![(1, 0), (2, 0), (2, 14)]);
+
+        // 折行的延续、列表项、标题和代码行的行首不检查
+        let mut issues = Vec::new();
+        check_sentence_capitalization("continues here.", 3, Some("The text"), &mut issues);
+        check_sentence_capitalization("- item one", 4, None, &mut issues);
+        check_sentence_capitalization("# intro", 5, None, &mut issues);
+        check_sentence_capitalization("    let x = foo();", 6, None, &mut issues);
+        assert!(issues.is_empty());
+    }
 }
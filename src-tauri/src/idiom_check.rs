@@ -0,0 +1,96 @@
+use crate::byte_to_char_index;
+use crate::TextIssue;
+use crate::MAX_ISSUES;
+use std::sync::OnceLock;
+
+// 常被误用的成语表：(成语, 正确含义)，只加载一次
+static IDIOM_MISUSE_TABLE: OnceLock<Vec<(String, String)>> = OnceLock::new();
+
+// 未找到成语表文件时使用的内置条目
+const BUILTIN_IDIOMS: &[(&str, &str)] = &[
+    (
+        "差强人意",
+        "大体上还能使人满意，是褒义词，不表示\"令人失望\"",
+    ),
+    (
+        "首当其冲",
+        "比喻最先受到攻击或遭遇灾难，不表示\"首先、带头\"",
+    ),
+    (
+        "万人空巷",
+        "形容人们都从家中出来（多为观看盛况），不表示\"街上空无一人\"",
+    ),
+    ("空穴来风", "原指有了漏洞才有风进来，比喻消息和传说事出有因"),
+    (
+        "不忍卒读",
+        "指文章内容悲惨动人，不忍心读完，不表示\"写得太差读不下去\"",
+    ),
+];
+
+// 解析成语表：每行 "成语<Tab>含义"，忽略空行和 # 注释
+fn parse_idiom_table(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (idiom, meaning) = line.split_once('\t')?;
+            let (idiom, meaning) = (idiom.trim(), meaning.trim());
+            if idiom.is_empty() || meaning.is_empty() {
+                None
+            } else {
+                Some((idiom.to_string(), meaning.to_string()))
+            }
+        })
+        .collect()
+}
+
+// 加载成语表文件
+fn load_idiom_table() -> &'static Vec<(String, String)> {
+    IDIOM_MISUSE_TABLE.get_or_init(|| {
+        let table = crate::dictionary::find_resource_file("Idioms.txt", "成语表", |path| {
+            let table = parse_idiom_table(&std::fs::read_to_string(path).ok()?);
+            (!table.is_empty()).then_some(table)
+        });
+        if let Some(table) = table {
+            return table;
+        }
+
+        eprintln!("未找到成语表文件，使用内置的成语列表");
+        BUILTIN_IDIOMS
+            .iter()
+            .map(|(idiom, meaning)| (idiom.to_string(), meaning.to_string()))
+            .collect()
+    })
+}
+
+// Remind about idioms that are often used with the wrong meaning
+pub fn check_idiom_misuse(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    for (idiom, meaning) in load_idiom_table() {
+        for (pos, _) in line.match_indices(idiom.as_str()) {
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, pos),
+                end: byte_to_char_index(line, pos + idiom.len()),
+                issue_type: "成语语义".to_string(),
+                message: format!("'{}' 常被误用", idiom),
+                suggestion: format!("该成语意为{}，请确认用法", meaning),
+                rule_id: "idiom_misuse".to_string(),
+                replacement: None,
+                confidence: 0.5,
+                severity: "info".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+}
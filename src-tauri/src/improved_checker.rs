@@ -1,9 +1,10 @@
 use crate::byte_to_char_index;
 use crate::dictionary;
+use crate::feedback;
 use crate::spelling_dict;
 use crate::TextIssue;
 use crate::MAX_ISSUES;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 // 查找完整单词的所有位置，确保不会匹配到单词的一部分
 pub fn find_all_whole_words(text: &str, word: &str) -> Vec<usize> {
@@ -50,12 +51,54 @@ pub fn find_whole_word(text: &str, word: &str) -> Option<usize> {
     find_all_whole_words(text, word).into_iter().next()
 }
 
+// 拼写建议最多给出的候选数
+const MAX_SPELLING_CANDIDATES: usize = 3;
+
+// 对词典中未找到的词，在词典中按编辑距离（不超过 2）查找最接近的候选
+pub fn suggest_corrections(word: &str) -> Vec<String> {
+    spelling_dict::suggest_corrections(word)
+        .into_iter()
+        .take(MAX_SPELLING_CANDIDATES)
+        .collect()
+}
+
+// 根据最佳候选的编辑距离估计置信度；有同样接近的其他候选时无法确定，返回 None 表示不给默认替换
+fn spelling_candidate_confidence(word: &str, candidates: &[String]) -> (f32, Option<String>) {
+    let word_lower = word.to_lowercase();
+    let distances: Vec<usize> = candidates
+        .iter()
+        .map(|candidate| spelling_dict::edit_distance(&word_lower, candidate, 2))
+        .collect();
+
+    let (best, best_distance) = match (candidates.first(), distances.first()) {
+        (Some(best), Some(distance)) => (best, *distance),
+        _ => return (0.5, None),
+    };
+    let confidence = match best_distance {
+        0 | 1 => 0.8,
+        2 => 0.6,
+        _ => 0.5,
+    };
+
+    if distances
+        .iter()
+        .skip(1)
+        .any(|distance| *distance <= best_distance)
+    {
+        (confidence - 0.2, None)
+    } else {
+        (confidence, Some(best.clone()))
+    }
+}
+
 // 改进的拼写检查函数，统一处理所有拼写检查逻辑
+// mixed 为 true 时该行中英混排，中文和英文的常见错误都检查
 pub fn check_spelling(
     line: &str,
     line_idx: usize,
     issues: &mut Vec<TextIssue>,
     global_detected_words: &mut HashSet<String>,
+    mixed: bool,
 ) {
     // Skip if we've already found too many issues
     if issues.len() >= MAX_ISSUES {
@@ -91,6 +134,11 @@ pub fn check_spelling(
             continue;
         }
 
+        // 跳过用户在本次会话中忽略或多次标记为误报的词
+        if feedback::is_session_ignored(&word) {
+            continue;
+        }
+
         // 检查单词是否在拼写错误字典中
         if let Some(correction) = spelling_dict::check_word_spelling(&word) {
             // 找到单词在原始行中的所有位置（确保是完整单词）
@@ -105,6 +153,11 @@ pub fn check_spelling(
                     issue_type: "可能的拼写错误".to_string(),
                     message: format!("可能的拼写错误: '{}'", word),
                     suggestion: format!("建议修改为: '{}'", correction),
+                    rule_id: "spelling".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "error".to_string(),
+                    language: String::new(),
                 });
 
                 // 添加到本行已检测集合
@@ -154,13 +207,37 @@ pub fn check_spelling(
                     continue;
                 }
 
+                // 按编辑距离给出候选，唯一最接近的候选作为默认替换
+                let candidates = suggest_corrections(&word);
+                let (confidence, replacement) = spelling_candidate_confidence(&word, &candidates);
+                let quoted = |list: &[String]| {
+                    list.iter()
+                        .map(|c| format!("'{}'", c))
+                        .collect::<Vec<_>>()
+                        .join("、")
+                };
+                let suggestion = match (&replacement, candidates.split_first()) {
+                    (Some(best), Some((_, []))) => format!("建议修改为: '{}'", best),
+                    (Some(best), Some((_, others))) => {
+                        format!("建议修改为: '{}'（其他候选: {}）", best, quoted(others))
+                    }
+                    // 多个候选同样接近时只列出，由用户选择
+                    (None, Some(_)) => format!("可能的候选: {}", quoted(&candidates)),
+                    (_, None) => "请检查拼写是否正确".to_string(),
+                };
+
                 issues.push(TextIssue {
                     line_number: line_idx + 1,
                     start: byte_to_char_index(line, pos),
                     end: byte_to_char_index(line, pos + word.len()),
                     issue_type: "可能的拼写错误".to_string(),
                     message: format!("词典中未找到: '{}'", word),
-                    suggestion: "请检查拼写是否正确".to_string(),
+                    suggestion,
+                    rule_id: "spelling".to_string(),
+                    replacement,
+                    confidence,
+                    severity: "error".to_string(),
+                    language: String::new(),
                 });
 
                 // 添加到本行已检测集合
@@ -174,16 +251,6 @@ pub fn check_spelling(
         }
     }
 
-    // 特别检查标题中的错误和常见拼写错误
-    check_title_errors(
-        line,
-        line_idx,
-        issues,
-        &mut line_detected_errors,
-        &mut line_detected_word_roots,
-        global_detected_words,
-    );
-
     // 检查常见拼写错误（整合原来的 check_common_typos 功能）
     check_common_spelling_errors(
         line,
@@ -192,127 +259,10 @@ pub fn check_spelling(
         &mut line_detected_errors,
         &mut line_detected_word_roots,
         global_detected_words,
+        mixed,
     );
 }
 
-// 特别检查标题中的错误
-fn check_title_errors(
-    line: &str,
-    line_idx: usize,
-    issues: &mut Vec<TextIssue>,
-    detected_errors: &mut HashSet<String>,
-    detected_word_roots: &mut HashSet<String>,
-    global_detected_words: &mut HashSet<String>,
-) {
-    // 特别针对您示例中的错误
-    let example_errors = [
-        ("Enronment", "Environment"),
-        ("Financal", "Financial"),
-        ("Alocation", "Allocation"),
-        ("Empincal", "Empirical"),
-        ("Eydence", "Evidence"),
-        ("Corporat", "Corporate"),
-        ("Geographc", "Geographic"),
-        ("Busines", "Business"),
-    ];
-
-    for (error, correction) in example_errors.iter() {
-        // 如果已经检测到这个错误，跳过
-        if detected_errors.contains(*error) {
-            continue;
-        }
-
-        // 检查词根是否已经被检测过（不区分大小写）
-        let error_lower = error.to_lowercase();
-        if detected_word_roots.contains(&error_lower) {
-            continue;
-        }
-
-        // 尝试查找完整单词的所有位置
-        let positions = find_all_whole_words(line, error);
-        if let Some(pos) = positions.first() {
-            issues.push(TextIssue {
-                line_number: line_idx + 1,
-                start: byte_to_char_index(line, *pos),
-                end: byte_to_char_index(line, *pos + error.len()),
-                issue_type: "可能的拼写错误".to_string(),
-                message: format!("可能的拼写错误: '{}'", error),
-                suggestion: format!("建议修改为: '{}'", correction),
-            });
-
-            // 添加到已检测集合
-            detected_errors.insert((*error).to_string());
-            detected_word_roots.insert(error_lower.clone());
-
-            // 添加到全局检测集合
-            global_detected_words.insert((*error).to_string());
-            global_detected_words.insert(error_lower.clone());
-
-            if issues.len() >= MAX_ISSUES {
-                return;
-            }
-        }
-
-        // 尝试小写版本 - 只有在词根没有被处理过的情况下才检查
-        if !detected_word_roots.contains(&error_lower) {
-            if !detected_errors.contains(&error_lower) {
-                let positions = find_all_whole_words(line, &error_lower);
-                if let Some(pos) = positions.first() {
-                    issues.push(TextIssue {
-                        line_number: line_idx + 1,
-                        start: byte_to_char_index(line, *pos),
-                        end: byte_to_char_index(line, *pos + error_lower.len()),
-                        issue_type: "可能的拼写错误".to_string(),
-                        message: format!("可能的拼写错误: '{}'", &error_lower),
-                        suggestion: format!("建议修改为: '{}'", correction),
-                    });
-
-                    // 添加到已检测集合
-                    detected_errors.insert(error_lower.clone());
-                    detected_word_roots.insert(error_lower.clone());
-
-                    // 添加到全局检测集合
-                    global_detected_words.insert(error_lower.clone());
-
-                    if issues.len() >= MAX_ISSUES {
-                        return;
-                    }
-                }
-            }
-        }
-
-        // 尝试首字母大写版本 - 只有在词根没有被处理过的情况下才检查
-        if !detected_word_roots.contains(&error_lower) {
-            let error_cap = capitalize_first(error);
-            if !detected_errors.contains(error_cap.as_str()) {
-                let positions = find_all_whole_words(line, &error_cap);
-                if let Some(pos) = positions.first() {
-                    issues.push(TextIssue {
-                        line_number: line_idx + 1,
-                        start: byte_to_char_index(line, *pos),
-                        end: byte_to_char_index(line, *pos + error_cap.len()),
-                        issue_type: "可能的拼写错误".to_string(),
-                        message: format!("可能的拼写错误: '{}'", &error_cap),
-                        suggestion: format!("建议修改为: '{}'", correction),
-                    });
-
-                    // 添加到已检测集合
-                    detected_errors.insert(error_cap.clone());
-                    detected_word_roots.insert(error_lower.clone());
-
-                    // 添加到全局检测集合
-                    global_detected_words.insert(error_cap.clone());
-                    global_detected_words.insert(error_lower.clone());
-
-                    if issues.len() >= MAX_ISSUES {
-                        return;
-                    }
-                }
-            }
-        }
-    }
-}
-
 // 检查常见拼写错误（整合原来的 check_common_typos 功能）
 fn check_common_spelling_errors(
     line: &str,
@@ -321,6 +271,7 @@ fn check_common_spelling_errors(
     detected_errors: &mut HashSet<String>,
     detected_word_roots: &mut HashSet<String>,
     global_detected_words: &mut HashSet<String>,
+    mixed: bool,
 ) {
     // Skip if we've already found too many issues
     if issues.len() >= MAX_ISSUES {
@@ -330,10 +281,11 @@ fn check_common_spelling_errors(
     // 检测语言类型
     let language = detect_language_simple(line);
 
-    if language == "zh" {
+    if language == "zh" || mixed {
         // 中文重复字符检测
         check_chinese_repeated_chars(line, line_idx, issues);
-    } else {
+    }
+    if language != "zh" || mixed {
         // 英文常见拼写错误检测
         check_english_common_typos(
             line,
@@ -382,7 +334,12 @@ fn check_chinese_repeated_chars(line: &str, line_idx: usize, issues: &mut Vec<Te
     let mut i = 0;
 
     while i < chars.len().saturating_sub(1) {
-        if chars[i] == chars[i + 1] && chars[i] >= '\u{4e00}' && chars[i] <= '\u{9fff}' {
+        // “的的”由 grammar_check::check_excessive_de 报告
+        if chars[i] == chars[i + 1]
+            && chars[i] != '的'
+            && chars[i] >= '\u{4e00}'
+            && chars[i] <= '\u{9fff}'
+        {
             // 检查是否是正常的重复组合
             let repeated_pair = format!("{}{}", chars[i], chars[i]);
 
@@ -410,6 +367,11 @@ fn check_chinese_repeated_chars(line: &str, line_idx: usize, issues: &mut Vec<Te
                     issue_type: "重复字符".to_string(),
                     message: format!("可能的重复字符: '{}{}'", chars[i], chars[i]),
                     suggestion: format!("检查是否需要删除重复的 '{}'", chars[i]),
+                    rule_id: "spelling".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "error".to_string(),
+                    language: String::new(),
                 });
 
                 if issues.len() >= MAX_ISSUES {
@@ -495,6 +457,7 @@ fn check_english_common_typos(
             || detected_word_roots.contains(&clean_word_lower)
             || global_detected_words.contains(&clean_word.to_string())
             || global_detected_words.contains(&clean_word_lower)
+            || feedback::is_session_ignored(clean_word)
         {
             continue;
         }
@@ -510,6 +473,11 @@ fn check_english_common_typos(
                     issue_type: "可能的拼写错误".to_string(),
                     message: format!("可能的拼写错误: '{}'", clean_word),
                     suggestion: format!("建议修改为: '{}'", correction),
+                    rule_id: "spelling".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "error".to_string(),
+                    language: String::new(),
                 });
 
                 // 添加到检测集合
@@ -527,6 +495,28 @@ fn check_english_common_typos(
     }
 }
 
+/// 统计文本中词典未收录的词及出现次数，按次数降序返回，便于维护者扩充词典
+///
+/// 词统一转为小写计数；带连字符的复合词和含数字的词与拼写检查一样跳过。
+pub fn collect_unknown_words(text: &str) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for line in text.lines() {
+        for word in extract_words_from_line(line) {
+            if word.contains('-') || word.chars().any(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            if !dictionary::is_word_in_dictionary(&word) {
+                *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut words: Vec<(String, usize)> = counts.into_iter().collect();
+    words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    words
+}
+
 // 从行中提取单词的函数，支持中英文混合文本
 fn extract_words_from_line(line: &str) -> Vec<String> {
     let mut words = Vec::new();
@@ -572,12 +562,3 @@ fn extract_words_from_line(line: &str) -> Vec<String> {
 
     words
 }
-
-// 首字母大写的辅助函数
-fn capitalize_first(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
-    }
-}
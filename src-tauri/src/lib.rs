@@ -1,22 +1,45 @@
+use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::sync::OnceLock;
 use std::time::Duration;
 use tauri::Emitter;
 
+use config::{AnalysisConfig, CheckOptions};
+
 // 导入拼写检查模块
+mod amount_check;
+mod checker;
+mod config;
 mod dictionary;
+mod document_check;
 mod document_parser;
+mod export;
+mod feedback;
 mod fix_functions;
+mod fixes;
 mod grammar_check;
+mod idiom_check;
 mod improved_checker;
+pub mod lsp;
+mod markdown;
+mod normalize;
+mod readability;
 mod spelling_dict;
-mod title_checker;
+mod storage;
+mod structure;
+mod traditional;
+mod unit_check;
+
+// 供命令行工具等不依赖 Tauri 的调用方使用
+pub use document_parser::parse_document;
+pub use export::{report_csv, report_markdown};
 
-// Import our gr text processing limits
+// Default text processing limits, can be overridden by config::AnalysisLimits
 const MAX_TEXT_LENGTH: usize = 50_000; // Maximum text length to process at once
 const MAX_LINE_LENGTH: usize = 500; // Maximum line length to process
 const MAX_ISSUES: usize = 500; // Maximum number of issues to return
@@ -40,14 +63,50 @@ fn truncate_string_safe(text: &str, max_chars: usize) -> &str {
     &text[0..byte_index]
 }
 
+// 按字符数截断文本，并尽量在最后一个完整行结束，避免把一行或一个句子切成两半
+// 截断部分没有换行（单行超长）时退回到字符边界
+fn truncate_at_line_boundary(text: &str, max_chars: usize) -> &str {
+    let truncated = truncate_string_safe(text, max_chars);
+    if truncated.len() == text.len() || text[truncated.len()..].starts_with('\n') {
+        return truncated;
+    }
+    match truncated.rfind('\n') {
+        Some(pos) => &truncated[..pos],
+        None => truncated,
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TextIssue {
-    line_number: usize,
-    start: usize,
-    end: usize,
-    issue_type: String,
-    message: String,
-    suggestion: String,
+    pub line_number: usize,
+    pub start: usize,
+    pub end: usize,
+    pub issue_type: String,
+    pub message: String,
+    pub suggestion: String,
+    // 产生该问题的规则 ID，与配置中的规则开关一致
+    #[serde(default)]
+    pub rule_id: String,
+    // 可直接用于替换问题区间的文本，没有明确替换时为 None
+    #[serde(default)]
+    pub replacement: Option<String>,
+    // 置信度（0-1），启发式规则给出较低的值
+    #[serde(default = "default_confidence")]
+    pub confidence: f32,
+    // 严重程度：error / warning / info
+    #[serde(default = "default_severity")]
+    pub severity: String,
+    // 问题所在行的语言（"zh" / "en"），用于分语言统计
+    #[serde(default)]
+    pub language: String,
+}
+
+fn default_confidence() -> f32 {
+    1.0
+}
+
+fn default_severity() -> String {
+    "warning".to_string()
 }
 
 // Convert byte index to character index
@@ -57,9 +116,23 @@ fn byte_to_char_index(s: &str, byte_idx: usize) -> usize {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AnalysisResult {
-    issues: Vec<TextIssue>,
-    stats: HashMap<String, usize>,
-    truncated: bool,
+    pub issues: Vec<TextIssue>,
+    pub stats: HashMap<String, usize>,
+    pub truncated: bool,
+    // 按 issue_type 统计的问题数，与实际返回的 issues 一致
+    #[serde(default)]
+    pub issue_counts: HashMap<String, usize>,
+    // 分页分析时下一页的起始行下标（从 0 开始），已处理到末尾或非分页分析时为 None
+    #[serde(default)]
+    pub next_offset_line: Option<usize>,
+}
+
+/// 读取的文件内容及探测到的编码
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FileContent {
+    content: String,
+    // 纯文本文件探测到的编码（如 "UTF-8"、"GBK"），Word、PDF 等文档为 None
+    encoding: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -79,16 +152,42 @@ pub struct AsyncAnalysisResult {
     error: Option<String>,
 }
 
+/// 按当前配置分析文本
+///
+/// 不依赖 Tauri，可供命令行工具和测试直接调用；超出配置上限的部分截断并设置 truncated。
+pub fn analyze(text: &str) -> AnalysisResult {
+    analyze_with_config(text, &config::current_config())
+}
+
 #[tauri::command]
-fn analyze_text(text: &str) -> AnalysisResult {
+fn analyze_text(text: &str, options: Option<CheckOptions>) -> AnalysisResult {
+    let options = match options {
+        Some(options) => options,
+        None => return analyze(text),
+    };
+    let mut config = config::current_config();
+    config.check_options = options;
+    analyze_with_config(text, &config)
+}
+
+// 使用前端传入的配置分析文本，处理上限超出合理范围时返回错误
+#[tauri::command]
+fn analyze_text_with_config(text: &str, config: AnalysisConfig) -> Result<AnalysisResult, String> {
+    config.validate()?;
+    Ok(analyze_with_config(text, &config))
+}
+
+// 按指定配置分析文本，超出配置上限时截断并设置 truncated
+fn analyze_with_config(text: &str, config: &AnalysisConfig) -> AnalysisResult {
+    let limits = &config.limits;
     let mut issues = Vec::new();
     let mut stats = HashMap::new();
     let mut truncated = false;
 
-    // Limit text size to prevent crashes (UTF-8 safe)
-    let text = if text.chars().count() > MAX_TEXT_LENGTH {
+    // Limit text size to prevent crashes (UTF-8 safe, ends at a complete line)
+    let text = if text.chars().count() > limits.max_text_length {
         truncated = true;
-        truncate_string_safe(text, MAX_TEXT_LENGTH)
+        truncate_at_line_boundary(text, limits.max_text_length)
     } else {
         text
     };
@@ -103,32 +202,148 @@ fn analyze_text(text: &str) -> AnalysisResult {
     stats.insert("total_lines".to_string(), total_lines);
 
     // Process text in smaller chunks to avoid memory issues
-    process_text_chunk(text, 0, &mut issues, &mut truncated);
+    let mut reported_words = HashSet::new();
+    process_text_chunk(
+        text,
+        0,
+        None,
+        &mut issues,
+        &mut truncated,
+        config,
+        &mut reported_words,
+    );
+    run_document_checks(text, config, &mut issues);
+    sort_and_dedup_issues(&mut issues);
 
     // Limit the number of issues returned
-    if issues.len() > MAX_ISSUES {
-        issues.truncate(MAX_ISSUES);
+    if issues.len() > limits.max_issues {
+        issues.truncate(limits.max_issues);
         truncated = true;
     }
 
+    insert_language_stats(&issues, &mut stats);
+
     AnalysisResult {
+        issue_counts: count_issue_types(&issues),
         issues,
         stats,
         truncated,
+        next_offset_line: None,
     }
 }
 
+/// 分页分析：从第 offset_line 行（从 0 开始）起检查，问题数达到 max_issues 时在行边界处停止
+///
+/// 返回的 next_offset_line 为下一页的起始行，处理到末尾时为 None。不受全文长度上限的截断，
+/// 文档级检查只在第一页运行；拼写错误只在同一页内去重。
+pub fn analyze_paged(text: &str, offset_line: usize, max_issues: usize) -> AnalysisResult {
+    let config = config::current_config();
+    let max_issues = max_issues.max(1);
+    let mut issues = Vec::new();
+    let mut stats = HashMap::new();
+    let mut truncated = false;
+    let mut next_offset_line = None;
+
+    stats.insert("total_chars".to_string(), text.chars().count());
+    stats.insert("total_words".to_string(), text.split_whitespace().count());
+    stats.insert("total_lines".to_string(), text.lines().count());
+
+    let checkers = checker::build_pipeline(&config);
+    let mut reported_words = HashSet::new();
+    let all_lines: Vec<&str> = text.lines().collect();
+    let lines: Vec<(usize, &str)> = all_lines
+        .iter()
+        .copied()
+        .enumerate()
+        .skip(offset_line)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+
+    'batches: for batch in lines.chunks(PARALLEL_BATCH_LINES) {
+        let results: Vec<(Vec<TextIssue>, bool)> = batch
+            .par_iter()
+            .map(|&(line_idx, line)| {
+                let prev = line_idx.checked_sub(1).map(|idx| all_lines[idx]);
+                check_single_line(line, line_idx, prev, &checkers, &config, max_issues)
+            })
+            .collect();
+
+        for (&(line_idx, line), (mut line_issues, line_truncated)) in batch.iter().zip(results) {
+            line_issues.retain(|issue| {
+                issue.rule_id != "spelling"
+                    || !reported_words.contains(&issue_text(line, issue).to_lowercase())
+            });
+
+            // 本页放不下这一行的全部问题时，从这一行开始下一页；单行问题过多时仍放在本页
+            if !issues.is_empty() && issues.len() + line_issues.len() > max_issues {
+                next_offset_line = Some(line_idx);
+                break 'batches;
+            }
+
+            if line_truncated {
+                truncated = true;
+            }
+            for issue in &line_issues {
+                if issue.rule_id == "spelling" {
+                    reported_words.insert(issue_text(line, issue).to_lowercase());
+                }
+            }
+            issues.append(&mut line_issues);
+        }
+    }
+
+    // 文档级问题按所在行分到对应的页，与逐行问题合并后再按页大小截断
+    let mut document_issues = Vec::new();
+    document_check::check_document(text, &config, &mut document_issues);
+    let page_lines = offset_line..next_offset_line.unwrap_or(usize::MAX);
+    issues.extend(
+        document_issues
+            .into_iter()
+            .filter(|issue| page_lines.contains(&issue.line_number.saturating_sub(1))),
+    );
+    sort_and_dedup_issues(&mut issues);
+
+    // 合并后超出页大小时，从放不下的那一行开始下一页；单行问题过多时仍放在本页
+    if issues.len() > max_issues {
+        let overflow_line = issues[max_issues].line_number;
+        let mut cut = issues.partition_point(|issue| issue.line_number < overflow_line);
+        if cut == 0 {
+            cut = issues.partition_point(|issue| issue.line_number <= overflow_line);
+        }
+        if cut < issues.len() {
+            next_offset_line = Some(issues[cut].line_number - 1);
+            issues.truncate(cut);
+        }
+    }
+    insert_language_stats(&issues, &mut stats);
+
+    AnalysisResult {
+        issue_counts: count_issue_types(&issues),
+        issues,
+        stats,
+        truncated,
+        next_offset_line,
+    }
+}
+
+// 分页分析命令，前端按 next_offset_line 继续请求直到为 None
+#[tauri::command]
+fn analyze_text_paged(text: &str, offset_line: usize, max_issues: usize) -> AnalysisResult {
+    analyze_paged(text, offset_line, max_issues)
+}
+
 // 批量拼写检查命令
 #[tauri::command]
 fn batch_spell_check(text: &str) -> AnalysisResult {
+    let limits = config::current_config().limits;
     let mut issues = Vec::new();
     let mut stats = HashMap::new();
     let mut truncated = false;
 
-    // Limit text size to prevent crashes (UTF-8 safe)
-    let text = if text.chars().count() > MAX_TEXT_LENGTH {
+    // Limit text size to prevent crashes (UTF-8 safe, ends at a complete line)
+    let text = if text.chars().count() > limits.max_text_length {
         truncated = true;
-        truncate_string_safe(text, MAX_TEXT_LENGTH)
+        truncate_at_line_boundary(text, limits.max_text_length)
     } else {
         text
     };
@@ -144,14 +359,19 @@ fn batch_spell_check(text: &str) -> AnalysisResult {
 
     // 使用批量拼写检查函数
     let spelling_errors = spelling_dict::check_text_spelling(text);
+    let lines: Vec<&str> = text.lines().collect();
 
     // 将拼写错误转换为TextIssue格式
     for (wrong_word, correction, line_idx, pos) in spelling_errors {
-        if issues.len() >= MAX_ISSUES {
+        if issues.len() >= limits.max_issues {
             truncated = true;
             break;
         }
 
+        if feedback::is_session_ignored(&wrong_word) {
+            continue;
+        }
+
         issues.push(TextIssue {
             line_number: line_idx + 1,
             start: pos,
@@ -159,135 +379,294 @@ fn batch_spell_check(text: &str) -> AnalysisResult {
             issue_type: "可能的拼写错误".to_string(),
             message: format!("可能的拼写错误: '{}'", wrong_word),
             suggestion: format!("建议修改为: '{}'", correction),
+            rule_id: "spelling".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "error".to_string(),
+            language: lines
+                .get(line_idx)
+                .map(|line| detect_language(line))
+                .unwrap_or_default(),
         });
     }
 
+    insert_language_stats(&issues, &mut stats);
+
     AnalysisResult {
+        issue_counts: count_issue_types(&issues),
         issues,
         stats,
         truncated,
+        next_offset_line: None,
     }
 }
 
+// 按问题类型统计数量，需在截断之后调用
+fn count_issue_types(issues: &[TextIssue]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for issue in issues {
+        *counts.entry(issue.issue_type.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+// 按问题所在行的语言分别统计问题数
+fn insert_language_stats(issues: &[TextIssue], stats: &mut HashMap<String, usize>) {
+    let zh_issues = issues.iter().filter(|issue| issue.language == "zh").count();
+    let en_issues = issues.iter().filter(|issue| issue.language == "en").count();
+    stats.insert("zh_issues".to_string(), zh_issues);
+    stats.insert("en_issues".to_string(), en_issues);
+}
+
+// 在逐行检查之后运行文档级检查，结果需再经 sort_and_dedup_issues 排序
+// 流式分析大文件时无法获得全文，不运行文档级检查
+fn run_document_checks(text: &str, config: &AnalysisConfig, issues: &mut Vec<TextIssue>) {
+    if issues.len() >= config.limits.max_issues {
+        return;
+    }
+    // 文档级问题单独收集，避免受逐行问题数量的影响
+    let mut document_issues = Vec::new();
+    document_check::check_document(text, config, &mut document_issues);
+    issues.append(&mut document_issues);
+}
+
+// 按 (行号, 起始位置, 问题类型) 排序，并去掉位置和类型完全相同的重复问题
+// 不同模块可能对同一处报同一类问题，只保留最先产生的一条；需在截断之前调用
+fn sort_and_dedup_issues(issues: &mut Vec<TextIssue>) {
+    issues.sort_by(|a, b| {
+        a.line_number
+            .cmp(&b.line_number)
+            .then(a.start.cmp(&b.start))
+            .then_with(|| a.issue_type.cmp(&b.issue_type))
+            .then(a.end.cmp(&b.end))
+    });
+    issues.dedup_by(|b, a| {
+        a.line_number == b.line_number
+            && a.start == b.start
+            && a.end == b.end
+            && a.issue_type == b.issue_type
+    });
+}
+
+// 每批并行检查的行数，批与批之间检查问题总数，达到上限后不再处理后续行
+const PARALLEL_BATCH_LINES: usize = 256;
+
 // Process a chunk of text
+// 各行相互独立，用 rayon 并行检查，结果按行号顺序合并
+// reported_words 记录本次分析中已提示过的拼写错误（小写），分块处理时由调用方在各块间共享，
+// 同一个错词在整次分析中只提示第一次出现
+// prev_line 是块之前的一行，块从文档开头开始时为 None
 fn process_text_chunk(
     text: &str,
     start_line: usize,
+    prev_line: Option<&str>,
     issues: &mut Vec<TextIssue>,
     truncated: &mut bool,
+    config: &AnalysisConfig,
+    reported_words: &mut HashSet<String>,
 ) {
-    // 用于跟踪已经检测到的错误词根，避免重复提示相同词根的不同形式
-    // 这个集合在整个文本处理过程中共享，确保不会重复检测相同的错误
-    let mut global_detected_words = std::collections::HashSet::<String>::new();
-    // Analyze each line
-    for (rel_line_idx, line) in text.lines().enumerate() {
-        let line_idx = start_line + rel_line_idx;
-
-        // Skip empty lines
-        if line.trim().is_empty() {
-            continue;
-        }
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .map(|(rel_line_idx, line)| (start_line + rel_line_idx, line))
+        .collect();
+    process_lines(&lines, prev_line, issues, truncated, config, reported_words);
+}
 
-        // Limit line length to prevent excessive processing (UTF-8 safe)
-        let line = if line.chars().count() > MAX_LINE_LENGTH {
-            *truncated = true;
-            truncate_string_safe(line, MAX_LINE_LENGTH)
-        } else {
-            line
-        };
+// 检查若干连续的行，每行带有在全文中的行下标（从 0 开始），prev_line 是这些行之前的一行
+fn process_lines(
+    lines: &[(usize, &str)],
+    prev_line: Option<&str>,
+    issues: &mut Vec<TextIssue>,
+    truncated: &mut bool,
+    config: &AnalysisConfig,
+    reported_words: &mut HashSet<String>,
+) {
+    let checkers = checker::build_pipeline(config);
+    let limits = &config.limits;
 
+    // Skip empty lines，同时记下每行的上一行
+    let lines: Vec<(usize, &str, Option<&str>)> = lines
+        .iter()
+        .enumerate()
+        .map(|(pos, &(line_idx, line))| {
+            let prev = match pos {
+                0 => prev_line,
+                _ => Some(lines[pos - 1].1),
+            };
+            (line_idx, line, prev)
+        })
+        .filter(|(_, line, _)| !line.trim().is_empty())
+        .collect();
+
+    for batch in lines.chunks(PARALLEL_BATCH_LINES) {
         // Stop if we've found too many issues
-        if issues.len() >= MAX_ISSUES {
+        if issues.len() >= limits.max_issues {
             *truncated = true;
-            break;
-        }
-
-        // Auto-detect language for the current line
-        let line_language = detect_language(line);
-
-        // Check for repeated words
-        check_repeated_words(line, line_idx, issues);
-        if issues.len() >= MAX_ISSUES {
-            break;
-        }
-
-        // Check punctuation usage
-        check_punctuation(line, line_idx, issues);
-        if issues.len() >= MAX_ISSUES {
-            break;
+            return;
         }
+        let remaining = limits.max_issues - issues.len();
+
+        // collect 保持原有顺序，合并后仍按行号排列
+        let results: Vec<(Vec<TextIssue>, bool)> = batch
+            .par_iter()
+            .map(|&(line_idx, line, prev)| {
+                check_single_line(line, line_idx, prev, &checkers, config, remaining)
+            })
+            .collect();
 
-        // 被动语态检查已禁用
-        // check_passive_voice(line, line_idx, issues, &line_language);
-        // if issues.len() >= MAX_ISSUES {
-        //     break;
-        // }
-
-        // Check redundant expressions
-        check_redundant_expressions(line, line_idx, issues, &line_language);
-        if issues.len() >= MAX_ISSUES {
-            break;
-        }
+        for (&(_, line, _), (mut line_issues, line_truncated)) in batch.iter().zip(results) {
+            if line_truncated {
+                *truncated = true;
+            }
+            line_issues.retain(|issue| {
+                issue.rule_id != "spelling"
+                    || reported_words.insert(issue_text(line, issue).to_lowercase())
+            });
 
-        // 统一的拼写检查 - 只调用一个主要的拼写检查函数，避免重复检测
-        // 使用改进的拼写检查器，它已经包含了所有必要的拼写检查逻辑
-        improved_checker::check_spelling(line, line_idx, issues, &mut global_detected_words);
-        if issues.len() >= MAX_ISSUES {
-            break;
+            issues.append(&mut line_issues);
+            if issues.len() >= limits.max_issues {
+                *truncated = true;
+                return;
+            }
         }
+    }
+}
 
-        // 注释掉其他拼写检查函数，避免重复检测
-        // check_common_typos 的功能已经整合到 improved_checker 中
-        // title_checker 的功能也已经整合到 improved_checker 中
-
-        // Check grammar issues
-        check_grammar_issues(line, line_idx, issues, &line_language);
-        if issues.len() >= MAX_ISSUES {
-            break;
-        }
+// 问题所在位置的原文
+fn issue_text(line: &str, issue: &TextIssue) -> String {
+    line.chars()
+        .skip(issue.start)
+        .take(issue.end.saturating_sub(issue.start))
+        .collect()
+}
 
-        // 使用语法检查模块
-        grammar_check::check_word_order(line, line_idx, issues);
-        if issues.len() >= MAX_ISSUES {
-            break;
-        }
+// 检查单独一行，返回该行的问题以及该行是否因过长被截断；prev_line 为原文中的上一行
+fn check_single_line(
+    line: &str,
+    line_idx: usize,
+    prev_line: Option<&str>,
+    checkers: &[Box<dyn checker::LineChecker>],
+    config: &AnalysisConfig,
+    remaining: usize,
+) -> (Vec<TextIssue>, bool) {
+    let limits = &config.limits;
+
+    // Limit line length to prevent excessive processing (UTF-8 safe)
+    let line_truncated = line.chars().count() > limits.max_line_length;
+    let line = if line_truncated {
+        truncate_string_safe(line, limits.max_line_length)
+    } else {
+        line
+    };
 
-        grammar_check::check_chinese_punctuation(line, line_idx, issues);
-        if issues.len() >= MAX_ISSUES {
-            break;
-        }
+    // 繁体行可先逐字转换为简体再检查，字符数不变，问题位置仍对应原文
+    let is_traditional = traditional::is_traditional(line);
+    let simplified;
+    let line = if is_traditional && config.convert_traditional_to_simplified {
+        simplified = traditional::to_simplified(line);
+        simplified.as_str()
+    } else {
+        line
+    };
 
-        grammar_check::check_tense_consistency(line, line_idx, issues);
-        if issues.len() >= MAX_ISSUES {
-            break;
+    // 每行的问题先单独收集，便于按行限制数量
+    // 跨行的拼写去重在合并时进行，这里只记录本行已报告的词
+    // 中英混排的行同时运行另一种语言的部分规则，如中文里英文术语的拼写检查
+    let mix = detect_language_mix(line);
+    let line_language = mix.primary().to_string();
+    let threshold = config
+        .mixed_language_threshold
+        .unwrap_or(DEFAULT_MIXED_LANGUAGE_THRESHOLD);
+    let mut line_issues = Vec::new();
+    let mut detected_words = HashSet::<String>::new();
+    let mut ctx = checker::LineContext {
+        line_idx,
+        prev_line,
+        language: &line_language,
+        mixed: mix.is_mixed(threshold),
+        traditional: is_traditional && !config.convert_traditional_to_simplified,
+        config,
+        detected_words: &mut detected_words,
+    };
+    check_line(line, &mut ctx, checkers, &mut line_issues, remaining);
+
+    // 超过每行上限时截断，并用一条汇总问题提示剩余数量
+    if let Some(max_per_line) = config.max_issues_per_line {
+        if line_issues.len() > max_per_line {
+            let hidden = line_issues.len() - max_per_line;
+            line_issues.truncate(max_per_line);
+            line_issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: 0,
+                end: line.chars().count(),
+                issue_type: "问题过多".to_string(),
+                message: format!("此行还有 {} 个问题未显示", hidden),
+                suggestion: "可在配置中调整每行最多报告的问题数".to_string(),
+                rule_id: "max_issues_per_line".to_string(),
+                replacement: None,
+                confidence: 1.0,
+                severity: "info".to_string(),
+                language: String::new(),
+            });
         }
+    }
 
-        grammar_check::check_preposition_usage(line, line_idx, issues);
-        if issues.len() >= MAX_ISSUES {
-            break;
-        }
+    // 记录问题所在行的语言，并按配置调整严重程度
+    for issue in &mut line_issues {
+        issue.language = line_language.clone();
+        config.apply_severity_override(issue);
+    }
 
-        // 使用修复函数模块
-        fix_functions::check_idiom_usage(line, line_idx, issues);
-        if issues.len() >= MAX_ISSUES {
-            break;
-        }
+    (line_issues, line_truncated)
+}
 
-        fix_functions::check_academic_style(line, line_idx, issues, &line_language);
-        if issues.len() >= MAX_ISSUES {
-            break;
+// 按管线顺序对单行运行检查，结果写入该行自己的问题列表
+fn check_line(
+    line: &str,
+    ctx: &mut checker::LineContext,
+    checkers: &[Box<dyn checker::LineChecker>],
+    line_issues: &mut Vec<TextIssue>,
+    remaining: usize,
+) {
+    for line_checker in checkers {
+        let mut found = line_checker.check(line, ctx);
+        let hit = !found.is_empty();
+        line_issues.append(&mut found);
+        if line_issues.len() >= remaining {
+            return;
         }
 
-        fix_functions::check_sentence_length(line, line_idx, issues, &line_language);
-        if issues.len() >= MAX_ISSUES {
-            break;
+        // 短路：命中后跳过该行后续的检查，避免同一处重复报告
+        if hit
+            && ctx
+                .config
+                .short_circuit_rules
+                .iter()
+                .any(|id| id == line_checker.id())
+        {
+            return;
         }
+    }
+}
 
-        fix_functions::check_citation_format(line, line_idx, issues);
-        if issues.len() >= MAX_ISSUES {
-            break;
+// 按空白切分，返回每个词去掉首尾标点后的字节区间
+fn word_spans(line: &str) -> Vec<(usize, usize)> {
+    let mut words: Vec<(usize, usize)> = Vec::new();
+    let mut offset = 0;
+    for token in line.split_whitespace() {
+        let token_start = offset + line[offset..].find(token).unwrap_or(0);
+        offset = token_start + token.len();
+
+        let is_punct = |c: char| !c.is_alphanumeric();
+        let core = token.trim_matches(is_punct);
+        if core.is_empty() {
+            continue;
         }
+        let core_start = token_start + token.len() - token.trim_start_matches(is_punct).len();
+        words.push((core_start, core_start + core.len()));
     }
+    words
 }
 
 fn check_repeated_words(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
@@ -296,155 +675,328 @@ fn check_repeated_words(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>
         return;
     }
 
-    // 使用更简单的方法检测重复词
-    let words: Vec<&str> = line.split_whitespace().collect();
+    let words = word_spans(line);
 
-    // 跟踪已经检测到的重复词，避免重复报告
-    let mut detected_positions = Vec::new();
+    // 跟踪上一对是否已报告，避免 "a a a" 重复报告
+    let mut previous_reported = false;
+
+    for pair in words.windows(2) {
+        if previous_reported {
+            previous_reported = false;
+            continue;
+        }
+        let (first_start, first_end) = pair[0];
+        let (second_start, second_end) = pair[1];
+        let first = &line[first_start..first_end];
+        let second = &line[second_start..second_end];
 
-    for i in 0..words.len().saturating_sub(1) {
         // 跳过太短的词（少于4个字母的英文词或1个汉字）
-        let min_length = if words[i].chars().any(|c| c >= '\u{4e00}' && c <= '\u{9fff}') {
+        let min_length = if first
+            .chars()
+            .any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c))
+        {
             1 // 中文词至少1个字
         } else {
             4 // 英文词至少4个字母
         };
+        if first.chars().count() < min_length {
+            continue;
+        }
 
-        if words[i].chars().count() < min_length {
+        // 大小写无关比较，如 "The the"
+        if first.to_lowercase() != second.to_lowercase() {
             continue;
         }
 
-        // 检查是否与下一个词相同
-        if words[i] == words[i + 1] {
-            // 找到第一个词的位置
-            if let Some(first_word_pos) = find_whole_word(line, words[i]) {
-                // 计算第一个词的结束位置（字符安全）
-                let first_word_end = first_word_pos + words[i].len();
-
-                // 确保不会超出字符串边界
-                if first_word_end <= line.len() {
-                    // 找到第二个词的位置（从第一个词之后开始查找）
-                    let after_first = &line[first_word_end..];
-                    if let Some(second_pos) = find_whole_word(after_first, words[i]) {
-                        let second_word_pos = first_word_end + second_pos;
-
-                        // 确保两个词之间只有空白字符
-                        if second_word_pos <= line.len() {
-                            let between_text = &line[first_word_end..second_word_pos];
-                            if between_text.trim().is_empty() {
-                                // 检查是否已经检测到这个位置的重复词
-                                let already_detected =
-                                    detected_positions.iter().any(|&(start, end)| {
-                                        (first_word_pos >= start && first_word_pos < end)
-                                            || (second_word_pos >= start && second_word_pos < end)
-                                    });
-
-                                if !already_detected {
-                                    issues.push(TextIssue {
-                                        line_number: line_idx + 1,
-                                        start: byte_to_char_index(line, first_word_pos),
-                                        end: byte_to_char_index(
-                                            line,
-                                            second_word_pos + words[i].len(),
-                                        ),
-                                        issue_type: "重复词".to_string(),
-                                        message: format!("重复使用词语 '{}'", words[i]),
-                                        suggestion: format!("删除重复的 '{}'", words[i]),
-                                    });
-
-                                    // 记录已检测的位置
-                                    detected_positions
-                                        .push((first_word_pos, second_word_pos + words[i].len()));
-
-                                    // Stop if we've found too many issues
-                                    if issues.len() >= MAX_ISSUES {
-                                        return;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        // 两个词之间只允许空白和逗号，如 "word, word"；句号等分隔的是不同句子
+        let between = &line[first_end..second_start];
+        if !between
+            .chars()
+            .all(|c| c.is_whitespace() || c == ',' || c == '，')
+        {
+            continue;
+        }
+        let has_comma = between.contains([',', '，']);
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, first_start),
+            end: byte_to_char_index(line, second_end),
+            issue_type: "重复词".to_string(),
+            message: format!("重复使用词语 '{}'", &line[first_start..second_end]),
+            suggestion: format!("删除重复的 '{}'", first),
+            rule_id: "repeated_words".to_string(),
+            replacement: None,
+            // 逗号隔开的重复偶尔是有意强调
+            confidence: if has_comma { 0.8 } else { 1.0 },
+            severity: "warning".to_string(),
+            language: String::new(),
+        });
+        previous_reported = true;
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
         }
     }
 }
 
-// 查找完整单词的位置，确保不会匹配到单词的一部分
-fn find_whole_word(text: &str, word: &str) -> Option<usize> {
-    let mut search_start = 0;
+// 英文短语最多的词数、中文短语最少和最多的字数，窗口大小固定，长行也是线性时间
+const MAX_PHRASE_WORDS: usize = 4;
+const MIN_CHINESE_PHRASE_CHARS: usize = 3;
+const MAX_CHINESE_PHRASE_CHARS: usize = 8;
 
-    while search_start < text.len() {
-        // 使用字符安全的方式获取剩余文本
-        let remaining_text = &text[search_start..];
+fn repeated_phrase_issue(
+    line: &str,
+    line_idx: usize,
+    start: usize,
+    mid: usize,
+    end: usize,
+) -> TextIssue {
+    let phrase = line[start..mid].trim_end();
+    TextIssue {
+        line_number: line_idx + 1,
+        start: byte_to_char_index(line, start),
+        end: byte_to_char_index(line, end),
+        issue_type: "重复短语".to_string(),
+        message: format!("短语重复: '{}'", &line[start..end]),
+        suggestion: format!("删除重复的 '{}'", phrase),
+        rule_id: "repeated_phrases".to_string(),
+        replacement: Some(phrase.to_string()),
+        confidence: 0.9,
+        severity: "warning".to_string(),
+        language: String::new(),
+    }
+}
 
-        if let Some(pos) = remaining_text.find(word) {
-            let actual_pos = search_start + pos;
+// Check for phrases repeated back to back, e.g. "of the of the" or "进行分析进行分析"
+// 单个词的重复由 check_repeated_words 报告
+fn check_repeated_phrases(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
 
-            // 检查单词前后是否是单词边界（空格、标点符号等）
-            let is_start_boundary = actual_pos == 0
-                || !text
+    // 英文：比较相邻的两个 2–4 词短语，优先匹配较长的短语
+    let words = word_spans(line);
+    let lower: Vec<String> = words
+        .iter()
+        .map(|&(start, end)| line[start..end].to_lowercase())
+        .collect();
+    let mut i = 0;
+    while i < words.len() {
+        let matched = (2..=MAX_PHRASE_WORDS).rev().find(|&n| {
+            if i + 2 * n > words.len() {
+                return false;
+            }
+            let (first, second) = (&lower[i..i + n], &lower[i + n..i + 2 * n]);
+            // "over and over and over" 这类以连词首尾相接的是固定说法
+            let is_conjunction = |w: &String| matches!(w.as_str(), "and" | "or");
+            first == second
+                && first.iter().any(|w| w != &first[0])
+                && first.iter().all(|w| w.chars().all(|c| c.is_ascii_alphabetic()))
+                && !is_conjunction(&first[0])
+                && !is_conjunction(&first[n - 1])
+                // 词间只允许空白，标点隔开的可能是不同分句
+                && line[words[i].0..words[i + 2 * n - 1].1]
                     .chars()
-                    .nth(actual_pos.saturating_sub(1))
-                    .map_or(false, |c| c.is_alphanumeric());
+                    .all(|c| c.is_alphanumeric() || c.is_whitespace())
+        });
+        let n = match matched {
+            Some(n) => n,
+            None => {
+                i += 1;
+                continue;
+            }
+        };
 
-            let word_end_pos = actual_pos + word.len();
-            let is_end_boundary = word_end_pos >= text.len()
-                || !text
-                    .chars()
-                    .nth(word_end_pos)
-                    .map_or(false, |c| c.is_alphanumeric());
+        issues.push(repeated_phrase_issue(
+            line,
+            line_idx,
+            words[i].0,
+            words[i + n].0,
+            words[i + 2 * n - 1].1,
+        ));
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+        i += 2 * n;
+    }
 
-            if is_start_boundary && is_end_boundary {
-                return Some(actual_pos);
+    // 中文：没有空格分词，按字比较相邻的两段，两段之间可以有空白
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let matched = (MIN_CHINESE_PHRASE_CHARS..=MAX_CHINESE_PHRASE_CHARS)
+            .rev()
+            .find_map(|len| {
+                let unit = chars.get(i..i + len)?;
+                if !unit.iter().all(|&(_, c)| traditional::is_chinese_char(c))
+                    || unit.iter().all(|&(_, c)| c == unit[0].1)
+                {
+                    return None;
+                }
+                let mut j = i + len;
+                while chars.get(j).is_some_and(|&(_, c)| c.is_whitespace()) {
+                    j += 1;
+                }
+                let next = chars.get(j..j + len)?;
+                if unit.iter().zip(next).any(|(a, b)| a.1 != b.1) {
+                    return None;
+                }
+                // 空格隔开的两个相同的词由 check_repeated_words 报告
+                let spaced = j > i + len;
+                let whole_words = (i == 0 || chars[i - 1].1.is_whitespace())
+                    && chars.get(j + len).is_none_or(|&(_, c)| c.is_whitespace());
+                if spaced && whole_words {
+                    return None;
+                }
+                Some((len, j))
+            });
+        let (len, j) = match matched {
+            Some(found) => found,
+            None => {
+                i += 1;
+                continue;
             }
+        };
 
-            // 安全地移动到下一个字符位置
-            search_start = actual_pos + word.chars().next().map_or(1, |c| c.len_utf8());
-        } else {
-            break;
+        let end = chars.get(j + len).map_or(line.len(), |&(pos, _)| pos);
+        issues.push(repeated_phrase_issue(
+            line, line_idx, chars[i].0, chars[j].0, end,
+        ));
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
         }
+        i = j + len;
     }
+}
+
+// 网址、邮箱、代码片段、文件路径和带分隔符的数字，这些内容中的标点不参与标点检查
+const TECHNICAL_TOKEN_PATTERNS: &[&str] = &[
+    r"`[^`]*`",
+    r"(?i)\b(?:https?|ftp)://[!-~]*[A-Za-z0-9/#=_-]",
+    r"(?i)\bwww\.[!-~]*[A-Za-z0-9/#=_-]",
+    r"[A-Za-z0-9_.+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)+",
+    r"(?:[A-Za-z]:\\|\.{0,2}/)?(?:[A-Za-z0-9_.-]+[/\\])+[A-Za-z0-9_.-]+",
+    r"(?i)\b[A-Za-z0-9_-]+\.(?:txt|md|docx?|pdf|rs|py|js|ts|tsx|json|ya?ml|toml|html?|css|csv|xlsx?|png|jpe?g)\b",
+    r"\d+(?:[.,:]\d+)+",
+];
+
+static TECHNICAL_TOKEN_REGEXES: OnceLock<Vec<Regex>> = OnceLock::new();
+
+// 技术内容的正则只在首次使用时编译一次
+fn technical_token_regexes() -> &'static [Regex] {
+    TECHNICAL_TOKEN_REGEXES.get_or_init(|| {
+        TECHNICAL_TOKEN_PATTERNS
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect()
+    })
+}
 
-    None
+// 把网址、文件路径、代码片段和 3.14 这类数字替换为等长的空格
+// 字符数保持不变，在结果上计算的字符位置仍对应原文
+fn strip_technical_tokens(line: &str) -> String {
+    let mut stripped = line.to_string();
+    for re in technical_token_regexes() {
+        stripped = re
+            .replace_all(&stripped, |caps: &regex::Captures| {
+                " ".repeat(caps[0].chars().count())
+            })
+            .into_owned();
+    }
+    stripped
 }
 
+// 中文标点与对应的英文标点
+const CHINESE_PUNCT_PAIRS: &[(char, char)] = &[
+    ('，', ','),
+    ('。', '.'),
+    ('！', '!'),
+    ('？', '?'),
+    ('；', ';'),
+    ('：', ':'),
+];
+
 fn check_punctuation(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
     // Skip if we've already found too many issues
     if issues.len() >= MAX_ISSUES {
         return;
     }
 
+    // 网址、路径、数字等技术内容中的标点不算
+    let stripped = strip_technical_tokens(line);
+
     // Check for mixed Chinese and English punctuation
-    // Use individual character checks instead of regex for Chinese punctuation
-    let has_chinese_punct = line.contains('，')
-        || line.contains('。')
-        || line.contains('！')
-        || line.contains('？')
-        || line.contains('；')
-        || line.contains('：');
-
-    // Use a simpler regex for English punctuation to avoid escaping issues
-    let en_punct_regex = match Regex::new(r"[,.!?;:]") {
-        Ok(re) => re,
-        Err(_) => return,
-    };
+    // 统计中英文标点，夹在字母数字之间的（如 e.g 这类缩写）也不算
+    let chars: Vec<char> = stripped.chars().collect();
+    let mut chinese_marks = Vec::new();
+    let mut english_marks = Vec::new();
+    for (idx, c) in chars.iter().enumerate() {
+        if CHINESE_PUNCT_PAIRS.iter().any(|(zh, _)| zh == c) {
+            chinese_marks.push(idx);
+        } else if CHINESE_PUNCT_PAIRS.iter().any(|(_, en)| en == c) {
+            let inside_token = idx > 0
+                && chars[idx - 1].is_ascii_alphanumeric()
+                && chars
+                    .get(idx + 1)
+                    .is_some_and(|c| c.is_ascii_alphanumeric());
+            if !inside_token {
+                english_marks.push(idx);
+            }
+        }
+    }
 
-    let has_english_punct = en_punct_regex.is_match(line);
+    if !chinese_marks.is_empty() && !english_marks.is_empty() {
+        // 以本行用得多的一种为准，逐个定位另一种标点
+        let has_chinese_text = chars.iter().any(|c| ('\u{4e00}'..='\u{9fff}').contains(c));
+        let chinese_main = chinese_marks.len() > english_marks.len()
+            || (chinese_marks.len() == english_marks.len() && has_chinese_text);
+        let (minority, main_name) = if chinese_main {
+            (&english_marks, "中文")
+        } else {
+            (&chinese_marks, "英文")
+        };
 
-    if has_chinese_punct && has_english_punct {
-        issues.push(TextIssue {
-            line_number: line_idx + 1,
-            start: 0,
-            end: line.len(),
-            issue_type: "标点混用".to_string(),
-            message: "中英文标点符号混用".to_string(),
-            suggestion: "请统一使用中文或英文标点符号".to_string(),
-        });
+        for &idx in minority {
+            let mark = chars[idx];
+            let replacement = CHINESE_PUNCT_PAIRS
+                .iter()
+                .find_map(|(zh, en)| {
+                    if chinese_main && *en == mark {
+                        Some(*zh)
+                    } else if !chinese_main && *zh == mark {
+                        Some(*en)
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(mark);
 
-        // Stop if we've found too many issues
-        if issues.len() >= MAX_ISSUES {
-            return;
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: idx,
+                end: idx + 1,
+                issue_type: "标点混用".to_string(),
+                message: format!("中英文标点符号混用: '{}'", mark),
+                suggestion: format!(
+                    "建议修改为: '{}'（本行主要使用{}标点）",
+                    replacement, main_name
+                ),
+                rule_id: "punctuation".to_string(),
+                replacement: Some(replacement.to_string()),
+                confidence: 1.0,
+                severity: "warning".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
         }
     }
 
@@ -454,19 +1006,88 @@ fn check_punctuation(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
         Err(_) => return,
     };
 
-    if let Some(mat) = consecutive_punct_regex.find(line) {
+    if let Some(mat) = consecutive_punct_regex.find(&stripped) {
         issues.push(TextIssue {
             line_number: line_idx + 1,
-            start: byte_to_char_index(line, mat.start()),
-            end: byte_to_char_index(line, mat.end()),
+            start: byte_to_char_index(&stripped, mat.start()),
+            end: byte_to_char_index(&stripped, mat.end()),
             issue_type: "连续标点".to_string(),
             message: "连续使用多个标点符号".to_string(),
             suggestion: "使用单个适当的标点符号".to_string(),
+            rule_id: "punctuation".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "warning".to_string(),
+            language: String::new(),
         });
     }
 }
 
-#[allow(dead_code)]
+// 被动语态中 "被" 作名词（被子）或固定词语的一部分时不算被动标记
+const NON_PASSIVE_BEI_NEXT: &[char] = &['子', '褥', '单', '套', '面', '窝', '里', '告', '动'];
+const NON_PASSIVE_BEI_PREV: &[char] = &['棉', '床', '盖', '绒', '丝', '毛', '锦'];
+
+// 以 -ed / -en 结尾但通常不是过去分词的词
+const NON_PARTICIPLES: &[&str] = &[
+    "open", "often", "even", "seven", "eleven", "then", "when", "ten", "men", "women", "children",
+    "garden", "kitchen", "heaven", "chicken", "golden", "wooden", "sudden", "red", "bed", "need",
+    "indeed", "hundred", "naked", "sacred", "wicked", "token", "citizen",
+];
+
+// 常见的不规则过去分词
+const IRREGULAR_PARTICIPLES: &[&str] = &[
+    "built",
+    "made",
+    "done",
+    "found",
+    "held",
+    "kept",
+    "left",
+    "lost",
+    "paid",
+    "said",
+    "sent",
+    "set",
+    "told",
+    "thought",
+    "brought",
+    "bought",
+    "caught",
+    "taught",
+    "put",
+    "cut",
+    "read",
+    "led",
+    "met",
+    "sold",
+    "spent",
+    "understood",
+    "won",
+    "shut",
+    "hit",
+    "hurt",
+    "felt",
+    "heard",
+    "meant",
+    "taken",
+    "seen",
+    "known",
+    "shown",
+    "written",
+    "given",
+    "chosen",
+    "driven",
+    "drawn",
+    "grown",
+    "thrown",
+    "spoken",
+    "broken",
+    "forgotten",
+    "hidden",
+    "begun",
+    "run",
+];
+
 fn check_passive_voice(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>, language: &str) {
     // Skip if we've already found too many issues
     if issues.len() >= MAX_ISSUES {
@@ -478,7 +1099,17 @@ fn check_passive_voice(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>,
         let passive_markers = ["被", "受到", "遭到", "遭受"];
 
         for marker in passive_markers {
-            if let Some(pos) = line.find(marker) {
+            for (pos, _) in line.match_indices(marker) {
+                if marker == "被" {
+                    let next = line[pos + marker.len()..].chars().next();
+                    let prev = line[..pos].chars().next_back();
+                    if next.is_some_and(|c| NON_PASSIVE_BEI_NEXT.contains(&c))
+                        || prev.is_some_and(|c| NON_PASSIVE_BEI_PREV.contains(&c))
+                    {
+                        continue;
+                    }
+                }
+
                 issues.push(TextIssue {
                     line_number: line_idx + 1,
                     start: byte_to_char_index(line, pos),
@@ -486,6 +1117,11 @@ fn check_passive_voice(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>,
                     issue_type: "被动语态".to_string(),
                     message: "使用了被动语态".to_string(),
                     suggestion: "考虑使用主动语态以增强表达力".to_string(),
+                    rule_id: "passive_voice".to_string(),
+                    replacement: None,
+                    confidence: 0.6,
+                    severity: "info".to_string(),
+                    language: String::new(),
                 });
 
                 // Stop if we've found too many issues
@@ -495,45 +1131,85 @@ fn check_passive_voice(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>,
             }
         }
     } else {
-        // English passive voice detection (simplified)
-        let be_verbs = ["is", "are", "was", "were", "be", "been", "being"];
-        let past_participles = ["ed", "en", "t"];
-
-        for be_verb in be_verbs {
-            if let Some(pos) = line.to_lowercase().find(be_verb) {
-                // Simple check for past participle after be verb
-                let after_be = &line[pos + be_verb.len()..];
-                let words_after: Vec<&str> = after_be.split_whitespace().collect();
-
-                if let Some(next_word) = words_after.first() {
-                    for suffix in past_participles {
-                        if next_word.to_lowercase().ends_with(suffix) {
-                            let end_pos = pos
-                                + be_verb.len()
-                                + after_be.find(next_word).unwrap_or(0)
-                                + next_word.len();
-                            issues.push(TextIssue {
-                                line_number: line_idx + 1,
-                                start: byte_to_char_index(line, pos),
-                                end: byte_to_char_index(line, end_pos),
-                                issue_type: "被动语态".to_string(),
-                                message: "检测到被动语态".to_string(),
-                                suggestion: "考虑使用主动语态以增强表达力".to_string(),
-                            });
-
-                            // Stop if we've found too many issues
-                            if issues.len() >= MAX_ISSUES {
-                                return;
-                            }
-                            break;
-                        }
-                    }
-                }
+        // English passive voice detection: be 动词（可隔一个副词）后接过去分词
+        let re = match Regex::new(
+            r"(?i)\b(?:am|is|are|was|were|be|been|being)\s+(?:[a-z]+ly\s+|not\s+)?([a-z]+)\b",
+        ) {
+            Ok(re) => re,
+            Err(_) => return,
+        };
+
+        for caps in re.captures_iter(line) {
+            let (whole, word) = match (caps.get(0), caps.get(1)) {
+                (Some(whole), Some(word)) => (whole, word),
+                _ => continue,
+            };
+            let lower = word.as_str().to_lowercase();
+            let is_participle = if NON_PARTICIPLES.contains(&lower.as_str()) {
+                false
+            } else {
+                IRREGULAR_PARTICIPLES.contains(&lower.as_str())
+                    || (lower.len() > 3 && (lower.ends_with("ed") || lower.ends_with("en")))
+            };
+            if !is_participle {
+                continue;
+            }
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, whole.start()),
+                end: byte_to_char_index(line, whole.end()),
+                issue_type: "被动语态".to_string(),
+                message: "检测到被动语态".to_string(),
+                suggestion: "考虑使用主动语态以增强表达力".to_string(),
+                rule_id: "passive_voice".to_string(),
+                replacement: None,
+                confidence: 0.6,
+                severity: "info".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
             }
         }
     }
 }
 
+// 语义重复的词对：(正则, 说明)，第 1 个捕获组是精简后保留的部分
+const REDUNDANT_PAIRS_EN: &[(&str, &str)] = &[
+    (
+        r"(?i)\b(?:very|quite|rather)\s+(unique)\b",
+        "unique 本身已表示独一无二，不受程度副词修饰",
+    ),
+    (
+        r"(?i)\b(?:more|most)\s+(better|best|worse|worst|easier|easiest|harder|hardest|simpler|simplest|bigger|biggest|smaller|smallest|larger|largest|faster|fastest)\b",
+        "比较级/最高级不需要再加 more/most",
+    ),
+    (r"(?i)\bfree\s+(gifts?)\b", "gift 本身就是免费的"),
+    (r"(?i)\badded\s+(bonus(?:es)?)\b", "bonus 本身就是额外的"),
+    (r"\b(ATMs?)\s+(?i:machines?)\b", "ATM 中已包含 machine"),
+    (r"\b(PINs?)\s+(?i:numbers?)\b", "PIN 中已包含 number"),
+    (r"(?i)\bend\s+(results?)\b", "result 本身就是最终的"),
+    (r"(?i)\bpast\s+(history)\b", "history 本身就是过去的"),
+    (
+        r"(?i)\b(revert(?:s|ed|ing)?)\s+back\b",
+        "revert 已包含 back 的意思",
+    ),
+];
+
+const REDUNDANT_PAIRS_ZH: &[(&str, &str)] = &[
+    (r"(首先)第一", "“首先”与“第一”意思重复"),
+    (
+        r"(大约[^，。！？；、,.!?;\s]{1,8}?)左右",
+        "“大约”与“左右”意思重复",
+    ),
+    (r"亲眼(目睹)", "“目睹”已包含亲眼看到的意思"),
+    (r"(凯旋)归来", "“凯旋”已包含归来的意思"),
+    (r"过分(溺爱)", "“溺爱”已包含过分的意思"),
+];
+
 fn check_redundant_expressions(
     line: &str,
     line_idx: usize,
@@ -579,6 +1255,52 @@ fn check_redundant_expressions(
                 issue_type: "冗余表达".to_string(),
                 message: format!("冗余表达: '{}'", phrase),
                 suggestion: suggestion.to_string(),
+                rule_id: "redundant_expressions".to_string(),
+                replacement: None,
+                confidence: 1.0,
+                severity: "info".to_string(),
+                language: String::new(),
+            });
+
+            // Stop if we've found too many issues
+            if issues.len() >= MAX_ISSUES {
+                return;
+            }
+        }
+    }
+
+    // 语义重复的词对，删去多余的部分即可
+    let redundant_pairs = if language == "zh" {
+        REDUNDANT_PAIRS_ZH
+    } else {
+        REDUNDANT_PAIRS_EN
+    };
+
+    for (pattern, explanation) in redundant_pairs {
+        let regex = match Regex::new(pattern) {
+            Ok(regex) => regex,
+            Err(_) => continue,
+        };
+
+        for caps in regex.captures_iter(line) {
+            let (whole, kept) = match (caps.get(0), caps.get(1)) {
+                (Some(whole), Some(kept)) => (whole, kept),
+                _ => continue,
+            };
+            let replacement = spelling_dict::match_case(whole.as_str(), kept.as_str());
+
+            issues.push(TextIssue {
+                line_number: line_idx + 1,
+                start: byte_to_char_index(line, whole.start()),
+                end: byte_to_char_index(line, whole.end()),
+                issue_type: "冗余表达".to_string(),
+                message: format!("冗余表达: '{}'", whole.as_str()),
+                suggestion: format!("建议修改为: '{}'，{}", replacement, explanation),
+                rule_id: "redundant_expressions".to_string(),
+                replacement: Some(replacement),
+                confidence: 0.9,
+                severity: "info".to_string(),
+                language: String::new(),
             });
 
             // Stop if we've found too many issues
@@ -628,6 +1350,11 @@ fn check_common_typos(
                 continue;
             }
 
+            // 本次会话中忽略的词不再提示
+            if feedback::is_session_ignored(clean_word) {
+                continue;
+            }
+
             // 检查单词是否在拼写错误字典中
             if let Some(correction) = spelling_dict::check_word_spelling(clean_word) {
                 // 检查是否已经检测到这个单词或其变体
@@ -644,6 +1371,11 @@ fn check_common_typos(
                             issue_type: "可能的拼写错误".to_string(),
                             message: format!("可能的拼写错误: '{}'", clean_word),
                             suggestion: format!("建议修改为: '{}'", correction),
+                            rule_id: "spelling".to_string(),
+                            replacement: None,
+                            confidence: 1.0,
+                            severity: "error".to_string(),
+                            language: String::new(),
                         });
 
                         // 添加到全局检测集合
@@ -927,6 +1659,11 @@ fn check_common_typos(
                     issue_type: "可能的拼写错误".to_string(),
                     message: format!("可能的拼写错误: '{}'", typo),
                     suggestion: format!("建议修改为: '{}'", correction),
+                    rule_id: "spelling".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "error".to_string(),
+                    language: String::new(),
                 });
 
                 // Stop if we've found too many issues
@@ -968,12 +1705,6 @@ fn check_grammar_issues(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>
         if issues.len() >= MAX_ISSUES {
             return;
         }
-
-        // Check article usage
-        check_article_usage(line, line_idx, issues);
-        if issues.len() >= MAX_ISSUES {
-            return;
-        }
     }
 }
 
@@ -1000,6 +1731,11 @@ fn check_de_usage(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
             issue_type: "语法错误".to_string(),
             message: "形容词后接动词应使用'地'而非'的'".to_string(),
             suggestion: "将'的'改为'地'".to_string(),
+            rule_id: "grammar".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "error".to_string(),
+            language: String::new(),
         });
 
         // Stop if we've found too many issues
@@ -1024,6 +1760,82 @@ fn check_de_usage(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
             issue_type: "语法错误".to_string(),
             message: "动词后接形容词应使用'得'而非'地'".to_string(),
             suggestion: "将'地'改为'得'".to_string(),
+            rule_id: "grammar".to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "error".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+
+    // Verb + "的" + complement, like "跑的快"
+    // 补语只取常见形容词，可带程度副词，保守触发；
+    // 补语后须是句末、标点或"了/极了"等，"做的好事"里的"的"修饰名词，不是补语
+    let de_complement_regex = match Regex::new(
+        r"[跑走看听说读写做想吃喝唱跳飞游睡学干玩讲洗打画](的)(?:很|非常|太|真|特别|十分|更|越来越)?(?:清楚|干净|漂亮|明白|仔细|认真|流利|熟练|整齐|[快慢好坏高低远近早晚])(?:极了|多了|了|一些|一点|[啊呀，。！？；、,.!?;\s]|$)",
+    ) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    for caps in de_complement_regex.captures_iter(line) {
+        let de = match caps.get(1) {
+            Some(de) => de,
+            None => continue,
+        };
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, de.start()),
+            end: byte_to_char_index(line, de.end()),
+            issue_type: "语法错误".to_string(),
+            message: "动词后接补语应使用'得'而非'的'".to_string(),
+            suggestion: "将'的'改为'得'".to_string(),
+            rule_id: "grammar".to_string(),
+            replacement: Some("得".to_string()),
+            confidence: 1.0,
+            severity: "error".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+
+    // Modifier + "地" + noun, like "美丽地风景"
+    // 名词只取常见词表，保守触发
+    let di_noun_regex = match Regex::new(
+        r"(?:美丽|漂亮|美好|伟大|幸福|快乐|安静|温暖|明亮|宽阔|广阔|辽阔|干净|整齐|可爱|聪明|勇敢|善良|高大|古老|新鲜|热闹|繁华|优美|壮丽|灿烂|丰富|优秀|良好|重要|严重|红色|绿色|蓝色|白色)(地)(?:风景|景色|天空|世界|城市|生活|日子|时光|花朵|房子|道路|人民|孩子|朋友|国家|校园|家乡|祖国|环境|心情|声音|故事|未来|成绩|作品|问题|结果|意义|作用)",
+    ) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    for caps in di_noun_regex.captures_iter(line) {
+        let di = match caps.get(1) {
+            Some(di) => di,
+            None => continue,
+        };
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, di.start()),
+            end: byte_to_char_index(line, di.end()),
+            issue_type: "语法错误".to_string(),
+            message: "修饰名词应使用'的'而非'地'".to_string(),
+            suggestion: "将'地'改为'的'".to_string(),
+            rule_id: "grammar".to_string(),
+            replacement: Some("的".to_string()),
+            confidence: 1.0,
+            severity: "error".to_string(),
+            language: String::new(),
         });
 
         // Stop if we've found too many issues
@@ -1055,9 +1867,100 @@ fn check_common_chinese_errors(line: &str, line_idx: usize, issues: &mut Vec<Tex
                 issue_type: "语法错误".to_string(),
                 message: "'把'字句可能缺少宾语".to_string(),
                 suggestion: "检查句子结构，确保'把'字后有完整的宾语和动作".to_string(),
+                rule_id: "grammar".to_string(),
+                replacement: None,
+                confidence: 1.0,
+                severity: "error".to_string(),
+                language: String::new(),
             });
         }
     }
+
+    // 标题行不是完整句子，不检查残句
+    if !structure::is_heading_line(line) {
+        check_chinese_fragments(line, line_idx, issues);
+    }
+}
+
+// 句首出现时通常需要前半句的连词
+const LEADING_CONJUNCTIONS: &[&str] = &[
+    "但是", "可是", "然而", "不过", "因此", "所以", "因而", "而且", "并且", "于是", "但", "而",
+];
+
+// 出现这些字词时认为句子含有谓语（动词、判断词、助词、副词等），不提示缺谓语
+const PREDICATE_MARKERS: &[&str] = &[
+    "是", "有", "在", "了", "着", "过", "为", "会", "能", "要", "可", "应", "需", "将", "被", "把",
+    "让", "使", "得", "不", "没", "很", "太", "更", "最", "都", "也", "就", "才", "又", "还", "已",
+    "正", "做", "作", "用", "进行", "提出", "表明", "显示", "说明", "认为", "发现", "采用", "包括",
+    "属于", "具有", "成为", "导致", "影响", "得到", "取得", "完成", "实现", "提高", "降低", "增加",
+    "减少", "来", "去", "到", "给", "看", "说", "想", "走",
+];
+
+// 残句启发式：以连词开头的过短句子、只有名词短语的句子
+fn check_chinese_fragments(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
+    let mut sentence_start = 0;
+    for (pos, c) in line.char_indices() {
+        if !matches!(c, '。' | '！' | '？') {
+            continue;
+        }
+        let raw = &line[sentence_start..pos];
+        let offset = sentence_start + (raw.len() - raw.trim_start().len());
+        let sentence = raw.trim();
+        let end = pos + c.len_utf8();
+        sentence_start = end;
+
+        let length = sentence.chars().count();
+        if length == 0 || sentence.contains(['，', ',', '：', '“', '"', '《']) {
+            continue;
+        }
+
+        let conjunction = LEADING_CONJUNCTIONS
+            .iter()
+            .find(|word| sentence.starts_with(**word));
+        let (message, suggestion) = if let Some(word) = conjunction {
+            // 连词之后的内容过短，多半是从上一句断开的半句话
+            if length - word.chars().count() > 6 {
+                continue;
+            }
+            (
+                format!("以'{}'开头的句子过短，可能是残句", word),
+                "检查是否缺少前半句，或与上一句合并".to_string(),
+            )
+        } else if c == '。'
+            && (4..=20).contains(&length)
+            && sentence.contains('的')
+            && !PREDICATE_MARKERS
+                .iter()
+                .any(|marker| sentence.contains(marker))
+        {
+            // 只有“……的……”这样的名词短语，没有动词或判断词
+            (
+                "句子可能缺少谓语".to_string(),
+                "检查句子是否只有名词短语，补充谓语使句子完整".to_string(),
+            )
+        } else {
+            continue;
+        };
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, offset),
+            end: byte_to_char_index(line, end),
+            issue_type: "句子成分".to_string(),
+            message,
+            suggestion,
+            rule_id: "grammar".to_string(),
+            replacement: None,
+            confidence: 0.4,
+            severity: "info".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
 }
 
 // Check English subject-verb agreement
@@ -1087,6 +1990,11 @@ fn check_subject_verb_agreement(line: &str, line_idx: usize, issues: &mut Vec<Te
                     issue_type: "语法错误".to_string(),
                     message: format!("主谓一致性错误: '{}' 与 '{}'", subject, verb),
                     suggestion: format!("对于单数主语 '{}' 应使用单数动词形式", subject),
+                    rule_id: "grammar".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "error".to_string(),
+                    language: String::new(),
                 });
 
                 // Stop if we've found too many issues
@@ -1105,27 +2013,113 @@ fn check_article_usage(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>)
         return;
     }
 
-    // Check article before vowel-starting words
-    let a_vowel_regex = match Regex::new(r"\ba\s+[aeiouAEIOU]\w+\b") {
+    let article_regex = match Regex::new(r"\b([Aa]n?)\s+([A-Za-z][A-Za-z'-]*)") {
         Ok(re) => re,
         Err(_) => return, // Return early if regex creation fails
     };
 
-    if let Some(mat) = a_vowel_regex.find(line) {
+    for caps in article_regex.captures_iter(line) {
+        let (article, word) = match (caps.get(1), caps.get(2)) {
+            (Some(article), Some(word)) => (article, word.as_str()),
+            _ => continue,
+        };
+        // 缩写和单个字母按字母读音，如 an FBI agent、a URL、an X-ray，不作判断
+        let first_part = word.split('-').next().unwrap_or(word);
+        if first_part.len() == 1 || first_part.chars().all(|c| c.is_ascii_uppercase()) {
+            continue;
+        }
+
+        let vowel_sound = starts_with_vowel_sound(word);
+        let is_an = article.as_str().len() == 2;
+        if vowel_sound == is_an {
+            continue;
+        }
+
+        let (correct, message) = if vowel_sound {
+            ("an", "元音读音开头的单词前应使用'an'而非'a'")
+        } else {
+            ("a", "辅音读音开头的单词前应使用'a'而非'an'")
+        };
+        let replacement = if article.as_str().starts_with('A') {
+            correct.replacen('a', "A", 1)
+        } else {
+            correct.to_string()
+        };
+
         issues.push(TextIssue {
             line_number: line_idx + 1,
-            start: byte_to_char_index(line, mat.start()),
-            end: byte_to_char_index(line, mat.start() + 1),
+            start: byte_to_char_index(line, article.start()),
+            end: byte_to_char_index(line, article.end()),
             issue_type: "冠词错误".to_string(),
-            message: "元音开头的单词前应使用'an'而非'a'".to_string(),
-            suggestion: "将'a'替换为'an'".to_string(),
+            message: format!("{}: {} {}", message, article.as_str(), word),
+            suggestion: format!("建议修改为: '{}'", replacement),
+            rule_id: "article_usage".to_string(),
+            replacement: Some(replacement),
+            confidence: 1.0,
+            severity: "error".to_string(),
+            language: String::new(),
         });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
+
+// h 不发音、以元音读音开头的词（前缀匹配）
+const SILENT_H_PREFIXES: &[&str] = &["hour", "honest", "honor", "honour", "heir"];
+
+// 元音字母开头但以辅音读音开头的词（前缀匹配）：u 读 /ju/、eu 读 /ju/、one 读 /w/
+const CONSONANT_SOUND_PREFIXES: &[&str] = &[
+    "union",
+    "unique",
+    "unit",
+    "univers",
+    "uniform",
+    "unicorn",
+    "unilateral",
+    "unison",
+    "unanim",
+    "usual",
+    "usage",
+    "use",
+    "using",
+    "usur",
+    "utensil",
+    "utili",
+    "utopia",
+    "uranium",
+    "urin",
+    "ukrain",
+    "ubiquit",
+    "eu",
+    "ewe",
+    "one",
+    "once",
+];
+
+// 判断英文单词的读音是否以元音开头
+fn starts_with_vowel_sound(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    if SILENT_H_PREFIXES
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+    {
+        return true;
+    }
+    if CONSONANT_SOUND_PREFIXES
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+    {
+        return false;
     }
+    lower.starts_with(['a', 'e', 'i', 'o', 'u'])
 }
 
 // Read file content with support for different document formats
 #[tauri::command]
-fn read_file_content(path: &str) -> Result<String, String> {
+fn read_file_content(path: &str) -> Result<FileContent, String> {
     // Check if file exists
     let path_obj = Path::new(path);
     if !path_obj.exists() {
@@ -1139,54 +2133,75 @@ fn read_file_content(path: &str) -> Result<String, String> {
     };
 
     // Check if file is too large
-    if metadata.len() > MAX_FILE_SIZE {
+    let limits = config::current_config().limits;
+    if metadata.len() > limits.max_file_size {
         return Err(format!(
             "文件过大，请选择小于{}MB的文件",
-            MAX_FILE_SIZE / 1_000_000
+            limits.max_file_size / 1_000_000
         ));
     }
 
     // 检测文件类型并使用相应的解析器
     let file_type = document_parser::detect_file_type(path);
 
-    let content = match file_type.as_str() {
-        "docx" | "doc" => {
-            // 使用文档解析器处理Word文档
-            document_parser::parse_document(path)?
+    let (content, encoding) = match file_type.as_str() {
+        "docx" | "doc" | "pdf" | "odt" | "rtf" => {
+            // 使用文档解析器处理Word/ODT/RTF文档和PDF
+            (document_parser::parse_document(path)?, None)
         }
         _ => {
-            // 对于其他文件类型，尝试使用文档解析器（支持多种编码）
-            match document_parser::parse_document(path) {
-                Ok(content) => content,
-                Err(_) => {
-                    // 如果文档解析器失败，回退到原始方法
-                    match std::fs::read_to_string(path_obj) {
-                        Ok(content) => content,
-                        Err(e) => return Err(format!("读取文件失败: {}", e)),
-                    }
-                }
-            }
+            // 纯文本文件（包括JSON/YAML配置文件）自动探测编码（UTF-8、GBK、UTF-16 等），
+            // 原样返回，保证编辑器中的行号与原文件一致
+            let (content, encoding) = document_parser::read_text_file(path)?;
+            (content, Some(encoding.to_string()))
         }
     };
 
-    // If content is too large, truncate it (UTF-8 safe)
-    if content.chars().count() > MAX_TEXT_LENGTH {
-        let truncated = truncate_string_safe(&content, MAX_TEXT_LENGTH).to_string();
-        Ok(truncated)
+    // If content is too large, truncate it at the last complete line (UTF-8 safe)
+    let content = if content.chars().count() > limits.max_text_length {
+        truncate_at_line_boundary(&content, limits.max_text_length).to_string()
     } else {
-        Ok(content)
+        content
+    };
+
+    Ok(FileContent { content, encoding })
+}
+
+// 另一种语言的占比达到该值时视为中英混排的行
+const DEFAULT_MIXED_LANGUAGE_THRESHOLD: f32 = 0.1;
+
+/// 一段文字中汉字和英文字母各自的占比
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LanguageMix {
+    pub chinese_ratio: f32,
+    pub english_ratio: f32,
+}
+
+impl LanguageMix {
+    /// 主要语言（"zh" / "en"），数量相同时视为英文
+    pub fn primary(&self) -> &'static str {
+        if self.chinese_ratio > self.english_ratio {
+            "zh"
+        } else {
+            "en"
+        }
+    }
+
+    /// 次要语言的占比不低于阈值时视为中英混排
+    pub fn is_mixed(&self, threshold: f32) -> bool {
+        let minor = self.chinese_ratio.min(self.english_ratio);
+        minor > 0.0 && minor >= threshold
     }
 }
 
-// Auto-detect text language
-fn detect_language(text: &str) -> String {
-    // Count Chinese and English characters
+// Count the share of Chinese characters and English letters
+fn detect_language_mix(text: &str) -> LanguageMix {
     let mut chinese_count = 0;
     let mut english_count = 0;
 
     for c in text.chars() {
-        if c >= '\u{4e00}' && c <= '\u{9fff}' {
-            // Chinese character range
+        if traditional::is_chinese_char(c) {
+            // Chinese character range, including traditional and extension characters
             chinese_count += 1;
         } else if c.is_ascii_alphabetic() {
             // English letters
@@ -1194,14 +2209,24 @@ fn detect_language(text: &str) -> String {
         }
     }
 
-    // Determine language based on character count
-    if chinese_count > english_count {
-        "zh".to_string()
-    } else {
-        "en".to_string()
+    let total = (chinese_count + english_count) as f32;
+    if total == 0.0 {
+        return LanguageMix {
+            chinese_ratio: 0.0,
+            english_ratio: 0.0,
+        };
+    }
+    LanguageMix {
+        chinese_ratio: chinese_count as f32 / total,
+        english_ratio: english_count as f32 / total,
     }
 }
 
+// Auto-detect text language
+fn detect_language(text: &str) -> String {
+    detect_language_mix(text).primary().to_string()
+}
+
 // Process large file in chunks with document format support
 #[tauri::command]
 fn analyze_large_file(path: &str) -> Result<AnalysisResult, String> {
@@ -1218,10 +2243,11 @@ fn analyze_large_file(path: &str) -> Result<AnalysisResult, String> {
     };
 
     // Check if file is too large
-    if metadata.len() > MAX_FILE_SIZE {
+    let limits = config::current_config().limits;
+    if metadata.len() > limits.max_file_size {
         return Err(format!(
             "文件过大，请选择小于{}MB的文件",
-            MAX_FILE_SIZE / 1_000_000
+            limits.max_file_size / 1_000_000
         ));
     }
 
@@ -1229,10 +2255,10 @@ fn analyze_large_file(path: &str) -> Result<AnalysisResult, String> {
     let file_type = document_parser::detect_file_type(path);
 
     match file_type.as_str() {
-        "docx" | "doc" => {
-            // 对于Word文档，先解析为文本再分析
+        "docx" | "doc" | "pdf" | "odt" | "rtf" | "json" | "yaml" | "yml" => {
+            // 对于Word文档、PDF和配置文件，先解析为文本再分析
             let content = document_parser::parse_document(path)?;
-            Ok(analyze_text(&content))
+            Ok(analyze(&content))
         }
         _ => {
             // 对于纯文本文件，使用流式读取
@@ -1248,70 +2274,103 @@ fn analyze_text_file_streaming(path: &Path) -> Result<AnalysisResult, String> {
         Err(e) => return Err(format!("无法打开文件: {}", e)),
     };
 
-    let reader = BufReader::new(file);
+    analyze_reader_streaming(BufReader::new(file), &config::current_config())
+}
+
+// 逐行读取并分块分析，每行的行下标在读取时确定，分块边界不影响行号
+fn analyze_reader_streaming<R: BufRead>(
+    reader: R,
+    config: &AnalysisConfig,
+) -> Result<AnalysisResult, String> {
+    let limits = &config.limits;
     let mut issues = Vec::new();
     let mut stats = HashMap::new();
     let mut truncated = false;
+    // 拼写错误的去重跨越各个块
+    let mut reported_words = HashSet::new();
 
     // Count statistics
     let mut total_chars = 0;
     let mut total_words = 0;
     let mut total_lines = 0;
 
-    // Process file in chunks
-    let mut line_idx = 0;
-    let mut chunk = String::new();
+    // Process file in chunks: (行下标, 行内容)
+    let mut chunk: Vec<(usize, String)> = Vec::new();
     let mut chunk_size = 0;
+    // 上一块的最后一行，用于判断本块首行是否为折行的延续
+    let mut prev_line: Option<String> = None;
+
+    for (line_idx, line_result) in reader.lines().enumerate() {
+        let line = line_result.map_err(|e| format!("读取文件行时出错: {}", e))?;
+        total_lines += 1;
+        total_chars += line.chars().count();
+        total_words += line.split_whitespace().count();
+
+        chunk_size += line.len() + 1;
+        chunk.push((line_idx, line));
+
+        // Process chunk when it reaches the limit
+        if chunk_size >= limits.max_text_length / 10 {
+            let lines: Vec<(usize, &str)> = chunk
+                .iter()
+                .map(|(idx, line)| (*idx, line.as_str()))
+                .collect();
+            process_lines(
+                &lines,
+                prev_line.as_deref(),
+                &mut issues,
+                &mut truncated,
+                config,
+                &mut reported_words,
+            );
+            prev_line = chunk.pop().map(|(_, line)| line);
+            chunk.clear();
+            chunk_size = 0;
 
-    for line_result in reader.lines() {
-        match line_result {
-            Ok(line) => {
-                total_lines += 1;
-                total_chars += line.chars().count();
-                total_words += line.split_whitespace().count();
-
-                chunk.push_str(&line);
-                chunk.push('\n');
-                chunk_size += line.len() + 1;
-
-                // Process chunk when it reaches the limit
-                if chunk_size >= MAX_TEXT_LENGTH / 10 || issues.len() >= MAX_ISSUES {
-                    process_text_chunk(&chunk, line_idx, &mut issues, &mut truncated);
-                    line_idx += chunk.lines().count();
-                    chunk.clear();
-                    chunk_size = 0;
-
-                    // Stop if we've found too many issues
-                    if issues.len() >= MAX_ISSUES {
-                        truncated = true;
-                        break;
-                    }
-                }
+            // Stop if we've found too many issues
+            if issues.len() >= limits.max_issues {
+                truncated = true;
+                break;
             }
-            Err(e) => return Err(format!("读取文件行时出错: {}", e)),
         }
     }
 
     // Process remaining chunk
-    if !chunk.is_empty() && issues.len() < MAX_ISSUES {
-        process_text_chunk(&chunk, line_idx, &mut issues, &mut truncated);
+    if !chunk.is_empty() && issues.len() < limits.max_issues {
+        let lines: Vec<(usize, &str)> = chunk
+            .iter()
+            .map(|(idx, line)| (*idx, line.as_str()))
+            .collect();
+        process_lines(
+            &lines,
+            prev_line.as_deref(),
+            &mut issues,
+            &mut truncated,
+            config,
+            &mut reported_words,
+        );
     }
 
     // Update statistics
     stats.insert("total_chars".to_string(), total_chars);
     stats.insert("total_words".to_string(), total_words);
     stats.insert("total_lines".to_string(), total_lines);
+    sort_and_dedup_issues(&mut issues);
 
     // Limit the number of issues returned
-    if issues.len() > MAX_ISSUES {
-        issues.truncate(MAX_ISSUES);
+    if issues.len() > limits.max_issues {
+        issues.truncate(limits.max_issues);
         truncated = true;
     }
 
+    insert_language_stats(&issues, &mut stats);
+
     Ok(AnalysisResult {
+        issue_counts: count_issue_types(&issues),
         issues,
         stats,
         truncated,
+        next_offset_line: None,
     })
 }
 
@@ -1353,14 +2412,16 @@ async fn perform_async_analysis(
     window: tauri::Window,
     _analysis_id: String,
 ) -> Result<AnalysisResult, String> {
+    let config = config::current_config();
+    let limits = &config.limits;
     let mut issues = Vec::new();
     let mut stats = HashMap::new();
     let mut truncated = false;
 
-    // Limit text size to prevent crashes (UTF-8 safe)
-    let text = if text.chars().count() > MAX_TEXT_LENGTH {
+    // Limit text size to prevent crashes (UTF-8 safe, ends at a complete line)
+    let text = if text.chars().count() > limits.max_text_length {
         truncated = true;
-        truncate_string_safe(&text, MAX_TEXT_LENGTH).to_string()
+        truncate_at_line_boundary(&text, limits.max_text_length).to_string()
     } else {
         text
     };
@@ -1376,6 +2437,8 @@ async fn perform_async_analysis(
 
     // 分块处理文本，每处理一定行数就报告进度
     let lines: Vec<&str> = text.lines().collect();
+    // 拼写错误的去重跨越各个块
+    let mut reported_words = HashSet::new();
     let chunk_size = 50; // 每50行报告一次进度
 
     for (chunk_idx, chunk) in lines.chunks(chunk_size).enumerate() {
@@ -1400,10 +2463,18 @@ async fn perform_async_analysis(
 
         // 处理当前块
         let chunk_text = chunk.join("\n");
-        process_text_chunk(&chunk_text, current_line, &mut issues, &mut truncated);
+        process_text_chunk(
+            &chunk_text,
+            current_line,
+            current_line.checked_sub(1).map(|idx| lines[idx]),
+            &mut issues,
+            &mut truncated,
+            &config,
+            &mut reported_words,
+        );
 
         // 检查是否超过最大问题数
-        if issues.len() >= MAX_ISSUES {
+        if issues.len() >= limits.max_issues {
             truncated = true;
             break;
         }
@@ -1411,47 +2482,445 @@ async fn perform_async_analysis(
         // 添加小延迟以避免阻塞UI
         tokio::time::sleep(Duration::from_millis(10)).await;
     }
+    run_document_checks(&text, &config, &mut issues);
+    sort_and_dedup_issues(&mut issues);
 
     // Limit the number of issues returned
-    if issues.len() > MAX_ISSUES {
-        issues.truncate(MAX_ISSUES);
+    if issues.len() > limits.max_issues {
+        issues.truncate(limits.max_issues);
         truncated = true;
     }
 
+    insert_language_stats(&issues, &mut stats);
+
     Ok(AnalysisResult {
+        issue_counts: count_issue_types(&issues),
         issues,
         stats,
         truncated,
+        next_offset_line: None,
     })
 }
 
+// 把当前检查配置导出为 JSON 文件，便于团队共享
+#[tauri::command]
+fn export_config(path: &str) -> Result<(), String> {
+    config::export_to_file(path)
+}
+
+// 从 JSON 文件导入检查配置
+#[tauri::command]
+fn import_config(path: &str) -> Result<(), String> {
+    config::import_from_file(path)
+}
+
+// 加载团队共享的忽略配置（TOML 或 JSON）：忽略词和检查开关
+#[tauri::command]
+fn load_config(path: &str) -> Result<(), String> {
+    config::load_config(path)
+}
+
+// 把检查配置保存为命名档案（如“商业文案”），持久化到应用数据目录
+#[tauri::command]
+fn save_profile(name: &str, config: AnalysisConfig) -> Result<(), String> {
+    config::save_profile(name, config)
+}
+
+// 切换到命名档案，内置“通用”“学术”两个档案
+#[tauri::command]
+fn apply_profile(name: &str) -> Result<(), String> {
+    config::apply_profile(name)
+}
+
+// 列出可用的配置档案名
+#[tauri::command]
+fn list_profiles() -> Result<Vec<String>, String> {
+    config::list_profiles()
+}
+
+// 记录用户标记的误报，用于后续调优规则和词表
+#[tauri::command]
+fn report_false_positive(issue: TextIssue) -> Result<(), String> {
+    feedback::record_false_positive(&issue)
+}
+
+// 按规则汇总误报次数
+#[tauri::command]
+fn false_positive_stats() -> Result<Vec<feedback::FalsePositiveStat>, String> {
+    feedback::false_positive_stats()
+}
+
+// 评估文本可读性，按文本主要语言选择中文或英文的评估方式
+#[tauri::command]
+fn readability_score(text: &str) -> readability::ReadabilityReport {
+    let text = truncate_string_safe(text, config::current_config().limits.max_text_length);
+    readability::readability_score(text, &detect_language(text))
+}
+
+// 按章节分别统计问题，便于查看长文档各章的问题分布
+#[tauri::command]
+fn analyze_by_section(text: &str) -> Vec<structure::SectionResult> {
+    let result = analyze(text);
+    structure::group_by_section(text, &result.issues)
+}
+
+// 为拼写错误的单词返回排序后的候选纠正
+#[tauri::command]
+fn suggest_corrections(word: &str) -> Vec<String> {
+    spelling_dict::suggest_corrections(word)
+}
+
+// 记录用户采纳的纠正，用于个性化候选排序
+#[tauri::command]
+fn record_accepted_correction(wrong: &str, chosen: &str) -> Result<(), String> {
+    feedback::record_accepted_correction(wrong, chosen)
+}
+
+// 一键把全文标点统一为中文或英文风格
+#[tauri::command]
+fn normalize_punctuation(text: &str, target: &str) -> String {
+    normalize::normalize_punctuation(text, target)
+}
+
+// 返回按行组织的分析结果，供前端做富文本渲染
+#[tauri::command]
+fn analyze_structured(text: &str) -> structure::StructuredResult {
+    let result = analyze(text);
+    structure::build_structured(text, result)
+}
+
+// 预览应用选中修复后的改动，返回 unified diff
+// issues 为分析返回的问题列表，accepted 为用户接受的问题在其中的下标
+#[tauri::command]
+fn preview_fixes(text: &str, issues: Vec<TextIssue>, accepted: Vec<usize>) -> String {
+    let fixed = fixes::apply_fixes(text, &issues, &accepted);
+    fixes::unified_diff(text, &fixed)
+}
+
+// 分析文本并以 LSP 诊断格式返回结果，便于接入编辑器
+#[tauri::command]
+fn lsp_diagnostics(text: &str) -> Vec<lsp::LspDiagnostic> {
+    let result = analyze(text);
+    lsp::to_lsp_diagnostics(text, &result)
+}
+
+// 按严重程度、置信度和是否可自动修复排序，最值得修改的问题排在前面
+#[tauri::command]
+fn analyze_ranked(text: &str) -> Vec<TextIssue> {
+    let weights = config::current_config().ranking_weights;
+    let mut issues = analyze(text).issues;
+    // 分数相同时保持原有的行号顺序
+    issues.sort_by(|a, b| weights.score(b).total_cmp(&weights.score(a)));
+    issues
+}
+
+// 把单词加入用户词典，重启后依然生效
+#[tauri::command]
+fn add_user_words(words: Vec<String>) -> Result<(), String> {
+    dictionary::add_user_words(&words)
+}
+
+// 重新读取 English.dic 和用户词典，修改词典文件后无需重启
+#[tauri::command]
+fn reload_dictionary() -> Result<(), String> {
+    dictionary::reload_dictionary()
+}
+
+// 在本次会话中忽略某个词（含大小写和单复数变形），重启应用后失效
+#[tauri::command]
+fn ignore_word_for_session(word: &str) {
+    feedback::ignore_word_for_session(word);
+}
+
+// 应用单个问题的建议修改，返回修改后的全文和修改记录
+#[tauri::command]
+fn apply_fix(text: &str, issue: TextIssue) -> fixes::FixResult {
+    fixes::apply_fix(text, &issue)
+}
+
+// 应用所有可自动修复的问题，返回修改后的全文和修改记录
+#[tauri::command]
+fn apply_all_fixes(text: &str, issues: Vec<TextIssue>) -> fixes::FixResult {
+    fixes::apply_all_fixes(text, &issues)
+}
+
+// 按 apply_fix 返回的修改记录还原文本，用于撤销
+#[tauri::command]
+fn revert_fixes(text: &str, applied: Vec<(usize, usize, String)>) -> String {
+    fixes::revert_fixes(text, &applied)
+}
+
+// 把分析结果导出为 Markdown 报告
+#[tauri::command]
+fn export_report_markdown(result: AnalysisResult) -> String {
+    export::report_markdown(&result)
+}
+
+// 把分析结果导出为 CSV，便于在 Excel 中分析
+#[tauri::command]
+fn export_report_csv(result: AnalysisResult) -> String {
+    export::report_csv(&result)
+}
+
+// 查询某条规则当前是否启用
+#[tauri::command]
+fn is_rule_enabled(rule_id: &str) -> bool {
+    config::current_config().is_rule_enabled(rule_id)
+}
+
+// 开关某条规则，如被动语态提示
+#[tauri::command]
+fn set_rule_enabled(rule_id: &str, enabled: bool) {
+    config::set_rule_enabled(rule_id, enabled)
+}
+
+// 按 Markdown 语法分析文本，代码块、行内代码和链接地址不参与检查
+#[tauri::command]
+fn analyze_markdown(text: &str) -> AnalysisResult {
+    let masked = markdown::mask_markdown(text);
+    let mut result = analyze(&masked.text);
+
+    // 遮盖处的连续空格等问题不是原文的问题
+    result.issues.retain(|issue| !masked.is_skipped(issue));
+    result.issue_counts = count_issue_types(&result.issues);
+    insert_language_stats(&result.issues, &mut result.stats);
+    result
+}
+
+// 增量检查：只重新检查变更的行（行号从 1 开始，包含两端），返回的问题仍使用全局行号
+// 文档级检查需要全文，增量检查不运行
+#[tauri::command]
+fn analyze_lines(text: &str, changed_line_range: (usize, usize)) -> Vec<TextIssue> {
+    let (first, last) = changed_line_range;
+    let lines: Vec<&str> = text.lines().collect();
+    let last = last.min(lines.len());
+    if first == 0 || first > last {
+        return Vec::new();
+    }
+
+    let config = config::current_config();
+    let chunk = lines[first - 1..last].join("\n");
+    let mut issues = Vec::new();
+    let mut truncated = false;
+    process_text_chunk(
+        &chunk,
+        first - 1,
+        first.checked_sub(2).map(|idx| lines[idx]),
+        &mut issues,
+        &mut truncated,
+        &config,
+        &mut HashSet::new(),
+    );
+    sort_and_dedup_issues(&mut issues);
+    issues
+}
+
+// 把分析结果保存为 JSON 文件，便于脚本做批量质检统计
+#[tauri::command]
+fn save_analysis_json(result: AnalysisResult, out_path: &str) -> Result<(), String> {
+    export::save_json(&result, out_path)
+}
+
+// 批量检查时默认处理的文件类型
+const DEFAULT_BATCH_EXTENSIONS: &[&str] = &["txt", "md", "docx", "doc", "pdf", "odt", "rtf"];
+
+// 递归收集目录下扩展名匹配的文件，按路径排序；不跟随符号链接，避免循环
+// 无法读取的子目录带上原因一并返回，其余文件照常收集；只有根目录无法读取时才返回错误
+fn collect_files(
+    dir: &Path,
+    extensions: &[String],
+) -> Result<Vec<(std::path::PathBuf, Option<String>)>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let entries = match std::fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(e) if current == dir => {
+                return Err(format!("无法读取目录 {}: {}", current.display(), e));
+            }
+            Err(e) => {
+                files.push((current, Some(format!("无法读取目录: {}", e))));
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => pending.push(path),
+                Ok(file_type) if file_type.is_file() => {
+                    let extension = document_parser::detect_file_type(&path.to_string_lossy());
+                    if extensions.contains(&extension) {
+                        files.push((path, None));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+// 检查单个文件，失败时返回跳过原因
+fn analyze_batch_file(path: &Path, max_file_size: u64) -> Result<AnalysisResult, String> {
+    let metadata = std::fs::metadata(path).map_err(|e| format!("无法读取文件元数据: {}", e))?;
+    if metadata.len() > max_file_size {
+        return Err(format!(
+            "文件过大（超过{}MB），已跳过",
+            max_file_size / 1_000_000
+        ));
+    }
+
+    let content = document_parser::parse_document(&path.to_string_lossy())?;
+    Ok(analyze(&content))
+}
+
+// 批量检查结果：(相对路径, 检查结果或跳过原因)
+type DirectoryResults = Vec<(String, Result<AnalysisResult, String>)>;
+
+// 批量检查整个目录（递归），返回每个文件的相对路径和检查结果
+// 单个文件过大、解析失败或子目录无法读取时记录原因并继续处理其余文件；extensions 为空时检查所有支持的格式
+#[tauri::command]
+fn analyze_directory(dir: &str, extensions: Vec<String>) -> Result<DirectoryResults, String> {
+    let root = Path::new(dir);
+    if !root.is_dir() {
+        return Err(format!("目录不存在: {}", root.display()));
+    }
+
+    let extensions: Vec<String> = if extensions.is_empty() {
+        DEFAULT_BATCH_EXTENSIONS
+            .iter()
+            .map(|ext| ext.to_string())
+            .collect()
+    } else {
+        extensions
+            .iter()
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .collect()
+    };
+    let max_file_size = config::current_config().limits.max_file_size;
+
+    let results = collect_files(root, &extensions)?
+        .into_iter()
+        .map(|(path, unreadable)| {
+            let name = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .into_owned();
+            let result = match unreadable {
+                Some(reason) => Err(reason),
+                None => analyze_batch_file(&path, max_file_size),
+            };
+            (name, result)
+        })
+        .collect();
+
+    Ok(results)
+}
+
+// 从 "错词\t正确词" 格式的文件加载自定义拼写纠正对，返回加载的条数
+#[tauri::command]
+fn load_custom_typos(path: &str) -> Result<usize, String> {
+    spelling_dict::load_custom_typos(path)
+}
+
+// 添加一条自定义拼写纠正规则，重启后仍生效
+#[tauri::command]
+fn add_typo_rule(wrong: &str, correct: &str) -> Result<(), String> {
+    if wrong.trim().is_empty() || correct.trim().is_empty() {
+        return Err("错词和正确词都不能为空".to_string());
+    }
+    let pair = std::collections::BTreeMap::from([(wrong.to_string(), correct.to_string())]);
+    spelling_dict::add_custom_typos(&pair)
+}
+
+// 添加一条成语错别字规则，重启后仍生效
+#[tauri::command]
+fn add_idiom_rule(wrong: &str, correct: &str, explanation: &str) -> Result<(), String> {
+    fix_functions::add_idiom_rule(wrong, correct, explanation)
+}
+
+// 把原文渲染为带问题高亮的 HTML，鼠标悬停可查看问题说明
+#[tauri::command]
+fn render_highlighted_html(text: &str, issues: Vec<TextIssue>) -> String {
+    export::render_highlighted_html(text, &issues)
+}
+
+// 统计词典未收录的词及出现次数（按次数降序），用于扩充词典
+#[tauri::command]
+fn collect_unknown_words(text: &str) -> Vec<(String, usize)> {
+    let text = truncate_string_safe(text, config::current_config().limits.max_text_length);
+    improved_checker::collect_unknown_words(text)
+}
+
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .invoke_handler(tauri::generate_handler![
             analyze_text,
+            analyze_text_with_config,
+            analyze_text_paged,
             analyze_text_async,
             read_file_content,
             analyze_large_file,
-            batch_spell_check
+            batch_spell_check,
+            export_config,
+            import_config,
+            load_config,
+            save_profile,
+            apply_profile,
+            list_profiles,
+            report_false_positive,
+            false_positive_stats,
+            readability_score,
+            analyze_by_section,
+            suggest_corrections,
+            record_accepted_correction,
+            normalize_punctuation,
+            analyze_structured,
+            preview_fixes,
+            lsp_diagnostics,
+            analyze_ranked,
+            add_user_words,
+            reload_dictionary,
+            ignore_word_for_session,
+            apply_fix,
+            apply_all_fixes,
+            revert_fixes,
+            export_report_markdown,
+            export_report_csv,
+            is_rule_enabled,
+            set_rule_enabled,
+            analyze_markdown,
+            analyze_lines,
+            save_analysis_json,
+            analyze_directory,
+            load_custom_typos,
+            add_typo_rule,
+            add_idiom_rule,
+            render_highlighted_html,
+            collect_unknown_words
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+// 常见的正常重复字符组合，不应该被标记为错误
+const NORMAL_CHINESE_REPEATS: &[&str] = &[
+    "文文", "本本", "人人", "个个", "家家", "天天", "年年", "月月", "日日", "时时", "处处", "事事",
+    "样样", "种种", "步步", "层层", "点点", "面面", "线线", "片片", "块块", "条条", "根根", "张张",
+    "页页", "章章", "节节", "段段", "句句", "字字", "词词", "声声", "色色", "形形", "式式", "类类",
+    "项项", "件件", "套套", "组组", "批批", "群群", "队队", "班班", "级级", "届届", "期期", "次次",
+    "回回", "遍遍", "趟趟", "场场", "局局", "轮轮", "代代", "世世", "辈辈", "头头", "只只", "匹匹",
+    "尾尾",
+];
+
 // 改进的中文重复字符检测，避免误报
 fn check_chinese_repeated_chars_improved(line: &str, line_idx: usize, issues: &mut Vec<TextIssue>) {
-    // 常见的正常重复字符组合，不应该被标记为错误
-    let normal_repeats = [
-        "文文", "本本", "人人", "个个", "家家", "天天", "年年", "月月", "日日", "时时", "处处",
-        "事事", "样样", "种种", "步步", "层层", "点点", "面面", "线线", "片片", "块块", "条条",
-        "根根", "张张", "页页", "章章", "节节", "段段", "句句", "字字", "词词", "声声", "色色",
-        "形形", "式式", "类类", "项项", "件件", "套套", "组组", "批批", "群群", "队队", "班班",
-        "级级", "届届", "期期", "次次", "回回", "遍遍", "趟趟", "场场", "局局", "轮轮", "代代",
-        "世世", "辈辈", "头头", "只只", "匹匹", "尾尾",
-    ];
-
     let chars: Vec<char> = line.chars().collect();
     let mut i = 0;
 
@@ -1461,7 +2930,7 @@ fn check_chinese_repeated_chars_improved(line: &str, line_idx: usize, issues: &m
             let repeated_pair = format!("{}{}", chars[i], chars[i]);
 
             // 如果是正常的重复组合，跳过
-            if normal_repeats.contains(&repeated_pair.as_str()) {
+            if NORMAL_CHINESE_REPEATS.contains(&repeated_pair.as_str()) {
                 i += 2;
                 continue;
             }
@@ -1484,6 +2953,11 @@ fn check_chinese_repeated_chars_improved(line: &str, line_idx: usize, issues: &m
                     issue_type: "重复字符".to_string(),
                     message: format!("可能的重复字符: '{}{}'", chars[i], chars[i]),
                     suggestion: format!("检查是否需要删除重复的 '{}'", chars[i]),
+                    rule_id: "spelling".to_string(),
+                    replacement: None,
+                    confidence: 1.0,
+                    severity: "error".to_string(),
+                    language: String::new(),
                 });
 
                 if issues.len() >= MAX_ISSUES {
@@ -1529,3 +3003,355 @@ fn check_if_part_of_normal_word(_line: &str, char_index: usize, chars: &[char])
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(
+        line_number: usize,
+        start: usize,
+        end: usize,
+        issue_type: &str,
+        rule_id: &str,
+    ) -> TextIssue {
+        TextIssue {
+            line_number,
+            start,
+            end,
+            issue_type: issue_type.to_string(),
+            message: String::new(),
+            suggestion: String::new(),
+            rule_id: rule_id.to_string(),
+            replacement: None,
+            confidence: 1.0,
+            severity: "warning".to_string(),
+            language: String::new(),
+        }
+    }
+
+    // 用于比较两次分析结果的问题摘要
+    fn issue_keys(result: &AnalysisResult) -> Vec<(usize, usize, usize, String, String, String)> {
+        result
+            .issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.line_number,
+                    issue.start,
+                    issue.end,
+                    issue.issue_type.clone(),
+                    issue.rule_id.clone(),
+                    issue.message.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sort_and_dedup_orders_issues_and_drops_duplicates_from_other_checkers() {
+        let mut issues = vec![
+            issue(2, 0, 3, "重复词", "repeated_words"),
+            issue(1, 5, 8, "可能的拼写错误", "spelling"),
+            issue(1, 0, 4, "标点符号", "punctuation"),
+            // 另一个检查器在同一位置报告的同类问题
+            issue(1, 5, 8, "可能的拼写错误", "common_typos"),
+            issue(2, 0, 3, "重复词", "repeated_words"),
+        ];
+        sort_and_dedup_issues(&mut issues);
+
+        let positions: Vec<(usize, usize, &str)> = issues
+            .iter()
+            .map(|issue| (issue.line_number, issue.start, issue.issue_type.as_str()))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                (1, 0, "标点符号"),
+                (1, 5, "可能的拼写错误"),
+                (2, 0, "重复词")
+            ]
+        );
+    }
+
+    #[test]
+    fn analysis_is_deterministic_and_duplicate_free() {
+        let paragraph = [
+            "This is is a a test of teh checker,, with some errors .",
+            "teh same mistake appears again in in this line",
+            "中文的的标点混用,还有重复重复的词。",
+            "",
+            "Another line with recieve and teh typo.",
+        ];
+        // 行数超过一个并行批次，覆盖跨批次的合并
+        let text = vec![paragraph.join("\n"); 100].join("\n");
+        let config = AnalysisConfig::default();
+
+        let first = analyze_with_config(&text, &config);
+        let second = analyze_with_config(&text, &config);
+        assert_eq!(issue_keys(&first), issue_keys(&second));
+
+        let mut positions: Vec<(usize, usize, usize, &str)> = first
+            .issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.line_number,
+                    issue.start,
+                    issue.end,
+                    issue.issue_type.as_str(),
+                )
+            })
+            .collect();
+        let count = positions.len();
+        positions.dedup();
+        assert_eq!(positions.len(), count, "同一位置的同类问题只应出现一次");
+    }
+
+    #[test]
+    fn misspelling_repeated_across_lines_is_reported_once() {
+        let text = vec!["We recieve the report and teh summary."; 300].join("\n");
+        let config = AnalysisConfig::default();
+
+        for result in [
+            analyze_with_config(&text, &config),
+            analyze_reader_streaming(text.as_bytes(), &config).unwrap(),
+        ] {
+            let mut words: Vec<String> = result
+                .issues
+                .iter()
+                .filter(|issue| issue.rule_id == "spelling")
+                .map(|issue| {
+                    let line = text.lines().nth(issue.line_number - 1).unwrap();
+                    issue_text(line, issue).to_lowercase()
+                })
+                .collect();
+            let count = words.len();
+            words.sort();
+            words.dedup();
+            assert_eq!(words.len(), count, "同一个拼写错误只应提示一次");
+            assert!(result
+                .issues
+                .iter()
+                .filter(|issue| issue.rule_id == "spelling")
+                .all(|issue| issue.line_number == 1));
+        }
+    }
+
+    #[test]
+    fn de_complement_requires_complement_context() {
+        let de_issues = |line: &str| {
+            let mut issues = Vec::new();
+            check_de_usage(line, 0, &mut issues);
+            issues
+                .into_iter()
+                .filter(|issue| issue.replacement.as_deref() == Some("得"))
+                .map(|issue| (issue.start, issue.end))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(de_issues("他做的好事").is_empty());
+        assert!(de_issues("这是他写的好文章").is_empty());
+        assert_eq!(de_issues("他跑的快。"), vec![(2, 3)]);
+        assert_eq!(de_issues("他说的很清楚，大家都懂"), vec![(2, 3)]);
+    }
+
+    #[test]
+    fn paged_analysis_respects_page_size_and_covers_every_issue() {
+        let text = (0..60)
+            .map(|idx| match idx % 4 {
+                0 => format!("Line {} has the report report twice.", idx),
+                1 => "第一段的内容写到这里这里".to_string(),
+                2 => "是这样的，结尾。".to_string(),
+                _ => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let all = analyze_paged(&text, 0, usize::MAX);
+        assert_eq!(all.next_offset_line, None);
+        assert!(all.issues.len() > 3);
+
+        let mut paged = Vec::new();
+        let mut offset = Some(0);
+        while let Some(offset_line) = offset {
+            let page = analyze_paged(&text, offset_line, 3);
+            let page_lines: HashSet<usize> =
+                page.issues.iter().map(|issue| issue.line_number).collect();
+            assert!(page.issues.len() <= 3 || page_lines.len() == 1);
+            if let Some(next) = page.next_offset_line {
+                assert!(next > offset_line);
+                assert!(page.issues.iter().all(|issue| issue.line_number <= next));
+            }
+            paged.extend(page.issues);
+            offset = page.next_offset_line;
+        }
+
+        let keys = |issues: &[TextIssue]| {
+            // 拼写问题只在同一页内去重，分页时各页会各自报告一次
+            issues
+                .iter()
+                .filter(|issue| issue.issue_type != "可能的拼写错误")
+                .map(|issue| {
+                    (
+                        issue.line_number,
+                        issue.start,
+                        issue.end,
+                        issue.issue_type.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys(&paged), keys(&all.issues));
+    }
+
+    #[test]
+    fn severity_overrides_apply_to_reported_issues() {
+        let mut config = AnalysisConfig::default();
+        config
+            .severity_overrides
+            .insert("repeated_words".to_string(), "error".to_string());
+        assert!(config.validate().is_ok());
+
+        let result = analyze_with_config("This has the report report twice.", &config);
+        let repeated: Vec<&TextIssue> = result
+            .issues
+            .iter()
+            .filter(|issue| issue.rule_id == "repeated_words")
+            .collect();
+        assert!(!repeated.is_empty());
+        assert!(repeated.iter().all(|issue| issue.severity == "error"));
+
+        config
+            .severity_overrides
+            .insert("spelling".to_string(), "fatal".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn preview_fixes_applies_only_accepted_issues() {
+        let issue = |start: usize, end: usize, replacement: &str| TextIssue {
+            line_number: 1,
+            start,
+            end,
+            issue_type: "可能的拼写错误".to_string(),
+            message: String::new(),
+            suggestion: String::new(),
+            rule_id: "spelling".to_string(),
+            replacement: Some(replacement.to_string()),
+            confidence: 1.0,
+            severity: "error".to_string(),
+            language: String::new(),
+        };
+        let issues = vec![issue(0, 4, "this"), issue(5, 10, "Grace")];
+
+        let diff = preview_fixes("thsi Grase", issues.clone(), vec![1]);
+        assert!(diff.contains("-thsi Grase\n+thsi Grace\n"));
+        assert_eq!(preview_fixes("thsi Grase", issues, Vec::new()), "");
+    }
+
+    #[test]
+    fn truncation_ends_at_last_complete_line_with_chinese_at_limit() {
+        // 上限正好落在第二行的汉字中间
+        let text = "第一行内容\n第二行有中文\n第三行";
+        assert_eq!(truncate_at_line_boundary(text, 8), "第一行内容");
+        // 上限正好在换行处时保留整行
+        assert_eq!(truncate_at_line_boundary(text, 5), "第一行内容");
+        // 单行超长时退回到字符边界
+        assert_eq!(truncate_at_line_boundary("中文中文中文", 4), "中文中文");
+        assert_eq!(truncate_at_line_boundary(text, 100), text);
+    }
+
+    #[test]
+    fn read_file_content_truncates_at_line_boundary() {
+        let limit = config::current_config().limits.max_text_length;
+        let line = "中文内容与English混排的一行";
+        let count = limit / line.chars().count() + 2;
+        let text = vec![line; count].join("\n");
+        let path =
+            std::env::temp_dir().join(format!("localcheck-truncate-{}.txt", std::process::id()));
+        std::fs::write(&path, &text).unwrap();
+
+        let result = read_file_content(path.to_str().unwrap());
+        let _ = std::fs::remove_file(&path);
+        let content = result.unwrap().content;
+
+        assert!(content.chars().count() <= limit);
+        assert!(content.split('\n').all(|l| l == line));
+    }
+
+    #[test]
+    fn read_file_content_keeps_config_files_unchanged() {
+        let text = "{\n  \"title\": \"Recieve\",\n  \"count\": 3\n}";
+        let path =
+            std::env::temp_dir().join(format!("localcheck-config-{}.json", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+
+        let result = read_file_content(path.to_str().unwrap());
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(result.unwrap().content, text);
+    }
+
+    #[test]
+    fn streaming_reports_absolute_line_numbers_across_chunks() {
+        // 每 37 行有一处重复词，块大小约 100 字节，10000 行会被分成很多块
+        let lines: Vec<String> = (0..10_000)
+            .map(|idx| {
+                if idx % 37 == 5 {
+                    format!("Line {} has the report report in it", idx)
+                } else {
+                    format!("Line {} is plain", idx)
+                }
+            })
+            .collect();
+        let text = lines.join("\n") + "\n";
+
+        let mut config = AnalysisConfig::default();
+        config.limits.max_text_length = 1_000;
+        config.limits.max_issues = 100_000;
+        // 只保留重复词检查，其他规则的结果与行号无关
+        for category in [
+            "spelling",
+            "punctuation",
+            "redundant_expressions",
+            "grammar",
+            "english_articles",
+            "chinese_idioms",
+            "academic_style",
+            "sentence_length",
+            "citation_format",
+            "passive_voice",
+        ] {
+            config.check_options.set(category, false).unwrap();
+        }
+        let result = analyze_reader_streaming(text.as_bytes(), &config).unwrap();
+
+        let repeated: Vec<&TextIssue> = result
+            .issues
+            .iter()
+            .filter(|issue| issue.rule_id == "repeated_words")
+            .collect();
+        let expected: Vec<usize> = (0..10_000)
+            .filter(|idx| idx % 37 == 5)
+            .map(|idx| idx + 1)
+            .collect();
+        assert_eq!(
+            repeated
+                .iter()
+                .map(|issue| issue.line_number)
+                .collect::<Vec<_>>(),
+            expected
+        );
+        for issue in repeated {
+            let line = &lines[issue.line_number - 1];
+            let found: String = line
+                .chars()
+                .skip(issue.start)
+                .take(issue.end - issue.start)
+                .collect();
+            assert_eq!(found, "report report");
+        }
+        assert_eq!(result.stats.get("total_lines"), Some(&10_000));
+    }
+}
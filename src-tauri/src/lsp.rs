@@ -0,0 +1,88 @@
+// 把检查结果转换为 LSP（Language Server Protocol）兼容的诊断结构
+use crate::AnalysisResult;
+use serde::{Deserialize, Serialize};
+
+// 诊断来源，编辑器中显示为问题的来源名称
+const DIAGNOSTIC_SOURCE: &str = "localcheck";
+
+/// LSP 中的位置，行和列均从 0 开始，列按 UTF-16 编码单元计
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LspPosition {
+    pub line: usize,
+    pub character: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LspRange {
+    pub start: LspPosition,
+    pub end: LspPosition,
+}
+
+/// 与 LSP Diagnostic 字段一致，可直接序列化为协议 JSON
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LspDiagnostic {
+    pub range: LspRange,
+    /// 1 = Error，2 = Warning，3 = Information，4 = Hint
+    pub severity: u8,
+    pub source: String,
+    /// 规则 ID
+    pub code: String,
+    pub message: String,
+}
+
+// 把行内的字符下标转换为 UTF-16 列号
+fn char_to_utf16_column(line: &str, char_idx: usize) -> usize {
+    line.chars().take(char_idx).map(char::len_utf16).sum()
+}
+
+fn lsp_severity(severity: &str) -> u8 {
+    match severity {
+        "error" => 1,
+        "info" => 3,
+        _ => 2,
+    }
+}
+
+/// 把分析结果转换为 LSP 诊断列表
+///
+/// 问题的区间按字符计，需要原文才能换算为 UTF-16 列。
+pub fn to_lsp_diagnostics(text: &str, result: &AnalysisResult) -> Vec<LspDiagnostic> {
+    let lines: Vec<&str> = text.lines().collect();
+
+    result
+        .issues
+        .iter()
+        .map(|issue| {
+            let line_idx = issue.line_number.saturating_sub(1);
+            let (start, end) = match lines.get(line_idx) {
+                Some(line) => (
+                    char_to_utf16_column(line, issue.start),
+                    char_to_utf16_column(line, issue.end),
+                ),
+                None => (issue.start, issue.end),
+            };
+            let message = if issue.suggestion.is_empty() {
+                issue.message.clone()
+            } else {
+                format!("{}\n{}", issue.message, issue.suggestion)
+            };
+
+            LspDiagnostic {
+                range: LspRange {
+                    start: LspPosition {
+                        line: line_idx,
+                        character: start,
+                    },
+                    end: LspPosition {
+                        line: line_idx,
+                        character: end,
+                    },
+                },
+                severity: lsp_severity(&issue.severity),
+                source: DIAGNOSTIC_SOURCE.to_string(),
+                code: issue.rule_id.clone(),
+                message,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,96 @@
+// Markdown 感知：找出代码块、行内代码和链接地址，这些内容不参与检查
+use crate::byte_to_char_index;
+use crate::TextIssue;
+use regex::Regex;
+
+// 行内需要跳过的内容：行内代码、链接和图片的地址、尖括号自动链接、裸网址
+const SKIP_PATTERNS: &[&str] = &[
+    r"`[^`]*`",
+    r#"\]\([^)\s]*(?:\s+"[^"]*")?\)"#,
+    r"<(?:https?|mailto|ftp):[^>\s]*>",
+    r"(?i)\b(?:https?|ftp)://[!-~]*[A-Za-z0-9/#=_-]",
+    r"^\s*\[[^\]]+\]:\s*\S.*$",
+];
+
+/// 把跳过区替换为空格后的文本，以及各跳过区的位置
+pub struct MaskedMarkdown {
+    /// 与原文行数、每行字符数都相同的文本
+    pub text: String,
+    /// (行号, 起始字符, 结束字符)，行号从 1 开始
+    skipped: Vec<(usize, usize, usize)>,
+}
+
+impl MaskedMarkdown {
+    /// 问题是否落在跳过区内（与跳过区有重叠即算）
+    pub fn is_skipped(&self, issue: &TextIssue) -> bool {
+        self.skipped.iter().any(|(line_number, start, end)| {
+            *line_number == issue.line_number && issue.start < *end && issue.end > *start
+        })
+    }
+}
+
+/// 用空格遮盖 Markdown 中的代码块、行内代码和链接地址
+///
+/// 遮盖前后字符数不变，在遮盖后的文本上得到的问题位置仍对应原文。标题和链接文字保留。
+pub fn mask_markdown(text: &str) -> MaskedMarkdown {
+    let regexes: Vec<Regex> = SKIP_PATTERNS
+        .iter()
+        .filter_map(|pattern| Regex::new(pattern).ok())
+        .collect();
+    let mut lines = Vec::new();
+    let mut skipped = Vec::new();
+    // 当前所在代码块的围栏符号（``` 或 ~~~）
+    let mut fence: Option<&str> = None;
+
+    for (idx, line) in text.split('\n').enumerate() {
+        let trimmed = line.trim_start();
+        let line_fence = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+
+        // 围栏行和代码块内的行整行跳过
+        let whole_line = match (fence, line_fence) {
+            (Some(open), Some(marker)) if open == marker => {
+                fence = None;
+                true
+            }
+            (Some(_), _) => true,
+            (None, Some(marker)) => {
+                fence = Some(marker);
+                true
+            }
+            (None, None) => false,
+        };
+
+        let mut chars: Vec<char> = line.chars().collect();
+        let mut ranges = Vec::new();
+        if whole_line {
+            ranges.push((0, chars.len()));
+        } else {
+            for re in &regexes {
+                for mat in re.find_iter(line) {
+                    ranges.push((
+                        byte_to_char_index(line, mat.start()),
+                        byte_to_char_index(line, mat.end()),
+                    ));
+                }
+            }
+        }
+
+        for (start, end) in ranges {
+            for c in &mut chars[start..end] {
+                // 保留行尾的 \r，使行的切分与原文一致
+                if *c != '\r' {
+                    *c = ' ';
+                }
+            }
+            skipped.push((idx + 1, start, end));
+        }
+        lines.push(chars.into_iter().collect::<String>());
+    }
+
+    MaskedMarkdown {
+        text: lines.join("\n"),
+        skipped,
+    }
+}
@@ -0,0 +1,349 @@
+// 标点风格统一：把全文标点转换为中文（全角）或英文（半角）风格
+
+use crate::traditional::is_chinese_char;
+
+// 逐字符向后处理时维护前后最近的非空白字符，两个游标都只向前移动，整行只扫描一遍
+struct Neighbours<'a> {
+    chars: &'a [char],
+    // 已扫描到的位置及其之前最近的非空白字符
+    pos: usize,
+    prev: Option<char>,
+    // 最近一次找到的后一个非空白字符的位置
+    next_idx: usize,
+}
+
+impl<'a> Neighbours<'a> {
+    fn new(chars: &'a [char]) -> Self {
+        Neighbours {
+            chars,
+            pos: 0,
+            prev: None,
+            next_idx: 0,
+        }
+    }
+
+    // idx 之前的最近非空白字符，idx 不能小于上一次调用的值
+    fn prev(&mut self, idx: usize) -> Option<char> {
+        while self.pos < idx {
+            let c = self.chars[self.pos];
+            if !c.is_whitespace() {
+                self.prev = Some(c);
+            }
+            self.pos += 1;
+        }
+        self.prev
+    }
+
+    // idx 之后的最近非空白字符，idx 不能小于上一次调用的值
+    fn next(&mut self, idx: usize) -> Option<char> {
+        self.next_idx = self.next_idx.max(idx + 1);
+        while self.next_idx < self.chars.len() && self.chars[self.next_idx].is_whitespace() {
+            self.next_idx += 1;
+        }
+        self.chars.get(self.next_idx).copied()
+    }
+}
+
+// 标记需要原样保留的位置：行内代码和网址
+fn protected_ranges(chars: &[char]) -> Vec<bool> {
+    let mut protected = vec![false; chars.len()];
+
+    // `code`
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '`' {
+            if let Some(len) = chars[i + 1..].iter().position(|c| *c == '`') {
+                for flag in &mut protected[i..=i + 1 + len] {
+                    *flag = true;
+                }
+                i += len + 2;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    // http://... 或 https://... 直到空白为止
+    let text: String = chars.iter().collect();
+    for prefix in ["http://", "https://", "www."] {
+        for (byte_idx, _) in text.match_indices(prefix) {
+            let start = text[..byte_idx].chars().count();
+            let mut end = start;
+            while end < chars.len() && !chars[end].is_whitespace() && !is_chinese_char(chars[end]) {
+                end += 1;
+            }
+            for flag in &mut protected[start..end] {
+                *flag = true;
+            }
+        }
+    }
+
+    protected
+}
+
+// 把一行中的半角标点转换为中文标点（仅在中文上下文中转换）
+fn line_to_chinese(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let protected = protected_ranges(&chars);
+    let mut neighbours = Neighbours::new(&chars);
+    let mut result = String::new();
+    let mut double_quote_open = false;
+    let mut single_quote_open = false;
+    // 记录已转换的左括号，保证右括号与之配对
+    let mut paren_stack: Vec<bool> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if protected[i] {
+            result.push(c);
+            i += 1;
+            continue;
+        }
+
+        let prev = neighbours.prev(i);
+        let next = neighbours.next(i);
+        let prev_cjk = prev.is_some_and(is_chinese_char);
+        let next_cjk = next.is_some_and(is_chinese_char);
+        let chinese_context = prev_cjk || next_cjk;
+
+        // 省略号 ... -> ……
+        if c == '.' && chars.get(i + 1) == Some(&'.') && chars.get(i + 2) == Some(&'.') {
+            let mut end = i;
+            while end < chars.len() && chars[end] == '.' {
+                end += 1;
+            }
+            // 上下文取省略号整体前后的字符
+            if prev_cjk || neighbours.next(end - 1).is_some_and(is_chinese_char) {
+                result.push_str("……");
+            } else {
+                result.extend(&chars[i..end]);
+            }
+            i = end;
+            continue;
+        }
+
+        // 破折号 -- 或单个 — -> ——
+        if (c == '-' && chars.get(i + 1) == Some(&'-')) || c == '—' {
+            let mut end = i;
+            while end < chars.len() && chars[end] == c {
+                end += 1;
+            }
+            let is_dash = if c == '-' { end - i == 2 } else { end - i == 1 };
+            if is_dash && chinese_context {
+                result.push_str("——");
+            } else {
+                result.extend(&chars[i..end]);
+            }
+            i = end;
+            continue;
+        }
+
+        let converted = match c {
+            // 数字之间的逗号和冒号（如 1,000、10:30）不转换
+            ',' | '?' | '!' | ':' | ';'
+                if chinese_context
+                    && !(prev.is_some_and(|p| p.is_ascii_digit())
+                        && next.is_some_and(|n| n.is_ascii_digit())) =>
+            {
+                // 中文标点自带间距，去掉后面多余的空格
+                while chars.get(i + 1).is_some_and(|c| *c == ' ') && next_cjk {
+                    i += 1;
+                }
+                Some(match c {
+                    ',' => '，',
+                    '?' => '？',
+                    '!' => '！',
+                    ':' => '：',
+                    _ => '；',
+                })
+            }
+            // 小数点、缩写和域名中的点不转换
+            '.' if prev_cjk && !chars.get(i + 1).is_some_and(|c| c.is_ascii_alphanumeric()) => {
+                Some('。')
+            }
+            '(' => {
+                let convert = chinese_context;
+                paren_stack.push(convert);
+                if convert {
+                    Some('（')
+                } else {
+                    None
+                }
+            }
+            ')' => match paren_stack.pop() {
+                Some(true) => Some('）'),
+                Some(false) => None,
+                None if prev_cjk => Some('）'),
+                None => None,
+            },
+            '"' if chinese_context || double_quote_open => {
+                double_quote_open = !double_quote_open;
+                Some(if double_quote_open { '“' } else { '”' })
+            }
+            // 英文单词中的撇号（如 don't）不转换
+            '\'' if (chinese_context || single_quote_open)
+                && !(i > 0
+                    && chars[i - 1].is_ascii_alphabetic()
+                    && chars.get(i + 1).is_some_and(|c| c.is_ascii_alphabetic())) =>
+            {
+                single_quote_open = !single_quote_open;
+                Some(if single_quote_open { '‘' } else { '’' })
+            }
+            _ => None,
+        };
+
+        result.push(converted.unwrap_or(c));
+        i += 1;
+    }
+
+    result
+}
+
+// 把一行中的中文标点转换为英文标点（仅在英文上下文中转换）
+fn line_to_english(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let protected = protected_ranges(&chars);
+    let mut neighbours = Neighbours::new(&chars);
+    let mut result = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if protected[i] {
+            result.push(c);
+            i += 1;
+            continue;
+        }
+
+        let prev_cjk = neighbours.prev(i).is_some_and(is_chinese_char);
+        let next_cjk = neighbours.next(i).is_some_and(is_chinese_char);
+        let next = chars.get(i + 1).copied();
+        // 中文句子中的中文标点保持不变
+        if prev_cjk || next_cjk {
+            result.push(c);
+            i += 1;
+            continue;
+        }
+
+        // 后面紧跟文字时补一个空格
+        let needs_space =
+            next.is_some_and(|n| !n.is_whitespace() && !"，。！？；：）”’".contains(n));
+
+        match c {
+            '，' | '。' | '！' | '？' | '；' | '：' => {
+                result.push(match c {
+                    '，' => ',',
+                    '。' => '.',
+                    '！' => '!',
+                    '？' => '?',
+                    '；' => ';',
+                    _ => ':',
+                });
+                if needs_space {
+                    result.push(' ');
+                }
+            }
+            '（' | '“' | '‘' => {
+                // 左括号、左引号前与单词之间补空格
+                if result.ends_with(|p: char| p.is_ascii_alphanumeric() || p == ')') {
+                    result.push(' ');
+                }
+                result.push(match c {
+                    '（' => '(',
+                    '“' => '"',
+                    _ => '\'',
+                });
+            }
+            '）' | '”' | '’' => {
+                result.push(match c {
+                    '）' => ')',
+                    '”' => '"',
+                    _ => '\'',
+                });
+                // It’s 这类撇号后不加空格
+                let is_apostrophe = c == '’' && i > 0 && chars[i - 1].is_ascii_alphabetic();
+                if !is_apostrophe && next.is_some_and(|n| n.is_ascii_alphanumeric()) {
+                    result.push(' ');
+                }
+            }
+            '…' => {
+                // …… 整体转换为 ...
+                while chars.get(i + 1) == Some(&'…') {
+                    i += 1;
+                }
+                result.push_str("...");
+            }
+            '—' => {
+                // —— 转换为英文破折号 —
+                while chars.get(i + 1) == Some(&'—') {
+                    i += 1;
+                }
+                result.push('—');
+            }
+            _ => result.push(c),
+        }
+        i += 1;
+    }
+
+    result
+}
+
+/// 把全文标点统一为中文（"zh"）或英文（"en"）风格
+///
+/// 代码块、行内代码和网址保持不变；未知的 target 原样返回文本。
+pub fn normalize_punctuation(text: &str, target: &str) -> String {
+    let convert: fn(&str) -> String = match target {
+        "zh" => line_to_chinese,
+        "en" => line_to_english,
+        _ => return text.to_string(),
+    };
+
+    let mut in_code_block = false;
+    let mut lines = Vec::new();
+
+    for line in text.split('\n') {
+        // ``` 围起来的代码块原样保留
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            lines.push(line.to_string());
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(line.to_string());
+        } else {
+            lines.push(convert(line));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn punctuation_follows_surrounding_language() {
+        assert_eq!(
+            normalize_punctuation("你好,  世界...再见 -- 明天(见)", "zh"),
+            "你好，世界……再见 —— 明天（见）"
+        );
+        assert_eq!(
+            normalize_punctuation("Hello，world。", "en"),
+            "Hello, world."
+        );
+        assert_eq!(
+            normalize_punctuation("数值 1,000 和 Hi, there", "zh"),
+            "数值 1,000 和 Hi, there"
+        );
+    }
+
+    #[test]
+    fn long_lines_are_converted_in_one_pass() {
+        let line = "中文,".repeat(50_000) + &" ".repeat(50_000) + "结尾";
+        let converted = normalize_punctuation(&line, "zh");
+        assert_eq!(converted.matches('，').count(), 50_000);
+    }
+}
@@ -0,0 +1,286 @@
+use crate::traditional::is_chinese_char;
+use serde::{Deserialize, Serialize};
+
+// 中文长句阈值（字符数）
+const ZH_LONG_SENTENCE_CHARS: usize = 50;
+// 英文长句阈值（单词数）
+const EN_LONG_SENTENCE_WORDS: usize = 25;
+// 英文中视为难词的最小字母数
+const EN_LONG_WORD_LETTERS: usize = 12;
+
+/// 可读性评估结果，中英文使用同一结构
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReadabilityReport {
+    pub language: String,
+    /// 0-100，分数越高越容易阅读；英文为 Flesch Reading Ease
+    pub score: f64,
+    /// 易读 / 适中 / 晦涩
+    pub level: String,
+    pub sentence_count: usize,
+    /// 中文按字符计，英文按单词计
+    pub avg_sentence_length: f64,
+    pub long_sentence_ratio: f64,
+    /// 标点数占字符数的比例
+    pub punctuation_density: f64,
+    /// 中文为生僻字比例，英文为长词比例
+    pub rare_ratio: f64,
+    pub suggestions: Vec<String>,
+}
+
+// 按句末标点切分句子，忽略空句
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = text.chars().collect();
+
+    for (i, &c) in chars.iter().enumerate() {
+        let is_end = match c {
+            '。' | '！' | '？' | '；' | '…' | '\n' => true,
+            // 英文句点后需跟空白或位于末尾，避免把小数点和缩写中的点当作句末
+            '.' | '!' | '?' | ';' => chars.get(i + 1).is_none_or(|next| next.is_whitespace()),
+            _ => false,
+        };
+
+        if is_end {
+            if c != '\n' {
+                current.push(c);
+            }
+            if !current.trim().is_empty() {
+                sentences.push(current.trim().to_string());
+            }
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+
+    if !current.trim().is_empty() {
+        sentences.push(current.trim().to_string());
+    }
+
+    sentences
+}
+
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || "，。！？；：、“”‘’（）《》【】…—".contains(c)
+}
+
+// 常用字以 GB2312 一级字库（3755 个常用汉字）为准，其余汉字视为生僻字
+fn is_common_chinese_char(c: char) -> bool {
+    let mut buf = [0u8; 4];
+    let (bytes, _, had_errors) = encoding_rs::GBK.encode(c.encode_utf8(&mut buf));
+    if had_errors || bytes.len() != 2 {
+        return false;
+    }
+
+    let (high, low) = (bytes[0], bytes[1]);
+    match high {
+        0xB0..=0xD6 => (0xA1..=0xFE).contains(&low),
+        0xD7 => (0xA1..=0xF9).contains(&low),
+        _ => false,
+    }
+}
+
+fn level_for_score(score: f64) -> String {
+    if score >= 70.0 {
+        "易读".to_string()
+    } else if score >= 45.0 {
+        "适中".to_string()
+    } else {
+        "晦涩".to_string()
+    }
+}
+
+fn ratio(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+// 中文可读性：综合平均句长、长句比例、标点密度和生僻字比例
+fn chinese_readability(text: &str) -> ReadabilityReport {
+    let sentences = split_sentences(text);
+
+    let sentence_lengths: Vec<usize> = sentences
+        .iter()
+        .map(|s| {
+            s.chars()
+                .filter(|c| !c.is_whitespace() && !is_punctuation(*c))
+                .count()
+        })
+        .filter(|len| *len > 0)
+        .collect();
+    let sentence_count = sentence_lengths.len();
+    let total_length: usize = sentence_lengths.iter().sum();
+    let long_sentences = sentence_lengths
+        .iter()
+        .filter(|len| **len > ZH_LONG_SENTENCE_CHARS)
+        .count();
+
+    let visible_chars = text.chars().filter(|c| !c.is_whitespace()).count();
+    let punctuation_count = text.chars().filter(|c| is_punctuation(*c)).count();
+    let hanzi: Vec<char> = text.chars().filter(|c| is_chinese_char(*c)).collect();
+    let rare_count = hanzi
+        .iter()
+        .filter(|c| !is_common_chinese_char(**c))
+        .count();
+
+    let avg_sentence_length = ratio(total_length, sentence_count);
+    let long_sentence_ratio = ratio(long_sentences, sentence_count);
+    let punctuation_density = ratio(punctuation_count, visible_chars);
+    let rare_ratio = ratio(rare_count, hanzi.len());
+
+    let mut score = 100.0;
+    score -= (avg_sentence_length - 20.0).max(0.0) * 1.2;
+    score -= long_sentence_ratio * 40.0;
+    score -= rare_ratio * 200.0;
+    // 标点过少说明长串文字缺少停顿，过多则行文零碎
+    if punctuation_density < 0.05 {
+        score -= (0.05 - punctuation_density) * 300.0;
+    } else if punctuation_density > 0.25 {
+        score -= (punctuation_density - 0.25) * 100.0;
+    }
+    let score = score.clamp(0.0, 100.0);
+
+    let mut suggestions = Vec::new();
+    if avg_sentence_length > 30.0 {
+        suggestions.push(format!(
+            "平均句长 {:.1} 字偏长，建议控制在 30 字以内",
+            avg_sentence_length
+        ));
+    }
+    if long_sentence_ratio > 0.2 {
+        suggestions.push(format!(
+            "超过 {} 字的长句占 {:.0}%，建议拆分长句",
+            ZH_LONG_SENTENCE_CHARS,
+            long_sentence_ratio * 100.0
+        ));
+    }
+    if sentence_count > 0 && punctuation_density < 0.05 {
+        suggestions.push("标点偏少，建议在长句中适当使用逗号断开".to_string());
+    }
+    if rare_ratio > 0.05 {
+        suggestions.push(format!(
+            "生僻字占 {:.1}%，建议改用更常见的表达",
+            rare_ratio * 100.0
+        ));
+    }
+
+    ReadabilityReport {
+        language: "zh".to_string(),
+        score,
+        level: level_for_score(score),
+        sentence_count,
+        avg_sentence_length,
+        long_sentence_ratio,
+        punctuation_density,
+        rare_ratio,
+        suggestions,
+    }
+}
+
+// 估算英文单词的音节数：连续元音算一个音节，词尾不发音的 e 不计，至少一个音节
+fn count_syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for c in word.chars() {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    // make、use 的词尾 e 不发音，table、little 的 le 单独成音节
+    if count > 1 && word.ends_with('e') && !word.ends_with("le") && !word.ends_with("ee") {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+// 英文可读性：Flesch Reading Ease，另外给出长句比例和长词比例作为参考
+fn english_readability(text: &str) -> ReadabilityReport {
+    let sentences = split_sentences(text);
+
+    let sentence_lengths: Vec<usize> = sentences
+        .iter()
+        .map(|s| s.split_whitespace().count())
+        .filter(|len| *len > 0)
+        .collect();
+    let sentence_count = sentence_lengths.len();
+    let total_words: usize = sentence_lengths.iter().sum();
+    let long_sentences = sentence_lengths
+        .iter()
+        .filter(|len| **len > EN_LONG_SENTENCE_WORDS)
+        .count();
+
+    let long_words = text
+        .split_whitespace()
+        .filter(|w| w.chars().filter(|c| c.is_alphabetic()).count() >= EN_LONG_WORD_LETTERS)
+        .count();
+    let visible_chars = text.chars().filter(|c| !c.is_whitespace()).count();
+    let punctuation_count = text.chars().filter(|c| is_punctuation(*c)).count();
+
+    let avg_sentence_length = ratio(total_words, sentence_count);
+    let long_sentence_ratio = ratio(long_sentences, sentence_count);
+    let punctuation_density = ratio(punctuation_count, visible_chars);
+    let rare_ratio = ratio(long_words, total_words);
+
+    // Flesch Reading Ease = 206.835 - 1.015 × 平均句长 - 84.6 × 平均每词音节数
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let syllables: usize = words.iter().map(|word| count_syllables(word)).sum();
+    let syllables_per_word = ratio(syllables, words.len());
+    let score = if words.is_empty() {
+        0.0
+    } else {
+        206.835 - 1.015 * ratio(words.len(), sentence_count) - 84.6 * syllables_per_word
+    };
+    let score = score.clamp(0.0, 100.0);
+
+    let mut suggestions = Vec::new();
+    if avg_sentence_length > 20.0 {
+        suggestions.push(format!(
+            "平均句长 {:.1} 词偏长，建议控制在 20 词以内",
+            avg_sentence_length
+        ));
+    }
+    if long_sentence_ratio > 0.2 {
+        suggestions.push(format!(
+            "超过 {} 词的长句占 {:.0}%，建议拆分长句",
+            EN_LONG_SENTENCE_WORDS,
+            long_sentence_ratio * 100.0
+        ));
+    }
+    if rare_ratio > 0.1 || syllables_per_word > 1.7 {
+        suggestions.push("长词较多，建议改用更简单的词汇".to_string());
+    }
+
+    ReadabilityReport {
+        language: "en".to_string(),
+        score,
+        level: level_for_score(score),
+        sentence_count,
+        avg_sentence_length,
+        long_sentence_ratio,
+        punctuation_density,
+        rare_ratio,
+        suggestions,
+    }
+}
+
+/// 计算文本的可读性评分，按语言选择评估方式
+pub fn readability_score(text: &str, language: &str) -> ReadabilityReport {
+    if language == "zh" {
+        chinese_readability(text)
+    } else {
+        english_readability(text)
+    }
+}
@@ -1,4 +1,168 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+
+// 用户自定义的拼写纠正对及其文件的加载状态
+#[derive(Default)]
+struct CustomTypos {
+    // 错词统一以小写存储
+    pairs: HashMap<String, String>,
+    // 文件中格式错误的行，保存时原样写回，避免丢失用户数据
+    invalid_lines: Vec<String>,
+    // 文件存在但无法读取时的错误，此时拒绝保存，以免覆盖原文件
+    load_error: Option<String>,
+}
+
+static CUSTOM_TYPOS: OnceLock<Mutex<CustomTypos>> = OnceLock::new();
+
+// 持久化自定义纠正对的文件名，每行 "错词\t正确词"
+const CUSTOM_TYPOS_FILE: &str = "custom_typos.txt";
+
+// 首次使用时从数据目录加载已保存的纠正对，格式错误的行逐行跳过
+fn custom_typo_store() -> &'static Mutex<CustomTypos> {
+    CUSTOM_TYPOS.get_or_init(|| {
+        let mut store = CustomTypos::default();
+        let path = match crate::storage::data_file(CUSTOM_TYPOS_FILE) {
+            Ok(path) => path,
+            Err(e) => {
+                store.load_error = Some(e);
+                return Mutex::new(store);
+            }
+        };
+        if !path.exists() {
+            return Mutex::new(store);
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                let (pairs, invalid) = parse_typo_lines(&content);
+                for (line_number, _) in &invalid {
+                    eprintln!("自定义纠正对文件第{}行格式错误，已跳过", line_number);
+                }
+                store.pairs = pairs.into_iter().collect();
+                store.invalid_lines = invalid.into_iter().map(|(_, line)| line).collect();
+            }
+            Err(e) => store.load_error = Some(format!("无法读取自定义纠正对文件: {}", e)),
+        }
+        Mutex::new(store)
+    })
+}
+
+// 解析 "错词\t正确词" 格式的文本，空行和 # 开头的注释行跳过
+// 返回解析出的纠正对，以及格式错误的行（行号从 1 开始, 原文）
+fn parse_typo_lines(content: &str) -> (BTreeMap<String, String>, Vec<(usize, String)>) {
+    let mut pairs = BTreeMap::new();
+    let mut invalid = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim_start_matches('\u{feff}').trim_end_matches('\r');
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        match line.split_once('\t') {
+            Some((wrong, correct)) if !wrong.trim().is_empty() && !correct.trim().is_empty() => {
+                pairs.insert(wrong.trim().to_lowercase(), correct.trim().to_string());
+            }
+            _ => invalid.push((idx + 1, line.to_string())),
+        }
+    }
+    (pairs, invalid)
+}
+
+// 把自定义纠正对写入数据目录，原文件中格式错误的行附在末尾
+fn save_custom_typos(store: &CustomTypos) -> Result<(), String> {
+    if let Some(error) = &store.load_error {
+        return Err(format!("{}，为避免覆盖原文件，暂不保存", error));
+    }
+
+    let sorted: BTreeMap<&String, &String> = store.pairs.iter().collect();
+    let mut content: String = sorted
+        .iter()
+        .map(|(wrong, correct)| format!("{}\t{}\n", wrong, correct))
+        .collect();
+    for line in &store.invalid_lines {
+        content.push_str(line);
+        content.push('\n');
+    }
+
+    let path = crate::storage::data_file(CUSTOM_TYPOS_FILE)?;
+    std::fs::write(path, content).map_err(|e| format!("无法保存自定义纠正对: {}", e))
+}
+
+// 添加自定义拼写纠正对，并持久化到数据目录
+pub fn add_custom_typos(pairs: &BTreeMap<String, String>) -> Result<(), String> {
+    let mut store = custom_typo_store()
+        .lock()
+        .map_err(|_| "自定义纠正对已损坏".to_string())?;
+
+    let previous = store.pairs.clone();
+    let mut changed = false;
+    for (wrong, correct) in pairs {
+        let (wrong, correct) = (wrong.trim().to_lowercase(), correct.trim().to_string());
+        if wrong.is_empty() || correct.is_empty() {
+            continue;
+        }
+        if store.pairs.get(&wrong) != Some(&correct) {
+            store.pairs.insert(wrong, correct);
+            changed = true;
+        }
+    }
+
+    if !changed {
+        return Ok(());
+    }
+    // 保存失败时撤销内存中的修改，保持与文件一致
+    let result = save_custom_typos(&store);
+    if result.is_err() {
+        store.pairs = previous;
+    }
+    result
+}
+
+/// 用之前通过 get_custom_typos 取得的内容替换全部纠正对，用于撤销失败的导入
+pub fn restore_custom_typos(pairs: BTreeMap<String, String>) -> Result<(), String> {
+    let mut store = custom_typo_store()
+        .lock()
+        .map_err(|_| "自定义纠正对已损坏".to_string())?;
+    store.pairs = pairs.into_iter().collect();
+    save_custom_typos(&store)
+}
+
+/// 从 "错词\t正确词" 格式的文件加载纠正对，返回加载的条数
+///
+/// 文件中有格式错误的行时逐行报告，不导入任何内容。
+pub fn load_custom_typos(path: &str) -> Result<usize, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("无法读取纠正对文件: {}", e))?;
+    let (pairs, invalid) = parse_typo_lines(&content);
+    if !invalid.is_empty() {
+        let lines: Vec<String> = invalid.iter().map(|(line, _)| line.to_string()).collect();
+        return Err(format!(
+            "第{}行格式错误，应为 \"错词<Tab>正确词\"",
+            lines.join("、")
+        ));
+    }
+    add_custom_typos(&pairs)?;
+    Ok(pairs.len())
+}
+
+// 获取所有自定义拼写纠正对
+pub fn get_custom_typos() -> BTreeMap<String, String> {
+    match custom_typo_store().lock() {
+        Ok(store) => store
+            .pairs
+            .iter()
+            .map(|(wrong, correct)| (wrong.clone(), correct.clone()))
+            .collect(),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+// 在自定义纠正对中查找单词
+fn lookup_custom_typo(word: &str) -> Option<String> {
+    match custom_typo_store().lock() {
+        Ok(store) => store.pairs.get(&word.to_lowercase()).cloned(),
+        Err(_) => None,
+    }
+}
 
 // 创建一个包含常见学术英文拼写错误的字典
 pub fn get_academic_spelling_dict() -> HashMap<&'static str, &'static str> {
@@ -237,6 +401,7 @@ pub fn get_academic_spelling_dict() -> HashMap<&'static str, &'static str> {
 
     // 针对示例中的特定错误
     dict.insert("endowment", "endowment");
+    dict.insert("endowmnt", "endowment");
     dict.insert("enronment", "environment");
     dict.insert("financal", "financial");
     dict.insert("alocation", "allocation");
@@ -416,10 +581,35 @@ pub fn get_academic_spelling_dict() -> HashMap<&'static str, &'static str> {
     dict
 }
 
-// 检查单词是否是拼写错误，如果是则返回正确的拼写
-pub fn check_word_spelling(word: &str) -> Option<&'static str> {
+/// 按原词的大小写形式调整纠正词：全大写、首字母大写或保持纠正词原样
+pub fn match_case(original: &str, correction: &str) -> String {
+    let letters: Vec<char> = original.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() > 1 && letters.iter().all(|c| c.is_uppercase()) {
+        return correction.to_uppercase();
+    }
+    if letters.first().is_some_and(|c| c.is_uppercase()) {
+        return capitalize_first(correction);
+    }
+    correction.to_string()
+}
+
+/// 把单词首字母改为大写
+pub fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+// 检查单词是否是拼写错误（不区分大小写），如果是则返回按原词大小写调整后的正确拼写
+pub fn check_word_spelling(word: &str) -> Option<String> {
     let dict = get_academic_spelling_dict();
-    dict.get(word.to_lowercase().as_str()).copied()
+    let correction = match dict.get(word.to_lowercase().as_str()) {
+        Some(correction) => Some(correction.to_string()),
+        None => lookup_custom_typo(word),
+    };
+    correction.map(|correction| match_case(word, &correction))
 }
 
 // 检查文本中的拼写错误
@@ -473,10 +663,14 @@ pub fn check_text_spelling(text: &str) -> Vec<(String, String, usize, usize)> {
             }
 
             // 检查单词拼写
-            if let Some(correction) = dict.get(clean_word.to_lowercase().as_str()) {
+            let correction = match dict.get(clean_word.to_lowercase().as_str()) {
+                Some(correction) => Some(correction.to_string()),
+                None => lookup_custom_typo(clean_word),
+            };
+            if let Some(correction) = correction {
                 errors.push((
                     clean_word.to_string(),
-                    correction.to_string(),
+                    correction,
                     line_idx,
                     word_pos,
                 ));
@@ -488,3 +682,93 @@ pub fn check_text_spelling(text: &str) -> Vec<(String, String, usize, usize)> {
 
     errors
 }
+
+// 候选词的最大编辑距离
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+// 返回的最大候选数量
+const MAX_SUGGESTIONS: usize = 5;
+
+// 计算两个单词的编辑距离，超过上限时提前返回
+pub fn edit_distance(a: &str, b: &str, limit: usize) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > limit {
+        return limit + 1;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        let mut row_min = curr[0];
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+            row_min = row_min.min(curr[j]);
+        }
+        if row_min > limit {
+            return limit + 1;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+// 为拼写错误的单词生成候选纠正，按历史采纳次数和编辑距离综合排序
+pub fn suggest_corrections(word: &str) -> Vec<String> {
+    let word_lower = word.trim().to_lowercase();
+    if word_lower.is_empty() {
+        return Vec::new();
+    }
+
+    // (候选词, 编辑距离)，错词表中的纠正视为距离 0
+    let mut candidates: HashMap<String, usize> = HashMap::new();
+    if let Some(correction) = check_word_spelling(&word_lower) {
+        candidates.insert(correction, 0);
+    }
+
+    for entry in crate::dictionary::load_dictionary().iter() {
+        if entry.len().abs_diff(word_lower.len()) > MAX_SUGGESTION_DISTANCE || *entry == word_lower
+        {
+            continue;
+        }
+        let distance = edit_distance(&word_lower, entry, MAX_SUGGESTION_DISTANCE);
+        if distance <= MAX_SUGGESTION_DISTANCE {
+            candidates.entry(entry.clone()).or_insert(distance);
+        }
+    }
+
+    // 用户采纳过的候选即使不在词典中也保留
+    let accepted = crate::feedback::accepted_corrections(&word_lower);
+    for chosen in accepted.keys() {
+        candidates
+            .entry(chosen.clone())
+            .or_insert(MAX_SUGGESTION_DISTANCE);
+    }
+
+    let mut ranked: Vec<(String, f64, usize)> = candidates
+        .into_iter()
+        .map(|(candidate, distance)| {
+            let count = accepted.get(&candidate).copied().unwrap_or(0);
+            // 采纳次数越多越靠前，按对数衰减避免个别高频候选完全压过编辑距离
+            let score = distance as f64 - (1.0 + count as f64).ln() * 1.5;
+            (candidate, score, distance)
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        a.1.partial_cmp(&b.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.2.cmp(&b.2))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    ranked
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(candidate, _, _)| candidate)
+        .collect()
+}
@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+// 与 tauri.conf.json 中的 identifier 保持一致
+const APP_IDENTIFIER: &str = "cn.zstar.localcheck";
+
+/// 获取应用的本地数据目录，不存在时自动创建
+pub fn data_dir() -> Result<PathBuf, String> {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+            })
+    };
+
+    let dir = base
+        .ok_or_else(|| "无法确定应用数据目录".to_string())?
+        .join(APP_IDENTIFIER);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建数据目录: {}", e))?;
+
+    Ok(dir)
+}
+
+/// 获取数据目录下指定文件的路径
+pub fn data_file(name: &str) -> Result<PathBuf, String> {
+    Ok(data_dir()?.join(name))
+}
@@ -0,0 +1,237 @@
+use crate::detect_language;
+use crate::AnalysisResult;
+use crate::TextIssue;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+// 超过该长度的行不视为标题（按字符计）
+const MAX_HEADING_CHARS: usize = 40;
+
+/// 单个章节的统计结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SectionResult {
+    pub title: String,
+    /// 起止行号（从 1 开始，包含两端）
+    pub start_line: usize,
+    pub end_line: usize,
+    pub issue_count: usize,
+    /// 按问题类型统计的数量
+    pub issue_stats: HashMap<String, usize>,
+}
+
+// 编号标题中不应出现的句内标点，出现时多半是正文中的列举项
+const SENTENCE_PUNCTUATION: &[char] = &['。', '！', '？', '；', '，', ';', '!', '?', ','];
+
+/// 识别第 idx 行是否为章节标题，返回去掉标记后的标题文本
+///
+/// 支持 Markdown 标题、"第X章/节"、"一、" 以及 "1. / 1.2" 这类编号标题。
+/// 编号标题容易和列举项混淆，因此还要求行内没有句内标点，且前后都是空行（或文档首尾）。
+pub fn detect_heading(lines: &[&str], idx: usize) -> Option<String> {
+    let trimmed = lines.get(idx)?.trim();
+    let (title, numbered) = match_heading(trimmed)?;
+
+    if numbered {
+        let is_blank = |i: usize| lines.get(i).is_none_or(|line| line.trim().is_empty());
+        if trimmed.contains(SENTENCE_PUNCTUATION)
+            || (idx > 0 && !is_blank(idx - 1))
+            || !is_blank(idx + 1)
+        {
+            return None;
+        }
+    }
+
+    Some(title)
+}
+
+/// 只能看到单行时（如逐行检查）判断该行是否像标题，相当于前后都是空行
+pub fn is_heading_line(line: &str) -> bool {
+    detect_heading(&[line], 0).is_some()
+}
+
+// 编号标题的格式："一、"、"1. / 1.2"、"1、"
+const NUMBERED_HEADING_PATTERNS: &[&str] = &[
+    r"^[一二三四五六七八九十]+[、．.]\s*\S",
+    r"^\d+(?:\.\d+)*[\.、]?\s+\S",
+    r"^\d+(?:\.\d+)*、\S",
+];
+
+// 标题格式的正则只在首次使用时编译一次
+static MARKDOWN_HEADING_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+static CHAPTER_HEADING_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+static NUMBERED_HEADING_REGEXES: OnceLock<Vec<Regex>> = OnceLock::new();
+
+// 按行内格式匹配标题，返回标题文本以及是否为需要上下文确认的编号标题
+fn match_heading(trimmed: &str) -> Option<(String, bool)> {
+    if trimmed.is_empty() || trimmed.chars().count() > MAX_HEADING_CHARS + 6 {
+        return None;
+    }
+
+    // Markdown 标题不受句末标点限制
+    let markdown = MARKDOWN_HEADING_REGEX.get_or_init(|| Regex::new(r"^#{1,6}\s+(.+?)\s*#*$").ok());
+    if let Some(caps) = markdown.as_ref().and_then(|re| re.captures(trimmed)) {
+        return Some((caps[1].to_string(), false));
+    }
+
+    // 以句末标点结尾的通常是正文中的列举项，而不是标题
+    if trimmed.chars().count() > MAX_HEADING_CHARS
+        || trimmed.ends_with(['。', '；', ';', '，', ',', '：', ':'])
+        || (trimmed.ends_with('.') && !trimmed.ends_with(".."))
+    {
+        return None;
+    }
+
+    // "第X章" 这类标题不会与列举项混淆
+    let chapter = CHAPTER_HEADING_REGEX
+        .get_or_init(|| Regex::new(r"^第[一二三四五六七八九十百零〇\d]+[章节部分篇]").ok());
+    if chapter.as_ref().is_some_and(|re| re.is_match(trimmed)) {
+        return Some((trimmed.to_string(), false));
+    }
+
+    let numbered = NUMBERED_HEADING_REGEXES.get_or_init(|| {
+        NUMBERED_HEADING_PATTERNS
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect()
+    });
+    if numbered.iter().any(|re| re.is_match(trimmed)) {
+        return Some((trimmed.to_string(), true));
+    }
+
+    None
+}
+
+/// 按章节标题切分文本，并把问题按行号分配到各章节
+///
+/// 第一个标题之前的非空内容作为"前言"单独成节。
+pub fn group_by_section(text: &str, issues: &[TextIssue]) -> Vec<SectionResult> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut sections: Vec<SectionResult> = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        let line_number = idx + 1;
+
+        if let Some(title) = detect_heading(&lines, idx) {
+            if let Some(last) = sections.last_mut() {
+                last.end_line = line_number - 1;
+            }
+            sections.push(SectionResult {
+                title,
+                start_line: line_number,
+                end_line: line_number,
+                issue_count: 0,
+                issue_stats: HashMap::new(),
+            });
+        } else if sections.is_empty() && !line.trim().is_empty() {
+            sections.push(SectionResult {
+                title: "前言".to_string(),
+                start_line: 1,
+                end_line: line_number,
+                issue_count: 0,
+                issue_stats: HashMap::new(),
+            });
+        }
+    }
+
+    if let Some(last) = sections.last_mut() {
+        last.end_line = lines.len().max(last.start_line);
+    }
+
+    for issue in issues {
+        let section = sections
+            .iter_mut()
+            .find(|s| issue.line_number >= s.start_line && issue.line_number <= s.end_line);
+        if let Some(section) = section {
+            section.issue_count += 1;
+            *section
+                .issue_stats
+                .entry(issue.issue_type.clone())
+                .or_insert(0) += 1;
+        }
+    }
+
+    sections
+}
+
+/// 按行组织的单行分析结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StructuredLine {
+    pub line_number: usize,
+    pub text: String,
+    /// 该行的语言（"zh" / "en"），空行为空字符串
+    pub language: String,
+    /// 行类型：heading / body / empty
+    pub line_type: String,
+    pub issues: Vec<TextIssue>,
+}
+
+/// 结构化的分析结果，便于前端按行渲染
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StructuredResult {
+    pub lines: Vec<StructuredLine>,
+    pub stats: HashMap<String, usize>,
+    pub truncated: bool,
+}
+
+/// 把扁平的分析结果按行重组
+pub fn build_structured(text: &str, result: AnalysisResult) -> StructuredResult {
+    let source: Vec<&str> = text.lines().collect();
+    let mut lines: Vec<StructuredLine> = source
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let (language, line_type) = if line.trim().is_empty() {
+                (String::new(), "empty")
+            } else if detect_heading(&source, idx).is_some() {
+                (detect_language(line), "heading")
+            } else {
+                (detect_language(line), "body")
+            };
+
+            StructuredLine {
+                line_number: idx + 1,
+                text: line.to_string(),
+                language,
+                line_type: line_type.to_string(),
+                issues: Vec::new(),
+            }
+        })
+        .collect();
+
+    for issue in result.issues {
+        if let Some(line) = issue
+            .line_number
+            .checked_sub(1)
+            .and_then(|idx| lines.get_mut(idx))
+        {
+            line.issues.push(issue);
+        }
+    }
+
+    StructuredResult {
+        lines,
+        stats: result.stats,
+        truncated: result.truncated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbered_lines_need_heading_context() {
+        let text =
+            "1. 引言\n\n这是正文。\n\n购物清单：\n1. item\n2. item\n\n第二章 方法\n正文内容。";
+        let lines: Vec<&str> = text.lines().collect();
+        let headings: Vec<usize> = (0..lines.len())
+            .filter(|idx| detect_heading(&lines, *idx).is_some())
+            .collect();
+        assert_eq!(headings, vec![0, 8]);
+
+        // 被空行包围但带句内标点的编号行仍是正文
+        let lines = ["", "1. 首先，准备材料", ""];
+        assert!(detect_heading(&lines, 1).is_none());
+    }
+}
@@ -0,0 +1,81 @@
+// 繁体中文的识别与繁简转换
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+// 常用繁体字与对应的简体字，两个字符串按位置一一对应
+// 只收录繁简一一对应的字，转换前后字符数不变，问题位置仍对应原文
+const TRADITIONAL_CHARS: &str = "們這個來時說為國會對學經過發現與後還進動種實體點當開關題問樣\
+    應長頭義氣從機間員業務議論設計處區邊條資產萬則雖門聽見車東無\
+    傳達轉專語認識請讓記讀寫書買賣錢貨價費電話網絡線華樂歡愛興聲\
+    變團結職號準確據將歷驗裡裏麼嗎啓啟覺歲極標級總統維護質顯響獨\
+    熱環續緒織組紀約給紅綠藍顏飛馬鳥魚雞龍鐘鐵銷鋼錯錄陽陰際陳隨\
+    險雜難離靜頁項順須領預額風飯館養驚黨齊筆節範簡類糧緊練縣繼羅\
+    習聞聯腦臉舊藝葉蘇虛蟲補裝製複規視親觀觸訂訊許診試詩該誤課調\
+    談證讚負貢財責貴貿賀賓購贏趕跡輕較輸辦農連週運遠適選遺郵鄉醫\
+    釋針鏡閉閱隊階雙雲頂頻顧飲驅髮鬥麗齒壓廣廠廳張強彈憶戰戲擁擇\
+    擊擴數斷於曆構樹橋檢權歐歸殺決況淨湯滅滿漢潔濟災煙爭爺狀獎畫\
+    異療盡監盤眾礎禮稱穩窮競紙細終綜緣編縮績罰聖脫態懷戶換揮損搶\
+    攝敗敵師帶幫幹庫並亂億僅債傷儘優兒兩內冊劃劇勞勝勢勵協單衛卻\
+    參嚴圖圓園場報壞壯夢夠奮婦媽孫寶審導層屬島帥討鬧術藥鹽壽燈燒\
+    爐牆獲畢蘭衝覽詞譯豐貝賽趨軍軟輛辭遷醜鍵陸隱韓頓飽魯鹹黃";
+const SIMPLIFIED_CHARS: &str = "们这个来时说为国会对学经过发现与后还进动种实体点当开关题问样\
+    应长头义气从机间员业务议论设计处区边条资产万则虽门听见车东无\
+    传达转专语认识请让记读写书买卖钱货价费电话网络线华乐欢爱兴声\
+    变团结职号准确据将历验里里么吗启启觉岁极标级总统维护质显响独\
+    热环续绪织组纪约给红绿蓝颜飞马鸟鱼鸡龙钟铁销钢错录阳阴际陈随\
+    险杂难离静页项顺须领预额风饭馆养惊党齐笔节范简类粮紧练县继罗\
+    习闻联脑脸旧艺叶苏虚虫补装制复规视亲观触订讯许诊试诗该误课调\
+    谈证赞负贡财责贵贸贺宾购赢赶迹轻较输办农连周运远适选遗邮乡医\
+    释针镜闭阅队阶双云顶频顾饮驱发斗丽齿压广厂厅张强弹忆战戏拥择\
+    击扩数断于历构树桥检权欧归杀决况净汤灭满汉洁济灾烟争爷状奖画\
+    异疗尽监盘众础礼称稳穷竞纸细终综缘编缩绩罚圣脱态怀户换挥损抢\
+    摄败敌师带帮干库并乱亿仅债伤尽优儿两内册划剧劳胜势励协单卫却\
+    参严图圆园场报坏壮梦够奋妇妈孙宝审导层属岛帅讨闹术药盐寿灯烧\
+    炉墙获毕兰冲览词译丰贝赛趋军软辆辞迁丑键陆隐韩顿饱鲁咸黄";
+
+// 一行中繁体字占中文字符的比例达到该值时视为繁体
+const TRADITIONAL_RATIO: usize = 10;
+
+static CONVERSION_TABLE: OnceLock<HashMap<char, char>> = OnceLock::new();
+
+fn conversion_table() -> &'static HashMap<char, char> {
+    CONVERSION_TABLE.get_or_init(|| {
+        TRADITIONAL_CHARS
+            .chars()
+            .zip(SIMPLIFIED_CHARS.chars())
+            .collect()
+    })
+}
+
+/// 判断是否为汉字，包括扩展 A 区、兼容汉字和扩展 B 区以后的字符
+pub fn is_chinese_char(c: char) -> bool {
+    matches!(c,
+        '\u{4e00}'..='\u{9fff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{20000}'..='\u{2ebef}'
+    )
+}
+
+/// 判断文本是否为繁体中文：繁体字至少占汉字的十分之一
+pub fn is_traditional(text: &str) -> bool {
+    let table = conversion_table();
+    let mut chinese = 0;
+    let mut traditional = 0;
+    for c in text.chars().filter(|c| is_chinese_char(*c)) {
+        chinese += 1;
+        if table.contains_key(&c) {
+            traditional += 1;
+        }
+    }
+
+    traditional > 0 && traditional * TRADITIONAL_RATIO >= chinese.max(TRADITIONAL_RATIO)
+}
+
+/// 把常用繁体字转换为简体，逐字替换，字符数不变
+pub fn to_simplified(text: &str) -> String {
+    let table = conversion_table();
+    text.chars()
+        .map(|c| table.get(&c).copied().unwrap_or(c))
+        .collect()
+}
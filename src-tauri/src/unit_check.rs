@@ -0,0 +1,162 @@
+// 数字与单位之间的空格规范
+use crate::byte_to_char_index;
+use crate::TextIssue;
+use crate::MAX_ISSUES;
+use regex::Regex;
+
+// 英文（SI 等）单位，按配置的风格决定与数字之间是否加空格
+// 不收录 A、K 等单个大写字母和 s，避免把 3A、4K、1990s 这类写法当作数字加单位
+const ENGLISH_UNITS: &[&str] = &[
+    "kg", "mg", "km", "cm", "mm", "nm", "μm", "ms", "min", "Hz", "kHz", "MHz", "GHz", "KB", "MB",
+    "GB", "TB", "mV", "kV", "mA", "kW", "MW", "kJ", "Pa", "kPa", "MPa", "mL", "ml", "dB", "mol",
+    "g", "m", "h",
+];
+
+// 中文单位和百分号等符号前不加空格
+const CHINESE_UNITS: &[&str] = &[
+    "小时",
+    "分钟",
+    "公里",
+    "千米",
+    "千克",
+    "公斤",
+    "平方米",
+    "立方米",
+    "年",
+    "月",
+    "日",
+    "天",
+    "时",
+    "分",
+    "秒",
+    "个",
+    "米",
+    "克",
+    "吨",
+    "元",
+    "人",
+    "次",
+    "岁",
+    "倍",
+    "件",
+    "名",
+    "项",
+    "种",
+    "位",
+    "条",
+    "篇",
+    "页",
+    "章",
+    "节",
+    "万",
+    "亿",
+    "%",
+    "‰",
+    "℃",
+    "°",
+];
+
+// 把单位列表拼成正则分组，长的排在前面优先匹配
+fn unit_alternation(units: &[&str]) -> String {
+    let mut units: Vec<&str> = units.to_vec();
+    units.sort_by_key(|unit| std::cmp::Reverse(unit.chars().count()));
+    units
+        .iter()
+        .map(|unit| regex::escape(unit))
+        .collect::<Vec<String>>()
+        .join("|")
+}
+
+// 数字前面紧挨字母、数字或点时是型号、版本号等的一部分，如 H2O、mp3、v1.2
+fn is_part_of_token(line: &str, start: usize) -> bool {
+    line[..start]
+        .chars()
+        .next_back()
+        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+}
+
+// Check spacing between numbers and units
+//
+// space_before_english_unit 为 true 时要求 "50 kg"，为 false 时要求 "50kg"；
+// 中文单位和 % 等符号前始终不加空格，如 "10年"、"3%"。
+pub fn check_number_unit_spacing(
+    line: &str,
+    line_idx: usize,
+    issues: &mut Vec<TextIssue>,
+    space_before_english_unit: bool,
+) {
+    // Skip if we've already found too many issues
+    if issues.len() >= MAX_ISSUES {
+        return;
+    }
+
+    let english_regex = match Regex::new(&format!(
+        r"(\d+(?:\.\d+)?)([ \t]*)({})(?:[^A-Za-z0-9μ]|$)",
+        unit_alternation(ENGLISH_UNITS)
+    )) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+    let chinese_regex = match Regex::new(&format!(
+        r"(\d+(?:\.\d+)?)([ \t]+)({})",
+        unit_alternation(CHINESE_UNITS)
+    )) {
+        Ok(re) => re,
+        Err(_) => return,
+    };
+
+    // (数字与单位的捕获, 是否应加空格)
+    let mut found = Vec::new();
+    for caps in english_regex.captures_iter(line) {
+        let has_space = caps.get(2).is_some_and(|gap| !gap.is_empty());
+        if has_space != space_before_english_unit {
+            found.push((caps, space_before_english_unit));
+        }
+    }
+    for caps in chinese_regex.captures_iter(line) {
+        found.push((caps, false));
+    }
+
+    for (caps, want_space) in found {
+        let (number, unit) = match (caps.get(1), caps.get(3)) {
+            (Some(number), Some(unit)) => (number, unit),
+            _ => continue,
+        };
+        if is_part_of_token(line, number.start()) {
+            continue;
+        }
+
+        let original = &line[number.start()..unit.end()];
+        let replacement = if want_space {
+            format!("{} {}", number.as_str(), unit.as_str())
+        } else {
+            format!("{}{}", number.as_str(), unit.as_str())
+        };
+        let message = if want_space {
+            format!("数字与英文单位之间应加空格: {}", original)
+        } else if ENGLISH_UNITS.contains(&unit.as_str()) {
+            format!("数字与英文单位之间不应有空格: {}", original)
+        } else {
+            format!("数字与中文单位或符号之间不应有空格: {}", original)
+        };
+
+        issues.push(TextIssue {
+            line_number: line_idx + 1,
+            start: byte_to_char_index(line, number.start()),
+            end: byte_to_char_index(line, unit.end()),
+            issue_type: "数字单位间距".to_string(),
+            message,
+            suggestion: format!("建议修改为: '{}'", replacement),
+            rule_id: "number_unit_spacing".to_string(),
+            replacement: Some(replacement),
+            confidence: 0.8,
+            severity: "info".to_string(),
+            language: String::new(),
+        });
+
+        // Stop if we've found too many issues
+        if issues.len() >= MAX_ISSUES {
+            return;
+        }
+    }
+}
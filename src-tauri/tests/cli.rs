@@ -0,0 +1,57 @@
+// 命令行工具的输出要能直接被管道解析，加载词典等资源时的日志不能混入 stdout
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+// 把内容写入临时文件，用 localcheck-cli 检查并返回进程输出
+fn run_cli(name: &str, content: &str, format: &str) -> Output {
+    let path: PathBuf = std::env::temp_dir().join(format!(
+        "localcheck-cli-{}-{}.txt",
+        std::process::id(),
+        name
+    ));
+    std::fs::write(&path, content).expect("无法写入临时文件");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_localcheck-cli"))
+        .args(["--format", format])
+        .arg(&path)
+        .output()
+        .expect("无法运行 localcheck-cli");
+
+    let _ = std::fs::remove_file(&path);
+    output
+}
+
+#[test]
+fn json_output_is_parseable() {
+    let output = run_cli(
+        "json",
+        "This is is a test.\n做为一个例子，这里有错别字。\n",
+        "json",
+    );
+    assert!(matches!(output.status.code(), Some(0) | Some(1)));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout 不是 UTF-8");
+    let result: localcheck_lib::AnalysisResult =
+        serde_json::from_str(&stdout).expect("stdout 不是合法的分析结果 JSON");
+    assert_eq!(result.stats.get("total_lines"), Some(&2));
+}
+
+#[test]
+fn csv_output_starts_with_header() {
+    let output = run_cli("csv", "This is is a test.\n", "csv");
+    assert!(matches!(output.status.code(), Some(0) | Some(1)));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout 不是 UTF-8");
+    let mut lines = stdout.lines();
+    assert_eq!(
+        lines.next(),
+        Some("\u{feff}line_number,start,end,issue_type,message,suggestion")
+    );
+    // 表头之后每条记录都以行号开头，没有混入日志
+    for line in lines.filter(|line| !line.is_empty()) {
+        assert!(line
+            .split(',')
+            .next()
+            .is_some_and(|field| field.parse::<usize>().is_ok()));
+    }
+}
@@ -11,12 +11,24 @@ interface TextIssue {
   issue_type: string;
   message: string;
   suggestion: string;
+  rule_id?: string;
+  replacement?: string | null;
+  confidence?: number;
+  severity?: "error" | "warning" | "info";
+  language?: string;
 }
 
 interface AnalysisResult {
   issues: TextIssue[];
   stats: Record<string, number>;
   truncated: boolean;
+  next_offset_line?: number | null;
+  issue_counts?: Record<string, number>;
+}
+
+interface FixResult {
+  new_text: string;
+  applied: [number, number, string][];
 }
 
 interface AnalysisProgress {
@@ -43,10 +55,14 @@ function App() {
   const [error, setError] = useState<string | null>(null);
   const [isLargeFile, setIsLargeFile] = useState<boolean>(false);
   const [ignoredIssues, setIgnoredIssues] = useState<Set<number>>(new Set());
+  // 批量修复的记录，按应用顺序保存，用于逐步撤销
+  const [fixHistory, setFixHistory] = useState<FixResult["applied"][]>([]);
   const [selectedFilter, setSelectedFilter] = useState<string>("all");
+  const [selectedSeverity, setSelectedSeverity] = useState<string>("all");
   const [showAboutDialog, setShowAboutDialog] = useState<boolean>(false);
   const [analysisProgress, setAnalysisProgress] = useState<AnalysisProgress | null>(null);
   const [currentAnalysisId, setCurrentAnalysisId] = useState<string | null>(null);
+  const [passiveVoiceEnabled, setPassiveVoiceEnabled] = useState<boolean>(true);
   const editorRef = useRef<HTMLDivElement>(null);
   const textareaRef = useRef<HTMLTextAreaElement>(null);
 
@@ -88,8 +104,22 @@ function App() {
     };
 
     setupAsyncListeners().catch(console.error);
+
+    invoke<boolean>("is_rule_enabled", { ruleId: "passive_voice" })
+      .then(setPassiveVoiceEnabled)
+      .catch(console.error);
   }, []);
 
+  // 开关被动语态提示，下次分析时生效
+  const handlePassiveVoiceToggle = async (enabled: boolean) => {
+    try {
+      await invoke("set_rule_enabled", { ruleId: "passive_voice", enabled });
+      setPassiveVoiceEnabled(enabled);
+    } catch (error) {
+      console.error("切换被动语态提示时出错:", error);
+    }
+  };
+
   // 分析文本（支持异步和同步模式）
   const analyzeText = async () => {
     if (!text.trim() && !filePath) return;
@@ -101,8 +131,10 @@ function App() {
     try {
       // 检查文本长度，决定使用同步还是异步分析
       const shouldUseAsync = text.length > 10000 || (isLargeFile && filePath);
+      // Markdown 文件跳过代码块和链接地址，使用专门的分析命令
+      const isMarkdown = fileName.toLowerCase().endsWith(".md");
 
-      if (shouldUseAsync && !isLargeFile) {
+      if (shouldUseAsync && !isLargeFile && !isMarkdown) {
         // 使用异步分析处理大文本
         console.log("Using async analysis for large text:", text.substring(0, 50) + "...");
         const analysisId = await invoke<string>("analyze_text_async", { text });
@@ -119,7 +151,7 @@ function App() {
       } else {
         // 使用同步分析处理小文本
         console.log("Using sync analysis for small text:", text.substring(0, 50) + "...");
-        const result = await invoke<AnalysisResult>("analyze_text", { text });
+        const result = await invoke<AnalysisResult>(isMarkdown ? "analyze_markdown" : "analyze_text", { text });
         setAnalysisResult(result);
         setIgnoredIssues(new Set());
         setSelectedFilter("all");
@@ -149,7 +181,8 @@ function App() {
       const selected = await open({
         multiple: false,
         filters: [
-          { name: "文本文件", extensions: ["txt", "md", "doc", "docx"] },
+          { name: "文本文件", extensions: ["txt", "md", "doc", "docx", "pdf", "odt", "rtf"] },
+          { name: "配置文件", extensions: ["json", "yaml", "yml"] },
           { name: "所有文件", extensions: ["*"] }
         ]
       });
@@ -159,8 +192,11 @@ function App() {
         setFilePath(selected);
         
         try {
-          const content = await invoke<string>("read_file_content", { path: selected });
-          console.log("File content length:", content.length);
+          const { content, encoding } = await invoke<{ content: string; encoding: string | null }>(
+            "read_file_content",
+            { path: selected }
+          );
+          console.log("File content length:", content.length, "encoding:", encoding);
           
           // 提取文件名
           const pathParts = selected.split(/[/\\]/);
@@ -176,6 +212,7 @@ function App() {
           } else {
             setText(content);
           }
+          setFixHistory([]);
           
           // 自动分析
           setTimeout(() => {
@@ -196,6 +233,7 @@ function App() {
   const handleTextChange = (e: React.ChangeEvent<HTMLTextAreaElement>) => {
     setText(e.target.value);
     setIsLargeFile(false); // 用户编辑了文本，不再是大文件模式
+    setFixHistory([]); // 手动编辑后修复记录的位置不再可靠
   };
 
   // 点击问题项，高亮对应文本并优化滚动位置
@@ -360,11 +398,18 @@ function App() {
           // 执行文本替换
           const newText = text.substring(0, start) + replacement + text.substring(end);
           setText(newText);
+          setFixHistory([]);
 
           // 将问题标记为已忽略（因为已经修复）
           setIgnoredIssues(prev => new Set([...prev, index]));
 
           console.log(`Applied suggestion: "${originalText}" -> "${replacement}"`);
+
+          // 记录拼写纠正的采纳情况，用于个性化候选排序
+          if (issue.rule_id === "spelling") {
+            invoke("record_accepted_correction", { wrong: originalText, chosen: replacement })
+              .catch(error => console.error("记录采纳的纠正时出错:", error));
+          }
         } else {
           // 如果无法自动修复，只是忽略问题
           setIgnoredIssues(prev => new Set([...prev, index]));
@@ -383,6 +428,100 @@ function App() {
     setIgnoredIssues(prev => new Set([...prev, index]));
   };
 
+  // 标记为误报：记录到本地日志并忽略该问题
+  const handleReportFalsePositive = async (issue: TextIssue, index: number) => {
+    try {
+      await invoke("report_false_positive", { issue });
+    } catch (error) {
+      console.error("记录误报时出错:", error);
+    }
+    setIgnoredIssues(prev => new Set([...prev, index]));
+  };
+
+  // 从拼写问题的提示中取出被标记的单词
+  const getIssueWord = (issue: TextIssue) => {
+    const match = issue.message.match(/'([^']+)'/);
+    return match ? match[1] : null;
+  };
+
+  // 忽略同一单词的所有拼写问题
+  const ignoreSameWordIssues = (word: string) => {
+    if (!analysisResult) return;
+    const sameWord = analysisResult.issues
+      .map((other, i) => ({ other, i }))
+      .filter(({ other }) => other.rule_id === "spelling"
+        && getIssueWord(other)?.toLowerCase() === word.toLowerCase())
+      .map(({ i }) => i);
+    setIgnoredIssues(prev => new Set([...prev, ...sameWord]));
+  };
+
+  // 把拼写问题中的单词加入用户词典，并忽略同一单词的其他问题
+  const handleAddToDictionary = async (issue: TextIssue) => {
+    const word = getIssueWord(issue);
+    if (!word) return;
+
+    try {
+      await invoke("add_user_words", { words: [word] });
+    } catch (error) {
+      console.error("加入词典时出错:", error);
+      return;
+    }
+    ignoreSameWordIssues(word);
+  };
+
+  // 仅在本次会话中忽略该单词，重启应用后恢复检查
+  const handleIgnoreWordForSession = async (issue: TextIssue) => {
+    const word = getIssueWord(issue);
+    if (!word) return;
+
+    try {
+      await invoke("ignore_word_for_session", { word });
+    } catch (error) {
+      console.error("忽略单词时出错:", error);
+      return;
+    }
+    ignoreSameWordIssues(word);
+  };
+
+  // 一键应用所有可自动修复的建议，然后重新分析修改后的文本
+  const handleApplyAllFixes = async () => {
+    if (!analysisResult) return;
+    const issues = analysisResult.issues.filter((_, index) => !ignoredIssues.has(index));
+
+    try {
+      const { new_text: fixed, applied } = await invoke<FixResult>("apply_all_fixes", { text, issues });
+      if (fixed === text) return;
+      setText(fixed);
+      setFixHistory(prev => [...prev, applied]);
+
+      const result = await invoke<AnalysisResult>("analyze_text", { text: fixed });
+      setAnalysisResult(result);
+      setIgnoredIssues(new Set());
+      setSelectedFilter("all");
+    } catch (error) {
+      console.error("批量修复时出错:", error);
+    }
+  };
+
+  // 撤销最近一次批量修复，然后重新分析还原后的文本
+  const handleUndoFix = async () => {
+    const applied = fixHistory[fixHistory.length - 1];
+    if (!applied) return;
+
+    try {
+      const reverted = await invoke<string>("revert_fixes", { text, applied });
+      setText(reverted);
+      setFixHistory(prev => prev.slice(0, -1));
+
+      const result = await invoke<AnalysisResult>("analyze_text", { text: reverted });
+      setAnalysisResult(result);
+      setIgnoredIssues(new Set());
+      setSelectedFilter("all");
+    } catch (error) {
+      console.error("撤销修复时出错:", error);
+    }
+  };
+
   // 清除所有忽略的问题
   const handleClearIgnored = () => {
     setIgnoredIssues(new Set());
@@ -402,7 +541,8 @@ function App() {
     return analysisResult.issues
       .map((issue, index) => ({ issue, index }))
       .filter(({ index }) => !ignoredIssues.has(index))
-      .filter(({ issue }) => selectedFilter === "all" || issue.issue_type === selectedFilter);
+      .filter(({ issue }) => selectedFilter === "all" || issue.issue_type === selectedFilter)
+      .filter(({ issue }) => selectedSeverity === "all" || (issue.severity || "warning") === selectedSeverity);
   };
 
   // 处理筛选器变化
@@ -500,6 +640,14 @@ function App() {
           )}
         </div>
         <div>
+          <label className="rule-toggle">
+            <input
+              type="checkbox"
+              checked={passiveVoiceEnabled}
+              onChange={(e) => handlePassiveVoiceToggle(e.target.checked)}
+            />
+            被动语态提示
+          </label>
           <button
             className="button button-info"
             onClick={() => setShowAboutDialog(true)}
@@ -543,6 +691,16 @@ function App() {
                     <option key={type} value={type}>{type}</option>
                   ))}
                 </select>
+                <select
+                  className="filter-select"
+                  value={selectedSeverity}
+                  onChange={(e) => setSelectedSeverity(e.target.value)}
+                >
+                  <option value="all">全部级别</option>
+                  <option value="error">错误</option>
+                  <option value="warning">警告</option>
+                  <option value="info">提示</option>
+                </select>
               </div>
             )}
           </div>
@@ -570,6 +728,10 @@ function App() {
                     <span>检测到的问题:</span>
                     <span>{analysisResult.issues.length}{analysisResult.truncated ? "+" : ""}</span>
                   </div>
+                  <div className="stats-item">
+                    <span>中文/英文问题:</span>
+                    <span>{analysisResult.stats.zh_issues || 0} / {analysisResult.stats.en_issues || 0}</span>
+                  </div>
                   {selectedFilter !== "all" && (
                     <div className="stats-item">
                       <span>当前筛选:</span>
@@ -578,6 +740,23 @@ function App() {
                   )}
                 </div>
 
+                {analysisResult.issue_counts && Object.keys(analysisResult.issue_counts).length > 0 && (
+                  <div className="issue-summary">
+                    {Object.entries(analysisResult.issue_counts)
+                      .sort((a, b) => b[1] - a[1])
+                      .map(([type, count]) => (
+                        <div
+                          key={type}
+                          className={`issue-summary-item ${selectedFilter === type ? "active" : ""}`}
+                          onClick={() => handleFilterChange(selectedFilter === type ? "all" : type)}
+                        >
+                          <span>{type}</span>
+                          <span>{count}</span>
+                        </div>
+                      ))}
+                  </div>
+                )}
+
                 {analysisResult.truncated && (
                   <div className="warning-message">
                     注意: 文本过长或问题过多，仅显示部分分析结果。
@@ -590,9 +769,28 @@ function App() {
                   </div>
                 )}
 
+                {fixHistory.length > 0 && (
+                  <div className="filter-info">
+                    <button
+                      className="button button-small button-secondary"
+                      onClick={handleUndoFix}
+                    >
+                      撤销修复
+                    </button>
+                  </div>
+                )}
+
                 {analysisResult.issues.length > 0 ? (
                   <>
                     <div className="filter-info">
+                      {!isLargeFile && (
+                        <button
+                          className="button button-small"
+                          onClick={handleApplyAllFixes}
+                        >
+                          全部修复
+                        </button>
+                      )}
                       {ignoredIssues.size > 0 && (
                         <div className="ignored-info">
                           <span>已忽略 {ignoredIssues.size} 个问题</span>
@@ -619,7 +817,16 @@ function App() {
                     {getFilteredIssues().map(({ issue, index }) => (
                       <div
                         key={index}
-                        className="issue-item"
+                        className={`issue-item severity-${issue.severity || "warning"}`}
+                        onContextMenu={(e) => {
+                          // 右键拼写问题可直接把单词加入词典
+                          if (issue.rule_id !== "spelling") return;
+                          e.preventDefault();
+                          const word = getIssueWord(issue);
+                          if (word && window.confirm(`将 '${word}' 加入词典？`)) {
+                            handleAddToDictionary(issue);
+                          }
+                        }}
                       >
                         <div
                           className="issue-content"
@@ -653,6 +860,40 @@ function App() {
                           >
                             忽略
                           </button>
+                          <button
+                            className="button button-small button-ignore"
+                            onClick={(e) => {
+                              e.stopPropagation();
+                              handleReportFalsePositive(issue, index);
+                            }}
+                            title="标记为误报"
+                          >
+                            误报
+                          </button>
+                          {issue.rule_id === "spelling" && (
+                            <button
+                              className="button button-small button-ignore"
+                              onClick={(e) => {
+                                e.stopPropagation();
+                                handleAddToDictionary(issue);
+                              }}
+                              title="把该单词加入用户词典"
+                            >
+                              加入词典
+                            </button>
+                          )}
+                          {issue.rule_id === "spelling" && (
+                            <button
+                              className="button button-small button-ignore"
+                              onClick={(e) => {
+                                e.stopPropagation();
+                                handleIgnoreWordForSession(issue);
+                              }}
+                              title="在本次使用中忽略该单词"
+                            >
+                              本次忽略
+                            </button>
+                          )}
                         </div>
                       </div>
                     ))}